@@ -0,0 +1,171 @@
+-- Versão SQLite de migration.sql (mesmo esquema, tipos do dialeto)
+-- Migração para sistema de streaming de vídeos
+-- Criando tabelas para um sistema tipo Netflix
+
+-- Tabela de usuários
+CREATE TABLE users (
+    id VARCHAR(36) PRIMARY KEY,
+    email VARCHAR(255) UNIQUE NOT NULL,
+    password_hash VARCHAR(255) NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    profile_picture_url VARCHAR(500),
+    subscription_status VARCHAR(50) DEFAULT 'active',
+    subscription_expires_at DATETIME,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Tabela de categorias/genres
+CREATE TABLE categories (
+    id VARCHAR(36) PRIMARY KEY,
+    name VARCHAR(100) NOT NULL,
+    description VARCHAR(500),
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Tabela de vídeos
+CREATE TABLE videos (
+    id VARCHAR(36) PRIMARY KEY,
+    title VARCHAR(255) NOT NULL,
+    description VARCHAR(1000),
+    duration_seconds INT NOT NULL,
+    release_year INT,
+    rating REAL DEFAULT 0.0,
+    thumbnail_url VARCHAR(500),
+    video_url VARCHAR(500),
+    trailer_url VARCHAR(500),
+    is_featured BOOLEAN DEFAULT 0,
+    is_available BOOLEAN DEFAULT 1,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Tabela de relacionamento vídeo-categoria
+CREATE TABLE video_categories (
+    id VARCHAR(36) PRIMARY KEY,
+    video_id VARCHAR(36) NOT NULL,
+    category_id VARCHAR(36) NOT NULL,
+    FOREIGN KEY (video_id) REFERENCES videos(id),
+    FOREIGN KEY (category_id) REFERENCES categories(id)
+);
+
+-- Tabela de atores
+CREATE TABLE actors (
+    id VARCHAR(36) PRIMARY KEY,
+    name VARCHAR(255) NOT NULL,
+    biography VARCHAR(1000),
+    birth_date DATE,
+    profile_picture_url VARCHAR(500),
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Tabela de relacionamento vídeo-ator
+CREATE TABLE video_actors (
+    id VARCHAR(36) PRIMARY KEY,
+    video_id VARCHAR(36) NOT NULL,
+    actor_id VARCHAR(36) NOT NULL,
+    role_name VARCHAR(255),
+    is_lead BOOLEAN DEFAULT 0,
+    FOREIGN KEY (video_id) REFERENCES videos(id),
+    FOREIGN KEY (actor_id) REFERENCES actors(id)
+);
+
+-- Tabela de diretores
+CREATE TABLE directors (
+    id VARCHAR(36) PRIMARY KEY,
+    name VARCHAR(255) NOT NULL,
+    biography VARCHAR(1000),
+    birth_date DATE,
+    profile_picture_url VARCHAR(500),
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Tabela de relacionamento vídeo-diretor
+CREATE TABLE video_directors (
+    id VARCHAR(36) PRIMARY KEY,
+    video_id VARCHAR(36) NOT NULL,
+    director_id VARCHAR(36) NOT NULL,
+    FOREIGN KEY (video_id) REFERENCES videos(id),
+    FOREIGN KEY (director_id) REFERENCES directors(id)
+);
+
+-- Tabela de grupos de acesso
+CREATE TABLE access_groups (
+    id VARCHAR(36) PRIMARY KEY,
+    name VARCHAR(100) NOT NULL,
+    description VARCHAR(500),
+    permissions TEXT, -- JSON com permissões
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Tabela de relacionamento usuário-grupo de acesso
+CREATE TABLE users_access_groups (
+    id VARCHAR(36) PRIMARY KEY,
+    user_id VARCHAR(36) NOT NULL,
+    access_group_id VARCHAR(36) NOT NULL,
+    assigned_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    assigned_by VARCHAR(36),
+    FOREIGN KEY (user_id) REFERENCES users(id),
+    FOREIGN KEY (access_group_id) REFERENCES access_groups(id),
+    FOREIGN KEY (assigned_by) REFERENCES users(id)
+);
+
+-- Tabela de histórico de visualização
+CREATE TABLE watch_history (
+    id VARCHAR(36) PRIMARY KEY,
+    user_id VARCHAR(36) NOT NULL,
+    video_id VARCHAR(36) NOT NULL,
+    watched_seconds INT DEFAULT 0,
+    is_completed BOOLEAN DEFAULT 0,
+    last_watched_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (user_id) REFERENCES users(id),
+    FOREIGN KEY (video_id) REFERENCES videos(id)
+);
+
+-- Tabela de favoritos
+CREATE TABLE favorites (
+    id VARCHAR(36) PRIMARY KEY,
+    user_id VARCHAR(36) NOT NULL,
+    video_id VARCHAR(36) NOT NULL,
+    added_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (user_id) REFERENCES users(id),
+    FOREIGN KEY (video_id) REFERENCES videos(id),
+    UNIQUE(user_id, video_id)
+);
+
+-- Tabela de avaliações
+CREATE TABLE ratings (
+    id VARCHAR(36) PRIMARY KEY,
+    user_id VARCHAR(36) NOT NULL,
+    video_id VARCHAR(36) NOT NULL,
+    rating INT NOT NULL CHECK (rating >= 1 AND rating <= 5),
+    comment VARCHAR(1000),
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (user_id) REFERENCES users(id),
+    FOREIGN KEY (video_id) REFERENCES videos(id),
+    UNIQUE(user_id, video_id)
+);
+
+-- Inserir dados de exemplo
+INSERT INTO categories (id, name, description) VALUES
+('cat-001', 'Ação', 'Filmes de ação e aventura'),
+('cat-002', 'Comédia', 'Filmes de comédia'),
+('cat-003', 'Drama', 'Filmes dramáticos'),
+('cat-004', 'Ficção Científica', 'Filmes de ficção científica'),
+('cat-005', 'Terror', 'Filmes de terror e suspense');
+
+INSERT INTO access_groups (id, name, description) VALUES
+('group-001', 'Admin', 'Administradores do sistema'),
+('group-002', 'Moderator', 'Moderadores de conteúdo'),
+('group-003', 'Premium', 'Usuários premium'),
+('group-004', 'Standard', 'Usuários padrão');
+
+-- Criar índices para melhor performance
+CREATE INDEX idx_videos_title ON videos(title);
+CREATE INDEX idx_videos_category ON video_categories(category_id);
+CREATE INDEX idx_users_email ON users(email);
+CREATE INDEX idx_watch_history_user ON watch_history(user_id);
+CREATE INDEX idx_favorites_user ON favorites(user_id);
+
@@ -0,0 +1,18 @@
+-- Versão PostgreSQL de migration.sql
+
+ALTER TABLE users ADD COLUMN role VARCHAR(50) NOT NULL DEFAULT 'Viewer';
+
+-- Usuários do grupo Admin passam a ter o papel correspondente
+UPDATE users SET role = 'Admin'
+WHERE id IN (
+    SELECT uag.user_id
+    FROM users_access_groups uag
+    INNER JOIN access_groups ag ON ag.id = uag.access_group_id
+    WHERE ag.name = 'Admin'
+);
+
+CREATE INDEX idx_users_role ON users(role);
+
+ALTER TABLE video_categories ADD COLUMN created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP;
+ALTER TABLE video_actors ADD COLUMN created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP;
+ALTER TABLE video_directors ADD COLUMN created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP;
@@ -0,0 +1,20 @@
+-- Alinha o banco com as entidades: a migração inicial não tinha
+-- users.role nem created_at nas tabelas de relacionamento de vídeos
+
+ALTER TABLE users ADD role NVARCHAR(50) NOT NULL CONSTRAINT df_users_role DEFAULT 'Viewer';
+GO
+
+-- Usuários do grupo Admin passam a ter o papel correspondente
+UPDATE users SET role = 'Admin'
+WHERE id IN (
+    SELECT uag.user_id
+    FROM users_access_groups uag
+    INNER JOIN access_groups ag ON ag.id = uag.access_group_id
+    WHERE ag.name = 'Admin'
+);
+
+CREATE INDEX idx_users_role ON users(role);
+
+ALTER TABLE video_categories ADD created_at DATETIME2 NOT NULL CONSTRAINT df_video_categories_created_at DEFAULT GETDATE();
+ALTER TABLE video_actors ADD created_at DATETIME2 NOT NULL CONSTRAINT df_video_actors_created_at DEFAULT GETDATE();
+ALTER TABLE video_directors ADD created_at DATETIME2 NOT NULL CONSTRAINT df_video_directors_created_at DEFAULT GETDATE();
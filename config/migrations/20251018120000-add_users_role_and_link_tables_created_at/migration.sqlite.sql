@@ -0,0 +1,25 @@
+-- Versão SQLite de migration.sql
+-- O SQLite não aceita DEFAULT CURRENT_TIMESTAMP em ADD COLUMN: as colunas
+-- created_at entram sem default e as linhas existentes são preenchidas aqui.
+-- Os repositórios sempre informam created_at ao inserir.
+
+ALTER TABLE users ADD COLUMN role VARCHAR(50) NOT NULL DEFAULT 'Viewer';
+
+-- Usuários do grupo Admin passam a ter o papel correspondente
+UPDATE users SET role = 'Admin'
+WHERE id IN (
+    SELECT uag.user_id
+    FROM users_access_groups uag
+    INNER JOIN access_groups ag ON ag.id = uag.access_group_id
+    WHERE ag.name = 'Admin'
+);
+
+CREATE INDEX idx_users_role ON users(role);
+
+ALTER TABLE video_categories ADD COLUMN created_at DATETIME;
+ALTER TABLE video_actors ADD COLUMN created_at DATETIME;
+ALTER TABLE video_directors ADD COLUMN created_at DATETIME;
+
+UPDATE video_categories SET created_at = CURRENT_TIMESTAMP WHERE created_at IS NULL;
+UPDATE video_actors SET created_at = CURRENT_TIMESTAMP WHERE created_at IS NULL;
+UPDATE video_directors SET created_at = CURRENT_TIMESTAMP WHERE created_at IS NULL;
@@ -0,0 +1,7 @@
+-- Permissões iniciais dos grupos (JSON em access_groups.permissions).
+-- Todo usuário autenticado já tem videos:read; "*" libera tudo.
+
+UPDATE access_groups SET permissions = '["*"]' WHERE name = 'Admin';
+UPDATE access_groups SET permissions = '["videos:read","videos:write","videos:publish","users:read"]' WHERE name = 'Moderator';
+UPDATE access_groups SET permissions = '["videos:read"]' WHERE name = 'Premium';
+UPDATE access_groups SET permissions = '["videos:read"]' WHERE name = 'Standard';
@@ -0,0 +1,16 @@
+-- Tokens de uso único enviados por e-mail (ex.: redefinição de senha).
+-- Só o hash SHA-256 do token é guardado.
+
+CREATE TABLE user_tokens (
+    id NVARCHAR(36) PRIMARY KEY,
+    user_id NVARCHAR(36) NOT NULL,
+    purpose NVARCHAR(50) NOT NULL,
+    token_hash NVARCHAR(64) NOT NULL,
+    expires_at DATETIME2 NOT NULL,
+    used_at DATETIME2,
+    created_at DATETIME2 NOT NULL DEFAULT GETDATE(),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    CONSTRAINT uq_user_tokens_token_hash UNIQUE (token_hash)
+);
+
+CREATE INDEX idx_user_tokens_user_purpose ON user_tokens(user_id, purpose);
@@ -0,0 +1,7 @@
+-- Contas novas começam sem e-mail confirmado. As contas que já existiam são
+-- consideradas confirmadas, para ninguém perder o acesso com a migração.
+
+ALTER TABLE users ADD email_verified_at DATETIME2 NULL;
+GO
+
+UPDATE users SET email_verified_at = COALESCE(created_at, GETDATE()) WHERE email_verified_at IS NULL;
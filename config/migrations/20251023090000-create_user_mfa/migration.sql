@@ -0,0 +1,23 @@
+-- Autenticação em dois fatores (TOTP) e códigos de recuperação.
+-- Dos códigos de recuperação só o hash SHA-256 é guardado.
+
+CREATE TABLE user_mfa (
+    user_id NVARCHAR(36) PRIMARY KEY,
+    totp_secret NVARCHAR(64) NOT NULL,
+    enabled_at DATETIME2,
+    last_used_step BIGINT,
+    failed_attempts INT NOT NULL DEFAULT 0,
+    created_at DATETIME2 NOT NULL DEFAULT GETDATE(),
+    updated_at DATETIME2 NOT NULL DEFAULT GETDATE(),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE TABLE user_recovery_codes (
+    id NVARCHAR(36) PRIMARY KEY,
+    user_id NVARCHAR(36) NOT NULL,
+    code_hash NVARCHAR(64) NOT NULL,
+    used_at DATETIME2,
+    created_at DATETIME2 NOT NULL DEFAULT GETDATE(),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    CONSTRAINT uq_user_recovery_codes_user_hash UNIQUE (user_id, code_hash)
+);
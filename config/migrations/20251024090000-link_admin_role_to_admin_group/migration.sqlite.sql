@@ -0,0 +1,11 @@
+-- Versão SQLite de migration.sql
+
+INSERT INTO users_access_groups (id, user_id, access_group_id)
+SELECT lower(hex(randomblob(16))), u.id, ag.id
+FROM users u
+INNER JOIN access_groups ag ON ag.name = 'Admin'
+WHERE LOWER(u.role) = 'admin'
+  AND NOT EXISTS (
+      SELECT 1 FROM users_access_groups uag
+      WHERE uag.user_id = u.id AND uag.access_group_id = ag.id
+  );
@@ -46,6 +46,11 @@ create_migration() {
 
 EOF
 
+    # Versões por dialeto: são as que o runner (cargo run --bin migrate) aplica
+    for dialect in sqlite postgres; do
+        echo "-- Versão ${dialect} de migration.sql" > "${migration_dir}/migration.${dialect}.sql"
+    done
+
     # Criar arquivo README
     cat > "${migration_dir}/README.md" << EOF
 # Migração: ${name}
@@ -71,7 +76,7 @@ Descreva o que esta migração faz.
 EOF
 
     echo "✅ Migração criada em: ${migration_dir}"
-    echo "📁 Edite o arquivo migration.sql e execute: ./migrations/run_migrations_on_docker.sh"
+    echo "📁 Edite migration.sqlite.sql e migration.postgres.sql e execute: $0 up"
 }
 
 # Função para listar migrações
@@ -99,9 +104,8 @@ status_migrations() {
     echo "📊 Status das Migrações"
     echo "======================="
     
-    # TODO: Implementar verificação no banco de dados
-    echo "🔍 Verificando status no banco..."
-    echo "⚠️  Funcionalidade em desenvolvimento"
+    # Usa o runner em Rust (histórico com checksum em _migrations_history)
+    cargo run --quiet --bin migrate -- status
 }
 
 # Função para mostrar ajuda
@@ -112,6 +116,7 @@ show_help() {
     echo "  create <nome>    - Criar nova migração"
     echo "  list             - Listar migrações disponíveis"
     echo "  status           - Mostrar status das migrações"
+    echo "  up               - Aplicar migrações pendentes"
     echo "  help             - Mostrar esta ajuda"
     echo ""
     echo "Exemplos:"
@@ -137,6 +142,9 @@ case "${1:-help}" in
     "status")
         status_migrations
         ;;
+    "up")
+        cargo run --quiet --bin migrate -- up
+        ;;
     "help"|*)
         show_help
         ;;
@@ -16,6 +16,7 @@ CREATE TABLE users (
     profile_picture_url VARCHAR(500),
     subscription_status VARCHAR(50) DEFAULT 'active',
     subscription_expires_at DATETIME2,
+    email_verified_at DATETIME2,
     created_at DATETIME2 DEFAULT GETDATE(),
     updated_at DATETIME2 DEFAULT GETDATE()
 );
@@ -50,7 +51,7 @@ CREATE TABLE actors (
     id VARCHAR(36) PRIMARY KEY,
     name VARCHAR(255) NOT NULL,
     biography TEXT,
-    birth_date DATETIME2,
+    birth_date DATE,
     profile_picture_url VARCHAR(500),
     created_at DATETIME2 DEFAULT GETDATE()
 );
@@ -60,7 +61,7 @@ CREATE TABLE directors (
     id VARCHAR(36) PRIMARY KEY,
     name VARCHAR(255) NOT NULL,
     biography TEXT,
-    birth_date DATETIME2,
+    birth_date DATE,
     profile_picture_url VARCHAR(500),
     created_at DATETIME2 DEFAULT GETDATE()
 );
@@ -169,6 +170,41 @@ CREATE TABLE ratings (
     UNIQUE(user_id, video_id)
 );
 
+-- Tokens de uso único enviados por e-mail (só o hash é guardado)
+CREATE TABLE user_tokens (
+    id VARCHAR(36) PRIMARY KEY,
+    user_id VARCHAR(36) NOT NULL,
+    purpose VARCHAR(50) NOT NULL,
+    token_hash VARCHAR(64) NOT NULL UNIQUE,
+    expires_at DATETIME2 NOT NULL,
+    used_at DATETIME2,
+    created_at DATETIME2 DEFAULT GETDATE(),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+-- Autenticação em dois fatores (TOTP); enabled_at nulo = cadastro pendente
+CREATE TABLE user_mfa (
+    user_id VARCHAR(36) PRIMARY KEY,
+    totp_secret VARCHAR(64) NOT NULL,
+    enabled_at DATETIME2,
+    last_used_step BIGINT,
+    failed_attempts INT NOT NULL DEFAULT 0,
+    created_at DATETIME2 DEFAULT GETDATE(),
+    updated_at DATETIME2 DEFAULT GETDATE(),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+-- Códigos de recuperação do 2FA, de uso único (só o hash é guardado)
+CREATE TABLE user_recovery_codes (
+    id VARCHAR(36) PRIMARY KEY,
+    user_id VARCHAR(36) NOT NULL,
+    code_hash VARCHAR(64) NOT NULL,
+    used_at DATETIME2,
+    created_at DATETIME2 DEFAULT GETDATE(),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    UNIQUE (user_id, code_hash)
+);
+
 -- =====================================================
 -- ÍNDICES PARA PERFORMANCE
 -- =====================================================
@@ -198,6 +234,7 @@ CREATE INDEX idx_favorites_user ON favorites(user_id);
 CREATE INDEX idx_favorites_video ON favorites(video_id);
 CREATE INDEX idx_ratings_user ON ratings(user_id);
 CREATE INDEX idx_ratings_video ON ratings(video_id);
+CREATE INDEX idx_user_tokens_user_purpose ON user_tokens(user_id, purpose);
 
 -- =====================================================
 -- DADOS INICIAIS
@@ -221,12 +258,12 @@ INSERT INTO access_groups (id, name, description) VALUES
 ('group-003', 'Administradores', 'Acesso total ao sistema');
 
 -- Inserir usuário admin padrão
-INSERT INTO users (id, email, password_hash, name, role) VALUES
-('admin-001', 'admin@streaming.com', '$2b$12$LQv3c1yqBWVHxkd0LHAkCOYz6TtxMQJqhN8/LewdBPj4J/HS.iK8i', 'Admin', 'Admin');
+INSERT INTO users (id, email, password_hash, name, role, email_verified_at) VALUES
+('admin-001', 'admin@streaming.com', '$2b$12$LQv3c1yqBWVHxkd0LHAkCOYz6TtxMQJqhN8/LewdBPj4J/HS.iK8i', 'Admin', 'Admin', GETDATE());
 
 -- Inserir usuário viewer padrão
-INSERT INTO users (id, email, password_hash, name, role) VALUES
-('viewer-001', 'viewer@streaming.com', '$2b$12$LQv3c1yqBWVHxkd0LHAkCOYz6TtxMQJqhN8/LewdBPj4J/HS.iK8i', 'Viewer', 'Viewer');
+INSERT INTO users (id, email, password_hash, name, role, email_verified_at) VALUES
+('viewer-001', 'viewer@streaming.com', '$2b$12$LQv3c1yqBWVHxkd0LHAkCOYz6TtxMQJqhN8/LewdBPj4J/HS.iK8i', 'Viewer', 'Viewer', GETDATE());
 
 -- Associar usuários aos grupos
 INSERT INTO users_access_groups (id, user_id, access_group_id) VALUES
@@ -37,6 +37,8 @@ migration_dir="$MIGRATIONS_DIR/$migration_name"
 # Cria a pasta e arquivos
 mkdir -p "$migration_dir"
 touch "$migration_dir/migration.sql"
+# Versões por dialeto, as que o runner (cargo run --bin migrate) aplica
+touch "$migration_dir/migration.sqlite.sql" "$migration_dir/migration.postgres.sql"
 
 # Cria um README.md explicativo
 echo "# Migration: $migration_input" > "$migration_dir/README.md"
@@ -45,6 +45,7 @@ function Show-Help {
     Write-ColorOutput "  docker-run     - Executa container Docker" "Yellow"
     Write-ColorOutput "  docker-stop    - Para container Docker" "Yellow"
     Write-ColorOutput "  dev            - Inicia modo desenvolvimento" "Yellow"
+    Write-ColorOutput "  migrate        - Aplica as migrações pendentes (DATABASE_URL)" "Yellow"
     Write-ColorOutput "  pipeline       - Executa pipeline completo" "Yellow"
     Write-ColorOutput "  validate       - Valida código para produção" "Yellow"
     Write-ColorOutput "  status         - Mostra status do projeto" "Yellow"
@@ -195,6 +196,18 @@ function Start-Development {
     cargo run
 }
 
+# Função para migrações (SQLite ou PostgreSQL, pelo DATABASE_URL)
+function Invoke-Migrations {
+    Write-ColorOutput "🗄️ Aplicando migrações pendentes..." "Green"
+    cargo run --quiet --bin migrate -- up
+    if ($LASTEXITCODE -eq 0) {
+        Write-ColorOutput "✅ Migrações aplicadas!" "Green"
+    } else {
+        Write-ColorOutput "❌ Erro ao aplicar migrações" "Red"
+        exit 1
+    }
+}
+
 # Função para pipeline completo
 function Invoke-Pipeline {
     Write-ColorOutput "🚀 Executando pipeline completo..." "Green"
@@ -288,6 +301,7 @@ switch ($Command.ToLower()) {
     "docker-run" { Start-Docker }
     "docker-stop" { Stop-Docker }
     "dev" { Start-Development }
+    "migrate" { Invoke-Migrations }
     "pipeline" { Invoke-Pipeline }
     "validate" { Test-Validation }
     "status" { Show-Status }
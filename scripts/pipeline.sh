@@ -36,6 +36,7 @@ show_help() {
     print_color $YELLOW "  docker-run     - Executa container Docker"
     print_color $YELLOW "  docker-stop    - Para container Docker"
     print_color $YELLOW "  dev            - Inicia modo desenvolvimento"
+    print_color $YELLOW "  migrate        - Aplica as migrações pendentes (DATABASE_URL)"
     print_color $YELLOW "  pipeline       - Executa pipeline completo"
     print_color $YELLOW "  validate       - Valida código para produção"
     print_color $YELLOW "  status         - Mostra status do projeto"
@@ -176,6 +177,17 @@ start_dev() {
     cargo run
 }
 
+# Função para migrações (SQLite ou PostgreSQL, pelo DATABASE_URL)
+run_migrations() {
+    print_color $GREEN "🗄️ Aplicando migrações pendentes..."
+    if cargo run --quiet --bin migrate -- up; then
+        print_color $GREEN "✅ Migrações aplicadas!"
+    else
+        print_color $RED "❌ Erro ao aplicar migrações"
+        exit 1
+    fi
+}
+
 # Função para pipeline completo
 run_pipeline() {
     print_color $GREEN "🚀 Executando pipeline completo..."
@@ -295,6 +307,9 @@ case $COMMAND in
     "dev")
         start_dev
         ;;
+    "migrate")
+        run_migrations
+        ;;
     "pipeline")
         run_pipeline
         ;;
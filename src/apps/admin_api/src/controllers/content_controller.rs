@@ -1,16 +1,20 @@
-use axum::{
-    extract::Json,
-    http::StatusCode,
-    response::Json as JsonResponse,
-};
+use axum::response::Json as JsonResponse;
 use serde::{Deserialize, Serialize};
+use validator::Validate;
+use crate::dto::video_dto::MAX_DURATION_SECONDS;
 use crate::services::content_service::ContentService;
+use api_rust::errors::AppError;
+use api_rust::libs::shared::modules::validation::{not_blank, ValidatedJson};
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate)]
 pub struct CreateVideoRequest {
+    #[validate(custom(function = "not_blank"), length(max = 200))]
     pub title: String,
+    #[validate(length(max = 5000))]
     pub description: String,
+    #[validate(range(min = 1, max = MAX_DURATION_SECONDS))]
     pub duration: u32,
+    #[validate(custom(function = "not_blank"), length(max = 100))]
     pub genre: String,
 }
 
@@ -35,8 +39,8 @@ impl ContentController {
     }
 
     pub async fn create_video(
-        Json(payload): Json<CreateVideoRequest>,
-    ) -> Result<JsonResponse<VideoResponse>, StatusCode> {
+        ValidatedJson(payload): ValidatedJson<CreateVideoRequest>,
+    ) -> Result<JsonResponse<VideoResponse>, AppError> {
         let controller = Self::new();
         
         match controller.content_service.create_video(&payload).await {
@@ -50,7 +54,7 @@ impl ContentController {
                 };
                 Ok(JsonResponse(response))
             }
-            Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+            Err(e) => Err(e),
         }
     }
 
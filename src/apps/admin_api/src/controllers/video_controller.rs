@@ -1,20 +1,27 @@
 use axum::{
-    extract::{Json, Path, Query, State},
-    http::StatusCode,
+    extract::{Path, State},
     response::Json as JsonResponse,
 };
 use sea_orm::DatabaseConnection;
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
 use crate::services::video_service::VideoService;
 use crate::dto::video_dto::*;
+use api_rust::errors::AppError;
+use api_rust::libs::shared::modules::auth::permissions::{PermissionSet, VIDEOS_PUBLISH};
+use api_rust::libs::shared::modules::openapi::{InvalidRequest, NotFound};
+use api_rust::libs::shared::modules::validation::{ValidatedJson, ValidatedQuery};
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ListVideosQuery {
+    #[validate(range(min = 1, max = 100))]
     pub limit: Option<u64>,
     pub offset: Option<u64>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ListVideosResponseDto {
     pub videos: Vec<VideoResponseDto>,
     pub total: u64,
@@ -22,87 +29,155 @@ pub struct ListVideosResponseDto {
     pub offset: u64,
 }
 
-pub struct VideoController;
+#[utoipa::path(
+    post,
+    path = "/videos",
+    tag = "Admin - Vídeos",
+    summary = "Cadastrar vídeo",
+    request_body = CreateVideoRequestDto,
+    responses(
+        (status = 200, body = VideoResponseDto),
+        (status = 422, response = InvalidRequest),
+    ),
+)]
+pub async fn create_video(
+    State(db): State<DatabaseConnection>,
+    ValidatedJson(payload): ValidatedJson<CreateVideoRequestDto>,
+) -> Result<JsonResponse<VideoResponseDto>, AppError> {
+    let video_service = VideoService::new(db).await;
+    
+    match video_service.create_video(payload).await {
+        Ok(video) => Ok(JsonResponse(video)),
+        Err(e) => Err(e),
+    }
+}
 
-impl VideoController {
-    pub async fn create_video(
-        State(db): State<DatabaseConnection>,
-        Json(payload): Json<CreateVideoRequestDto>,
-    ) -> Result<JsonResponse<VideoResponseDto>, StatusCode> {
-        let video_service = VideoService::new(db);
-        
-        match video_service.create_video(payload).await {
-            Ok(video) => Ok(JsonResponse(video)),
-            Err(_) => Err(StatusCode::BAD_REQUEST),
-        }
+#[utoipa::path(
+    get,
+    path = "/videos/{video_id}",
+    operation_id = "admin_get_video_by_id",
+    tag = "Admin - Vídeos",
+    summary = "Buscar vídeo pelo id",
+    params(("video_id" = String, Path, description = "Id do vídeo")),
+    responses(
+        (status = 200, body = VideoResponseDto),
+        (status = 404, response = NotFound),
+    ),
+)]
+pub async fn get_video_by_id(
+    State(db): State<DatabaseConnection>,
+    Path(video_id): Path<String>,
+) -> Result<JsonResponse<VideoResponseDto>, AppError> {
+    let video_service = VideoService::new(db).await;
+    
+    match video_service.get_video_by_id(&video_id).await {
+        Ok(Some(video)) => Ok(JsonResponse(video)),
+        Ok(None) => Err(AppError::NotFound(format!("Vídeo {} não encontrado", video_id))),
+        Err(e) => Err(e),
     }
+}
 
-    pub async fn get_video_by_id(
-        State(db): State<DatabaseConnection>,
-        Path(video_id): Path<String>,
-    ) -> Result<JsonResponse<VideoResponseDto>, StatusCode> {
-        let video_service = VideoService::new(db);
-        
-        match video_service.get_video_by_id(&video_id).await {
-            Ok(Some(video)) => Ok(JsonResponse(video)),
-            Ok(None) => Err(StatusCode::NOT_FOUND),
-            Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
-        }
+#[utoipa::path(
+    put,
+    path = "/videos/{video_id}",
+    tag = "Admin - Vídeos",
+    summary = "Alterar vídeo",
+    description = "Mudar `is_available` ou `is_featured` exige também a permissão `videos:publish`.",
+    params(("video_id" = String, Path, description = "Id do vídeo")),
+    request_body = UpdateVideoRequestDto,
+    responses(
+        (status = 200, body = VideoResponseDto),
+        (status = 404, response = NotFound),
+        (status = 422, response = InvalidRequest),
+    ),
+)]
+pub async fn update_video(
+    State(db): State<DatabaseConnection>,
+    permissions: PermissionSet,
+    Path(video_id): Path<String>,
+    ValidatedJson(payload): ValidatedJson<UpdateVideoRequestDto>,
+) -> Result<JsonResponse<VideoResponseDto>, AppError> {
+    if payload.changes_publication() {
+        permissions.require(VIDEOS_PUBLISH)?;
     }
 
-    pub async fn update_video(
-        State(db): State<DatabaseConnection>,
-        Path(video_id): Path<String>,
-        Json(payload): Json<UpdateVideoRequestDto>,
-    ) -> Result<JsonResponse<VideoResponseDto>, StatusCode> {
-        let video_service = VideoService::new(db);
-        
-        match video_service.update_video(&video_id, payload).await {
-            Ok(Some(video)) => Ok(JsonResponse(video)),
-            Ok(None) => Err(StatusCode::NOT_FOUND),
-            Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
-        }
+    let video_service = VideoService::new(db).await;
+    
+    match video_service.update_video(&video_id, payload).await {
+        Ok(Some(video)) => Ok(JsonResponse(video)),
+        Ok(None) => Err(AppError::NotFound(format!("Vídeo {} não encontrado", video_id))),
+        Err(e) => Err(e),
     }
+}
 
-    pub async fn delete_video(
-        State(db): State<DatabaseConnection>,
-        Path(video_id): Path<String>,
-    ) -> Result<JsonResponse<serde_json::Value>, StatusCode> {
-        let video_service = VideoService::new(db);
-        
-        match video_service.delete_video(&video_id).await {
-            Ok(true) => {
-                let response = serde_json::json!({
-                    "message": "Vídeo deletado com sucesso"
-                });
-                Ok(JsonResponse(response))
-            }
-            Ok(false) => Err(StatusCode::NOT_FOUND),
-            Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+#[utoipa::path(
+    delete,
+    path = "/videos/{video_id}",
+    tag = "Admin - Vídeos",
+    summary = "Remover vídeo",
+    params(("video_id" = String, Path, description = "Id do vídeo")),
+    responses(
+        (status = 200, description = "`{ \"message\": ... }`", body = Object),
+        (status = 404, response = NotFound),
+    ),
+)]
+pub async fn delete_video(
+    State(db): State<DatabaseConnection>,
+    Path(video_id): Path<String>,
+) -> Result<JsonResponse<serde_json::Value>, AppError> {
+    let video_service = VideoService::new(db).await;
+    
+    match video_service.delete_video(&video_id).await {
+        Ok(true) => {
+            let response = serde_json::json!({
+                "message": "Vídeo deletado com sucesso"
+            });
+            Ok(JsonResponse(response))
         }
+        Ok(false) => Err(AppError::NotFound(format!("Vídeo {} não encontrado", video_id))),
+        Err(e) => Err(e),
     }
+}
 
-    pub async fn list_videos(
-        State(db): State<DatabaseConnection>,
-        Query(query): Query<ListVideosQuery>,
-    ) -> Result<JsonResponse<ListVideosResponseDto>, StatusCode> {
-        let video_service = VideoService::new(db);
-        
-        match video_service.list_videos(query.limit, query.offset).await {
-            Ok(videos) => {
-                let response = ListVideosResponseDto {
-                    videos: videos.clone(),
-                    total: videos.len() as u64,
-                    limit: query.limit.unwrap_or(10),
-                    offset: query.offset.unwrap_or(0),
-                };
-                Ok(JsonResponse(response))
-            }
-            Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+#[utoipa::path(
+    get,
+    path = "/videos",
+    tag = "Admin - Vídeos",
+    summary = "Listar vídeos",
+    params(ListVideosQuery),
+    responses(
+        (status = 200, body = ListVideosResponseDto),
+        (status = 422, response = InvalidRequest),
+    ),
+)]
+pub async fn list_videos(
+    State(db): State<DatabaseConnection>,
+    ValidatedQuery(query): ValidatedQuery<ListVideosQuery>,
+) -> Result<JsonResponse<ListVideosResponseDto>, AppError> {
+    let video_service = VideoService::new(db).await;
+    
+    match video_service.list_videos(query.limit, query.offset).await {
+        Ok(videos) => {
+            let response = ListVideosResponseDto {
+                videos: videos.clone(),
+                total: videos.len() as u64,
+                limit: query.limit.unwrap_or(10),
+                offset: query.offset.unwrap_or(0),
+            };
+            Ok(JsonResponse(response))
         }
+        Err(e) => Err(e),
     }
+}
 
-    pub async fn health() -> JsonResponse<&'static str> {
-        JsonResponse("Admin API - Healthy")
-    }
+#[utoipa::path(
+    get,
+    path = "/health",
+    operation_id = "admin_health",
+    tag = "Health",
+    summary = "Saúde da Admin API",
+    responses((status = 200, body = String)),
+)]
+pub async fn health() -> JsonResponse<&'static str> {
+    JsonResponse("Admin API - Healthy")
 }
@@ -1,29 +1,61 @@
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
+use validator::Validate;
+use api_rust::libs::shared::modules::validation::{not_blank, release_year_in_range};
 
-#[derive(Debug, Deserialize)]
+// Duração máxima aceita: 24 horas
+pub const MAX_DURATION_SECONDS: u32 = 24 * 60 * 60;
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
 pub struct CreateVideoRequestDto {
+    #[validate(custom(function = "not_blank"), length(max = 200))]
     pub title: String,
+    #[validate(length(max = 5000))]
     pub description: String,
+    #[validate(range(min = 1, max = MAX_DURATION_SECONDS))]
     pub duration_seconds: u32,
+    #[validate(custom(function = "release_year_in_range"))]
     pub release_year: Option<u32>,
+    #[validate(url, length(max = 500))]
     pub thumbnail_url: Option<String>,
+    #[validate(url, length(max = 500))]
     pub video_url: Option<String>,
+    #[validate(url, length(max = 500))]
     pub trailer_url: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate, ToSchema)]
 pub struct UpdateVideoRequestDto {
+    #[validate(custom(function = "not_blank"), length(max = 200))]
     pub title: Option<String>,
+    #[validate(length(max = 5000))]
     pub description: Option<String>,
+    #[validate(range(min = 1, max = MAX_DURATION_SECONDS))]
     pub duration_seconds: Option<u32>,
+    #[validate(custom(function = "release_year_in_range"))]
     pub release_year: Option<u32>,
+    #[validate(url, length(max = 500))]
     pub thumbnail_url: Option<String>,
+    #[validate(url, length(max = 500))]
     pub video_url: Option<String>,
+    #[validate(url, length(max = 500))]
     pub trailer_url: Option<String>,
+    // Mesma faixa da tabela ratings
+    #[validate(range(min = 1.0, max = 5.0))]
+    pub rating: Option<f64>,
+    pub is_featured: Option<bool>,
+    pub is_available: Option<bool>,
+}
+
+impl UpdateVideoRequestDto {
+    // Mudanças de visibilidade no catálogo
+    pub fn changes_publication(&self) -> bool {
+        self.is_available.is_some() || self.is_featured.is_some()
+    }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct VideoResponseDto {
     pub id: String,
     pub title: String,
@@ -1,6 +1,9 @@
-use axum::Router;
+use axum::{Extension, Router};
 use tracing_subscriber;
 use std::net::SocketAddr;
+use sea_orm::DatabaseConnection;
+use api_rust::libs::shared::database::connection;
+use api_rust::libs::shared::modules::rate_limit::RateLimitLayer;
 use tokio::net::TcpListener;
 
 #[tokio::main]
@@ -16,29 +19,41 @@ async fn main() {
         .parse::<u16>()
         .unwrap_or(3002);
 
+    // Configurar conexão com banco de dados
+    let database_url = std::env::var("DATABASE_URL")
+        .unwrap_or_else(|_| "sqlite://rust_cast.db?mode=rwc".to_string());
+
+    let db: DatabaseConnection = connection::connect(&database_url).await
+        .expect("Falha ao conectar com banco de dados");
+
     // Configurar endereço
     let addr = SocketAddr::from(([127, 0, 0, 1], port));
 
-    // Criar router
+    // Criar router com state; a conexão também vai nas extensions para o
+    // AuthGuard resolver permissões
     let app = Router::new()
-        .nest("/admin", admin_api::routes::create_router())
-        .route("/", axum::routing::get(|| async { "Admin API - Running" }));
+        .nest("/admin", Router::from(admin_api::routes::create_router()))
+        .route("/", axum::routing::get(|| async { "Admin API - Running" }))
+        .with_state(db.clone())
+        .layer(Extension(db))
+        // Políticas por rota do [rate_limit] do config/project.toml
+        .layer(RateLimitLayer::shared());
 
     // Logs automáticos
     tracing::info!("🚀 Admin API iniciando em http://{}", addr);
     tracing::info!("📱 Endpoints disponíveis:");
     tracing::info!("   - POST /admin/videos");
     tracing::info!("   - GET  /admin/videos");
-    tracing::info!("   - GET  /admin/videos/:video_id");
-    tracing::info!("   - PUT  /admin/videos/:video_id");
-    tracing::info!("   - DELETE /admin/videos/:video_id");
+    tracing::info!("   - GET  /admin/videos/{{video_id}}");
+    tracing::info!("   - PUT  /admin/videos/{{video_id}}");
+    tracing::info!("   - DELETE /admin/videos/{{video_id}}");
     tracing::info!("   - GET  /admin/health");
     tracing::info!("   - GET  /");
 
     // ✅ Nova forma no Axum 0.8
     let listener = TcpListener::bind(addr).await.unwrap();
     tracing::info!("✅ Admin API pronta e rodando!");
-    axum::serve(listener, app)
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
         .await
         .unwrap();
 }
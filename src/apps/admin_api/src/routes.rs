@@ -1,16 +1,28 @@
-use axum::{routing::{post, get, put, delete}, Router};
+use axum::{middleware, routing::get};
 use sea_orm::DatabaseConnection;
+use utoipa_axum::{router::OpenApiRouter, routes};
+use api_rust::libs::shared::logging::correlation::correlation_id;
+use api_rust::libs::shared::modules::auth::access_control::{public, require_permission};
+use api_rust::libs::shared::modules::auth::permissions::VIDEOS_WRITE;
+use api_rust::libs::shared::modules::openapi::GuardedRoutes;
+use crate::controllers::video_controller;
+
+// O caminho e o método de cada rota vêm do `#[utoipa::path]` do handler; o
+// mesmo router gera o documento OpenAPI
+pub fn create_router() -> OpenApiRouter<DatabaseConnection> {
+    OpenApiRouter::new()
+        // Rotas de vídeo; publicar (is_available/is_featured) exige também
+        // videos:publish, checado no handler
+        .routes(routes!(video_controller::create_video).with_guard(require_permission(VIDEOS_WRITE)))
+        .routes(routes!(video_controller::list_videos).with_guard(require_permission(VIDEOS_WRITE)))
+        .routes(routes!(video_controller::get_video_by_id).with_guard(require_permission(VIDEOS_WRITE)))
+        .routes(routes!(video_controller::update_video).with_guard(require_permission(VIDEOS_WRITE)))
+        .routes(routes!(video_controller::delete_video).with_guard(require_permission(VIDEOS_WRITE)))
 
-pub fn create_router() -> Router<DatabaseConnection> {
-    Router::new()
-        // Rotas de vídeo
-        .route("/videos", post(crate::controllers::video_controller::create_video))
-        .route("/videos", get(crate::controllers::video_controller::list_videos))
-        .route("/videos/:video_id", get(crate::controllers::video_controller::get_video_by_id))
-        .route("/videos/:video_id", put(crate::controllers::video_controller::update_video))
-        .route("/videos/:video_id", delete(crate::controllers::video_controller::delete_video))
-        
         // Health check
-        .route("/health", get(crate::controllers::video_controller::health))
-        .route("/", get(|| async { "Admin API - Running" }))
+        .routes(routes!(video_controller::health).with_guard(public()))
+        .route("/", get(|| async { "Admin API - Running" }).route_layer(public()))
+
+        // Id de correlação usado nos erros problem+json
+        .layer(middleware::from_fn(correlation_id))
 }
@@ -1,5 +1,6 @@
 use crate::models::video::Video;
 use crate::controllers::CreateVideoRequest;
+use api_rust::errors::AppError;
 
 pub struct ContentService;
 
@@ -8,7 +9,7 @@ impl ContentService {
         Self
     }
 
-    pub async fn create_video(&self, request: &CreateVideoRequest) -> Result<Video, String> {
+    pub async fn create_video(&self, request: &CreateVideoRequest) -> Result<Video, AppError> {
         // Simulação de criação de vídeo
         let video = Video {
             id: "video_1".to_string(),
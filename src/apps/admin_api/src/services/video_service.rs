@@ -1,21 +1,35 @@
 use sea_orm::DatabaseConnection;
 use crate::dto::video_dto::{CreateVideoRequestDto, UpdateVideoRequestDto, VideoResponseDto};
 use chrono::{DateTime, Utc};
+use std::time::Duration;
 use uuid::Uuid;
+use api_rust::errors::AppError;
 
 // Importar o repositório de vídeos
 use api_rust::libs::shared::database::repositories::videos::{VideosRepository, CreateVideoRequest, UpdateVideoRequest, Video as RepoVideo};
+use api_rust::libs::shared::cache::catalog_cache::{CatalogCache, CatalogChange};
 
 pub struct VideoService {
     db: DatabaseConnection,
+    catalog_cache: CatalogCache,
 }
 
 impl VideoService {
-    pub fn new(db: DatabaseConnection) -> Self {
-        Self { db }
+    pub async fn new(db: DatabaseConnection) -> Self {
+        let catalog_cache = CatalogCache::shared(Duration::from_secs(300));
+
+        Self { db, catalog_cache }
     }
 
-    pub async fn create_video(&self, request: CreateVideoRequestDto) -> Result<VideoResponseDto, String> {
+    // Remove do cache do viewer_api as entradas afetadas pela escrita.
+    // Falhas do Redis são só logadas: a escrita no banco já foi feita.
+    async fn invalidate_catalog_cache(&self, change: CatalogChange) {
+        if let Err(e) = self.catalog_cache.invalidate(&change.tags()).await {
+            tracing::warn!("Erro ao invalidar cache do catálogo: {}", e);
+        }
+    }
+
+    pub async fn create_video(&self, request: CreateVideoRequestDto) -> Result<VideoResponseDto, AppError> {
         let videos_repo = VideosRepository::new(self.db.clone());
         
         // Converter DTO da API para DTO do repositório
@@ -31,6 +45,12 @@ impl VideoService {
         
         match videos_repo.create(repo_request).await {
             Ok(repo_video) => {
+                self.invalidate_catalog_cache(CatalogChange {
+                    video_id: repo_video.id.clone(),
+                    created: true,
+                    ..Default::default()
+                }).await;
+
                 // Converter do repositório para DTO da API
                 let api_video = VideoResponseDto {
                     id: repo_video.id,
@@ -50,11 +70,11 @@ impl VideoService {
                 
                 Ok(api_video)
             }
-            Err(e) => Err(format!("Erro ao criar vídeo: {}", e)),
+            Err(e) => Err(e.into()),
         }
     }
 
-    pub async fn get_video_by_id(&self, video_id: &str) -> Result<Option<VideoResponseDto>, String> {
+    pub async fn get_video_by_id(&self, video_id: &str) -> Result<Option<VideoResponseDto>, AppError> {
         let videos_repo = VideosRepository::new(self.db.clone());
         
         match videos_repo.find_by_id(video_id).await {
@@ -79,11 +99,11 @@ impl VideoService {
                 Ok(Some(api_video))
             }
             Ok(None) => Ok(None),
-            Err(e) => Err(format!("Erro ao buscar vídeo: {}", e)),
+            Err(e) => Err(e.into()),
         }
     }
 
-    pub async fn update_video(&self, video_id: &str, request: UpdateVideoRequestDto) -> Result<Option<VideoResponseDto>, String> {
+    pub async fn update_video(&self, video_id: &str, request: UpdateVideoRequestDto) -> Result<Option<VideoResponseDto>, AppError> {
         let videos_repo = VideosRepository::new(self.db.clone());
         
         // Converter DTO da API para DTO do repositório
@@ -99,9 +119,13 @@ impl VideoService {
             is_featured: request.is_featured,
             is_available: request.is_available,
         };
+
+        let change = CatalogChange::from_update(video_id, &repo_request);
         
         match videos_repo.update(video_id, repo_request).await {
             Ok(Some(repo_video)) => {
+                self.invalidate_catalog_cache(change).await;
+
                 // Converter do repositório para DTO da API
                 let api_video = VideoResponseDto {
                     id: repo_video.id,
@@ -122,20 +146,30 @@ impl VideoService {
                 Ok(Some(api_video))
             }
             Ok(None) => Ok(None),
-            Err(e) => Err(format!("Erro ao atualizar vídeo: {}", e)),
+            Err(e) => Err(e.into()),
         }
     }
 
-    pub async fn delete_video(&self, video_id: &str) -> Result<bool, String> {
+    pub async fn delete_video(&self, video_id: &str) -> Result<bool, AppError> {
         let videos_repo = VideosRepository::new(self.db.clone());
         
         match videos_repo.delete(video_id).await {
-            Ok(success) => Ok(success),
-            Err(e) => Err(format!("Erro ao deletar vídeo: {}", e)),
+            Ok(success) => {
+                if success {
+                    self.invalidate_catalog_cache(CatalogChange {
+                        video_id: video_id.to_string(),
+                        deleted: true,
+                        ..Default::default()
+                    }).await;
+                }
+
+                Ok(success)
+            }
+            Err(e) => Err(e.into()),
         }
     }
 
-    pub async fn list_videos(&self, limit: Option<u64>, offset: Option<u64>) -> Result<Vec<VideoResponseDto>, String> {
+    pub async fn list_videos(&self, limit: Option<u64>, offset: Option<u64>) -> Result<Vec<VideoResponseDto>, AppError> {
         let videos_repo = VideosRepository::new(self.db.clone());
         
         match videos_repo.find_all(limit, offset).await {
@@ -159,7 +193,7 @@ impl VideoService {
                 
                 Ok(api_videos)
             }
-            Err(e) => Err(format!("Erro ao listar vídeos: {}", e)),
+            Err(e) => Err(e.into()),
         }
     }
 }
@@ -0,0 +1,216 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json as JsonResponse,
+};
+use sea_orm::DatabaseConnection;
+use crate::dto::{
+    AccessGroupResponseDto, CreateAccessGroupRequestDto, PermissionsResponseDto,
+    UpdateAccessGroupRequestDto, UpdatePermissionsRequestDto,
+};
+use crate::services::access_group_service::AccessGroupService;
+use api_rust::errors::AppError;
+use api_rust::libs::shared::modules::validation::ValidatedJson;
+use api_rust::libs::shared::modules::openapi::{Conflict, InvalidRequest, NotFound};
+use api_rust::libs::shared::modules::auth::middleware::CurrentClaims;
+use api_rust::libs::shared::modules::auth::permissions::{PermissionSet, KNOWN_PERMISSIONS};
+
+// Permissões efetivas de quem chama, para o front decidir o que mostrar
+#[utoipa::path(
+    get,
+    path = "/me/permissions",
+    tag = "Grupos de acesso",
+    summary = "Permissões efetivas de quem chama",
+    responses((status = 200, body = PermissionsResponseDto)),
+)]
+pub async fn my_permissions(permissions: PermissionSet) -> JsonResponse<PermissionsResponseDto> {
+    JsonResponse(PermissionsResponseDto::from(&permissions))
+}
+
+// Catálogo de permissões aceitas nos grupos
+#[utoipa::path(
+    get,
+    path = "/permissions",
+    tag = "Grupos de acesso",
+    summary = "Permissões aceitas nos grupos",
+    responses((status = 200, body = PermissionsResponseDto)),
+)]
+pub async fn known_permissions() -> JsonResponse<PermissionsResponseDto> {
+    JsonResponse(PermissionsResponseDto {
+        permissions: KNOWN_PERMISSIONS.iter().map(|p| p.to_string()).collect(),
+    })
+}
+
+#[utoipa::path(
+    get,
+    path = "/access-groups",
+    tag = "Grupos de acesso",
+    summary = "Listar os grupos de acesso",
+    responses((status = 200, body = Vec<AccessGroupResponseDto>)),
+)]
+pub async fn list_groups(
+    State(db): State<DatabaseConnection>,
+) -> Result<JsonResponse<Vec<AccessGroupResponseDto>>, AppError> {
+    let service = AccessGroupService::new(db);
+
+    Ok(JsonResponse(service.list_groups().await?))
+}
+
+#[utoipa::path(
+    post,
+    path = "/access-groups",
+    tag = "Grupos de acesso",
+    summary = "Criar um grupo de acesso",
+    request_body = CreateAccessGroupRequestDto,
+    responses(
+        (status = 201, body = AccessGroupResponseDto),
+        (status = 409, response = Conflict),
+        (status = 422, response = InvalidRequest),
+    ),
+)]
+pub async fn create_group(
+    State(db): State<DatabaseConnection>,
+    ValidatedJson(payload): ValidatedJson<CreateAccessGroupRequestDto>,
+) -> Result<(StatusCode, JsonResponse<AccessGroupResponseDto>), AppError> {
+    let service = AccessGroupService::new(db);
+
+    let group = service.create_group(payload).await?;
+
+    Ok((StatusCode::CREATED, JsonResponse(group)))
+}
+
+#[utoipa::path(
+    put,
+    path = "/access-groups/{group_id}",
+    tag = "Grupos de acesso",
+    summary = "Alterar nome ou descrição de um grupo",
+    params(("group_id" = String, Path, description = "Id do grupo")),
+    request_body = UpdateAccessGroupRequestDto,
+    responses(
+        (status = 200, body = AccessGroupResponseDto),
+        (status = 404, response = NotFound),
+        (status = 409, response = Conflict),
+        (status = 422, response = InvalidRequest),
+    ),
+)]
+pub async fn update_group(
+    State(db): State<DatabaseConnection>,
+    Path(group_id): Path<String>,
+    ValidatedJson(payload): ValidatedJson<UpdateAccessGroupRequestDto>,
+) -> Result<JsonResponse<AccessGroupResponseDto>, AppError> {
+    let service = AccessGroupService::new(db);
+
+    Ok(JsonResponse(service.update_group(&group_id, payload).await?))
+}
+
+#[utoipa::path(
+    put,
+    path = "/access-groups/{group_id}/permissions",
+    tag = "Grupos de acesso",
+    summary = "Substituir as permissões de um grupo",
+    params(("group_id" = String, Path, description = "Id do grupo")),
+    request_body = UpdatePermissionsRequestDto,
+    responses(
+        (status = 200, body = AccessGroupResponseDto),
+        (status = 404, response = NotFound),
+        (status = 422, response = InvalidRequest),
+    ),
+)]
+pub async fn update_group_permissions(
+    State(db): State<DatabaseConnection>,
+    Path(group_id): Path<String>,
+    ValidatedJson(payload): ValidatedJson<UpdatePermissionsRequestDto>,
+) -> Result<JsonResponse<AccessGroupResponseDto>, AppError> {
+    let service = AccessGroupService::new(db);
+
+    Ok(JsonResponse(service.set_permissions(&group_id, &payload.permissions).await?))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/access-groups/{group_id}",
+    tag = "Grupos de acesso",
+    summary = "Remover um grupo de acesso",
+    params(("group_id" = String, Path, description = "Id do grupo")),
+    responses(
+        (status = 204, description = "Grupo removido"),
+        (status = 404, response = NotFound),
+    ),
+)]
+pub async fn delete_group(
+    State(db): State<DatabaseConnection>,
+    Path(group_id): Path<String>,
+) -> Result<StatusCode, AppError> {
+    let service = AccessGroupService::new(db);
+
+    service.delete_group(&group_id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    get,
+    path = "/users/{user_id}/access-groups",
+    tag = "Grupos de acesso",
+    summary = "Grupos de um usuário",
+    params(("user_id" = String, Path, description = "Id do usuário")),
+    responses((status = 200, body = Vec<AccessGroupResponseDto>)),
+)]
+pub async fn list_user_groups(
+    State(db): State<DatabaseConnection>,
+    Path(user_id): Path<String>,
+) -> Result<JsonResponse<Vec<AccessGroupResponseDto>>, AppError> {
+    let service = AccessGroupService::new(db);
+
+    Ok(JsonResponse(service.user_groups(&user_id).await?))
+}
+
+#[utoipa::path(
+    put,
+    path = "/users/{user_id}/access-groups/{group_id}",
+    tag = "Grupos de acesso",
+    summary = "Incluir um usuário em um grupo",
+    params(
+        ("user_id" = String, Path, description = "Id do usuário"),
+        ("group_id" = String, Path, description = "Id do grupo"),
+    ),
+    responses(
+        (status = 200, description = "Grupos do usuário após a inclusão", body = Vec<AccessGroupResponseDto>),
+        (status = 404, response = NotFound),
+    ),
+)]
+pub async fn assign_group(
+    State(db): State<DatabaseConnection>,
+    CurrentClaims(claims): CurrentClaims,
+    Path((user_id, group_id)): Path<(String, String)>,
+) -> Result<JsonResponse<Vec<AccessGroupResponseDto>>, AppError> {
+    let service = AccessGroupService::new(db);
+
+    Ok(JsonResponse(service.assign(&user_id, &group_id, &claims.sub).await?))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/users/{user_id}/access-groups/{group_id}",
+    tag = "Grupos de acesso",
+    summary = "Tirar um usuário de um grupo",
+    params(
+        ("user_id" = String, Path, description = "Id do usuário"),
+        ("group_id" = String, Path, description = "Id do grupo"),
+    ),
+    responses(
+        (status = 204, description = "Usuário removido do grupo"),
+        (status = 404, response = NotFound),
+    ),
+)]
+pub async fn revoke_group(
+    State(db): State<DatabaseConnection>,
+    CurrentClaims(claims): CurrentClaims,
+    Path((user_id, group_id)): Path<(String, String)>,
+) -> Result<StatusCode, AppError> {
+    let service = AccessGroupService::new(db);
+
+    service.unassign(&user_id, &group_id, &claims.sub).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -1,66 +1,450 @@
-use axum::{extract::{Json, State}, http::StatusCode, response::Json as JsonResponse};
+use axum::{
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Json as JsonResponse, Response},
+};
+use jsonwebtoken::jwk::JwkSet;
 use sea_orm::DatabaseConnection;
 use serde::Deserialize;
-use crate::dto::{LoginRequestDto, RegisterRequestDto, TokenPair};
-use crate::services::auth_service::AuthService;
+use utoipa::ToSchema;
+use validator::Validate;
+use crate::dto::{
+    ChangePasswordRequestDto, ChangePasswordResponseDto, ForgotPasswordRequestDto, LoginRequestDto,
+    LoginResultDto, LogoutResponseDto, MessageResponseDto, MfaChallengeResponseDto, MfaLoginRequestDto, MfaLoginResponseDto, RegisterRequestDto, ResendVerificationRequestDto,
+    ResetPasswordRequestDto, SessionResponseDto, TokenPair, VerifyEmailQuery,
+};
+use crate::services::auth_service::{AuthService, LoginOutcome, RegisterOutcome};
+use crate::services::email_verification_service::EmailVerificationService;
+use crate::services::password_service::PasswordService;
+use api_rust::errors::AppError;
+use api_rust::libs::shared::modules::auth::middleware::CurrentClaims;
+use api_rust::libs::shared::modules::auth::refresh_tokens::ClientInfo;
+use api_rust::libs::shared::modules::openapi::{Conflict, InvalidRequest, NotFound, TooManyRequests, Unauthorized};
+use api_rust::libs::shared::modules::token_service::TokenService;
+use api_rust::libs::shared::modules::validation::{not_blank, ValidatedJson, ValidatedQuery};
 
-pub struct AuthController;
-
-impl AuthController {
-    pub async fn health() -> JsonResponse<&'static str> {
-        JsonResponse("Auth API - OK")
-    }
+#[utoipa::path(
+    get,
+    path = "/health",
+    operation_id = "auth_health",
+    tag = "Health",
+    summary = "Saúde da Auth API",
+    responses((status = 200, body = String)),
+)]
+pub async fn health() -> JsonResponse<&'static str> {
+    JsonResponse("Auth API - OK")
 }
 
+#[utoipa::path(
+    post,
+    path = "/login",
+    tag = "Autenticação",
+    summary = "Entrar com e-mail e senha",
+    request_body = LoginRequestDto,
+    responses(
+        (status = 200, description = "Sessão iniciada ou, com 2FA, desafio para POST /auth/login/mfa", body = LoginResultDto),
+        (status = 401, response = Unauthorized),
+        (status = 422, response = InvalidRequest),
+        (status = 429, response = TooManyRequests),
+    ),
+)]
 pub async fn login(
     State(db): State<DatabaseConnection>,
-    Json(payload): Json<LoginRequestDto>,
-) -> Result<JsonResponse<TokenPair>, StatusCode> {
-    let jwt_secret = std::env::var("JWT_ACCESS_SECRET")
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    client: ClientInfo,
+    ValidatedJson(payload): ValidatedJson<LoginRequestDto>,
+) -> Result<JsonResponse<LoginResultDto>, AppError> {
+    let auth_service = AuthService::new(db);
 
-    let auth_service = AuthService::new(db, jwt_secret)
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let outcome = auth_service.login(&payload.email, &payload.password, &client).await?;
 
-    let token_pair = auth_service.login(&payload.email, &payload.password).await
-        .map_err(|_| StatusCode::UNAUTHORIZED)?;
+    // Com 2FA, o cliente completa o login em POST /auth/login/mfa
+    Ok(JsonResponse(match outcome {
+        LoginOutcome::Session(token_pair) => LoginResultDto::Session(token_pair),
+        LoginOutcome::MfaRequired(challenge) => LoginResultDto::MfaRequired(MfaChallengeResponseDto {
+            mfa_required: true,
+            mfa_token: challenge.mfa_token,
+            expires_in: challenge.expires_in,
+            enrollment_required: challenge.enrollment_required,
+        }),
+    }))
+}
 
-    Ok(JsonResponse(token_pair))
+#[utoipa::path(
+    post,
+    path = "/login/mfa",
+    tag = "2FA",
+    summary = "Completar o login com o código do 2FA",
+    request_body = MfaLoginRequestDto,
+    responses(
+        (status = 200, body = MfaLoginResponseDto),
+        (status = 401, response = Unauthorized),
+        (status = 422, response = InvalidRequest),
+    ),
+)]
+pub async fn login_mfa(
+    State(db): State<DatabaseConnection>,
+    client: ClientInfo,
+    ValidatedJson(payload): ValidatedJson<MfaLoginRequestDto>,
+) -> Result<JsonResponse<MfaLoginResponseDto>, AppError> {
+    let auth_service = AuthService::new(db);
+
+    let (tokens, recovery_codes) = auth_service
+        .complete_mfa_login(&payload.mfa_token, &payload.code, &client)
+        .await?;
+
+    Ok(JsonResponse(MfaLoginResponseDto { tokens, recovery_codes }))
 }
 
+#[utoipa::path(
+    post,
+    path = "/register",
+    tag = "Autenticação",
+    summary = "Criar conta",
+    request_body = RegisterRequestDto,
+    responses(
+        (status = 200, description = "Conta criada e sessão iniciada", body = TokenPair),
+        (status = 202, description = "Conta criada; o login espera a confirmação do e-mail", body = MessageResponseDto),
+        (status = 409, response = Conflict),
+        (status = 422, response = InvalidRequest),
+    ),
+)]
 pub async fn register(
     State(db): State<DatabaseConnection>,
-    Json(payload): Json<RegisterRequestDto>,
-) -> Result<JsonResponse<TokenPair>, StatusCode> {
-    let jwt_secret = std::env::var("JWT_ACCESS_SECRET")
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    client: ClientInfo,
+    ValidatedJson(payload): ValidatedJson<RegisterRequestDto>,
+) -> Result<Response, AppError> {
+    let auth_service = AuthService::new(db);
 
-    let auth_service = AuthService::new(db, jwt_secret)
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let outcome = auth_service
+        .register(&payload.email, &payload.password, &payload.name, None, &client)
+        .await?;
 
-    let token_pair = auth_service.register(&payload.email, &payload.password, &payload.name, None).await
-        .map_err(|_| StatusCode::BAD_REQUEST)?;
+    // Com UNVERIFIED_LOGIN_POLICY=block a conta só entra depois de confirmar o e-mail
+    Ok(match outcome {
+        RegisterOutcome::Session(token_pair) => JsonResponse(token_pair).into_response(),
+        RegisterOutcome::PendingVerification => (
+            StatusCode::ACCEPTED,
+            JsonResponse(MessageResponseDto {
+                message: "Conta criada; confirme o seu e-mail pelo link enviado para entrar".to_string(),
+            }),
+        )
+            .into_response(),
+    })
+}
+
+#[utoipa::path(
+    post,
+    path = "/refresh",
+    tag = "Autenticação",
+    summary = "Trocar o refresh token por um novo par de tokens",
+    request_body = RefreshTokenRequestDto,
+    responses(
+        (status = 200, body = TokenPair),
+        (status = 401, response = Unauthorized),
+        (status = 422, response = InvalidRequest),
+    ),
+)]
+pub async fn refresh_token(
+    State(db): State<DatabaseConnection>,
+    client: ClientInfo,
+    ValidatedJson(payload): ValidatedJson<RefreshTokenRequestDto>,
+) -> Result<JsonResponse<TokenPair>, AppError> {
+    let auth_service = AuthService::new(db);
+
+    let token_pair = auth_service.refresh_token(&payload.refresh_token, &client).await?;
 
     Ok(JsonResponse(token_pair))
 }
 
-pub async fn refresh_token(
+// Sempre 202: a resposta não diz se o e-mail está cadastrado
+#[utoipa::path(
+    post,
+    path = "/password/forgot",
+    tag = "Senha",
+    summary = "Pedir o link de redefinição de senha",
+    request_body = ForgotPasswordRequestDto,
+    responses(
+        (status = 202, body = MessageResponseDto),
+        (status = 422, response = InvalidRequest),
+    ),
+)]
+pub async fn forgot_password(
     State(db): State<DatabaseConnection>,
-    Json(payload): Json<RefreshTokenRequestDto>,
-) -> Result<JsonResponse<TokenPair>, StatusCode> {
-    let jwt_secret = std::env::var("JWT_ACCESS_SECRET")
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    ValidatedJson(payload): ValidatedJson<ForgotPasswordRequestDto>,
+) -> Result<(StatusCode, JsonResponse<MessageResponseDto>), AppError> {
+    let password_service = PasswordService::new(db)?;
+
+    password_service.forgot_password(&payload.email).await?;
 
-    let auth_service = AuthService::new(db, jwt_secret)
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok((
+        StatusCode::ACCEPTED,
+        JsonResponse(MessageResponseDto {
+            message: "Se o e-mail estiver cadastrado, enviaremos um link para redefinir a senha".to_string(),
+        }),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/password/reset",
+    tag = "Senha",
+    summary = "Redefinir a senha com o token do e-mail",
+    request_body = ResetPasswordRequestDto,
+    responses(
+        (status = 200, body = MessageResponseDto),
+        (status = 422, response = InvalidRequest),
+    ),
+)]
+pub async fn reset_password(
+    State(db): State<DatabaseConnection>,
+    ValidatedJson(payload): ValidatedJson<ResetPasswordRequestDto>,
+) -> Result<JsonResponse<MessageResponseDto>, AppError> {
+    let password_service = PasswordService::new(db)?;
 
-    let token_pair = auth_service.refresh_token(&payload.refresh_token).await
-        .map_err(|_| StatusCode::UNAUTHORIZED)?;
+    password_service.reset_password(&payload.token, &payload.new_password).await?;
 
-    Ok(JsonResponse(token_pair))
+    Ok(JsonResponse(MessageResponseDto {
+        message: "Senha redefinida; entre novamente com a nova senha".to_string(),
+    }))
 }
 
-#[derive(Deserialize)]
+#[utoipa::path(
+    get,
+    path = "/verify-email",
+    tag = "E-mail",
+    summary = "Confirmar o e-mail com o token do link",
+    params(VerifyEmailQuery),
+    responses(
+        (status = 200, body = MessageResponseDto),
+        (status = 422, response = InvalidRequest),
+    ),
+)]
+pub async fn verify_email(
+    State(db): State<DatabaseConnection>,
+    ValidatedQuery(query): ValidatedQuery<VerifyEmailQuery>,
+) -> Result<JsonResponse<MessageResponseDto>, AppError> {
+    let verification_service = EmailVerificationService::new(db)?;
+
+    verification_service.verify(&query.token).await?;
+
+    Ok(JsonResponse(MessageResponseDto {
+        message: "E-mail confirmado".to_string(),
+    }))
+}
+
+// Sempre 202, mesmo acima do limite de reenvios: a resposta não diz se o
+// e-mail está cadastrado
+#[utoipa::path(
+    post,
+    path = "/verify-email/resend",
+    tag = "E-mail",
+    summary = "Reenviar o link de confirmação",
+    request_body = ResendVerificationRequestDto,
+    responses(
+        (status = 202, body = MessageResponseDto),
+        (status = 422, response = InvalidRequest),
+    ),
+)]
+pub async fn resend_verification(
+    State(db): State<DatabaseConnection>,
+    ValidatedJson(payload): ValidatedJson<ResendVerificationRequestDto>,
+) -> Result<(StatusCode, JsonResponse<MessageResponseDto>), AppError> {
+    let verification_service = EmailVerificationService::new(db)?;
+
+    verification_service.resend(&payload.email).await?;
+
+    Ok((
+        StatusCode::ACCEPTED,
+        JsonResponse(MessageResponseDto {
+            message: "Se houver uma conta pendente com esse e-mail, enviaremos um novo link de confirmação".to_string(),
+        }),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/password/change",
+    tag = "Senha",
+    summary = "Trocar a senha da própria conta",
+    request_body = ChangePasswordRequestDto,
+    responses(
+        (status = 200, body = ChangePasswordResponseDto),
+        (status = 422, response = InvalidRequest),
+    ),
+)]
+pub async fn change_password(
+    State(db): State<DatabaseConnection>,
+    CurrentClaims(claims): CurrentClaims,
+    ValidatedJson(payload): ValidatedJson<ChangePasswordRequestDto>,
+) -> Result<JsonResponse<ChangePasswordResponseDto>, AppError> {
+    let password_service = PasswordService::new(db)?;
+
+    let revoked_sessions = password_service
+        .change_password(&claims, &payload.current_password, &payload.new_password)
+        .await?;
+
+    Ok(JsonResponse(ChangePasswordResponseDto {
+        message: "Senha alterada".to_string(),
+        revoked_sessions,
+    }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/logout",
+    tag = "Sessões",
+    summary = "Encerrar a sessão atual",
+    responses((status = 204, description = "Sessão encerrada")),
+)]
+pub async fn logout(
+    State(db): State<DatabaseConnection>,
+    CurrentClaims(claims): CurrentClaims,
+) -> Result<StatusCode, AppError> {
+    let auth_service = AuthService::new(db);
+
+    auth_service.logout(&claims).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    post,
+    path = "/logout-all",
+    tag = "Sessões",
+    summary = "Encerrar todas as sessões da conta",
+    responses((status = 200, body = LogoutResponseDto)),
+)]
+pub async fn logout_all(
+    State(db): State<DatabaseConnection>,
+    CurrentClaims(claims): CurrentClaims,
+) -> Result<JsonResponse<LogoutResponseDto>, AppError> {
+    let auth_service = AuthService::new(db);
+
+    let revoked_sessions = auth_service.logout_all(&claims.sub).await?;
+
+    Ok(JsonResponse(LogoutResponseDto {
+        message: "Todas as sessões foram encerradas".to_string(),
+        revoked_sessions,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/sessions",
+    tag = "Sessões",
+    summary = "Listar as sessões ativas da conta",
+    responses((status = 200, body = Vec<SessionResponseDto>)),
+)]
+pub async fn list_sessions(
+    State(db): State<DatabaseConnection>,
+    CurrentClaims(claims): CurrentClaims,
+) -> Result<JsonResponse<Vec<SessionResponseDto>>, AppError> {
+    let auth_service = AuthService::new(db);
+
+    let sessions = auth_service
+        .list_sessions(&claims.sub)
+        .await?
+        .into_iter()
+        .map(|session| SessionResponseDto::from_session(session, claims.sid.as_deref()))
+        .collect();
+
+    Ok(JsonResponse(sessions))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/sessions/{session_id}",
+    tag = "Sessões",
+    summary = "Encerrar uma sessão da conta",
+    params(("session_id" = String, Path, description = "Id da sessão")),
+    responses(
+        (status = 204, description = "Sessão encerrada"),
+        (status = 404, response = NotFound),
+    ),
+)]
+pub async fn revoke_session(
+    State(db): State<DatabaseConnection>,
+    CurrentClaims(claims): CurrentClaims,
+    Path(session_id): Path<String>,
+) -> Result<StatusCode, AppError> {
+    let auth_service = AuthService::new(db);
+
+    auth_service.revoke_session(&claims.sub, &session_id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// Suporte: derruba todas as sessões de uma conta comprometida (rota só para
+// quem tem users:manage, ver routes.rs)
+#[utoipa::path(
+    delete,
+    path = "/users/{user_id}/sessions",
+    tag = "Usuários",
+    summary = "Encerrar todas as sessões de um usuário",
+    params(("user_id" = String, Path, description = "Id do usuário")),
+    responses((status = 200, body = LogoutResponseDto)),
+)]
+pub async fn revoke_user_sessions(
+    State(db): State<DatabaseConnection>,
+    CurrentClaims(claims): CurrentClaims,
+    Path(user_id): Path<String>,
+) -> Result<JsonResponse<LogoutResponseDto>, AppError> {
+    let auth_service = AuthService::new(db);
+
+    let revoked_sessions = auth_service.logout_all(&user_id).await?;
+    tracing::warn!("Sessões do usuário {} encerradas por {}", user_id, claims.sub);
+
+    Ok(JsonResponse(LogoutResponseDto {
+        message: format!("Sessões do usuário {} encerradas", user_id),
+        revoked_sessions,
+    }))
+}
+
+// Libera uma conta bloqueada por tentativas de login erradas
+#[utoipa::path(
+    delete,
+    path = "/users/{user_id}/lockout",
+    tag = "Usuários",
+    summary = "Remover o bloqueio de login de um usuário",
+    params(("user_id" = String, Path, description = "Id do usuário")),
+    responses((status = 200, body = MessageResponseDto)),
+)]
+pub async fn unlock_user(
+    State(db): State<DatabaseConnection>,
+    CurrentClaims(claims): CurrentClaims,
+    Path(user_id): Path<String>,
+) -> Result<JsonResponse<MessageResponseDto>, AppError> {
+    let auth_service = AuthService::new(db);
+
+    let was_locked = auth_service.unlock_account(&user_id).await?;
+    tracing::warn!("Bloqueio de login do usuário {} removido por {}", user_id, claims.sub);
+
+    let message = if was_locked {
+        format!("Bloqueio de login do usuário {} removido", user_id)
+    } else {
+        format!("O usuário {} não estava bloqueado", user_id)
+    };
+
+    Ok(JsonResponse(MessageResponseDto { message }))
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
 pub struct RefreshTokenRequestDto {
+    #[validate(custom(function = "not_blank"))]
     pub refresh_token: String,
-} 
\ No newline at end of file
+}
+
+// Chaves públicas para validar access tokens sem acesso à chave privada.
+// Durante uma rotação, a chave anterior continua publicada.
+#[utoipa::path(
+    get,
+    path = "/.well-known/jwks.json",
+    tag = "Autenticação",
+    summary = "Chaves públicas (JWKS) para validar access tokens",
+    responses((status = 200, description = "JWK Set (RFC 7517)", body = Object)),
+)]
+pub async fn jwks() -> impl IntoResponse {
+    let keys: JwkSet = TokenService::shared().jwks();
+
+    ([(header::CACHE_CONTROL, "public, max-age=300")], JsonResponse(keys))
+}
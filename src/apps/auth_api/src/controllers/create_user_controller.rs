@@ -1,14 +1,25 @@
-use axum::{extract::{Json, State}, http::StatusCode, response::Json as JsonResponse};
+use axum::{extract::State, response::Json as JsonResponse};
 use crate::services::create_user_service::CreateUserService;
 use crate::dto::UserResponseDto;
+use api_rust::errors::AppError;
+use api_rust::libs::shared::modules::auth::permissions::{PermissionSet, GROUPS_MANAGE};
+use api_rust::libs::shared::modules::validation::{not_blank, ValidatedJson};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use sea_orm::DatabaseConnection;
+use validator::Validate;
+use crate::dto::validate_role;
 
-#[derive(Debug, Deserialize)]
+// A senha passa pela PasswordPolicy no serviço
+#[derive(Debug, Deserialize, Validate, ToSchema)]
 pub struct CreateUserRequestDto {
+    #[validate(email, length(max = 254))]
     pub email: String,
+    #[validate(length(min = 1, message = "Informe a senha"))]
     pub password: String,
+    #[validate(custom(function = "not_blank"), length(max = 100))]
     pub name: String,
+    #[validate(custom(function = "validate_role"))]
     pub role: Option<String>,
 }
 
@@ -28,16 +39,20 @@ impl CreateUserController {
 
 pub async fn create_user(
     State(db): State<DatabaseConnection>,
-    Json(payload): Json<CreateUserRequestDto>,
-) -> Result<JsonResponse<CreateUserResponseDto>, StatusCode> {
+    permissions: PermissionSet,
+    ValidatedJson(payload): ValidatedJson<CreateUserRequestDto>,
+) -> Result<JsonResponse<CreateUserResponseDto>, AppError> {
+    // Papel admin só por quem já pode atribuir grupos
+    if payload.role.as_deref().is_some_and(|role| role.eq_ignore_ascii_case("admin")) {
+        permissions.require(GROUPS_MANAGE)?;
+    }
+
     let redis_url = std::env::var("REDIS_URL")
         .unwrap_or_else(|_| "redis://localhost:6379".to_string());
 
-    let service = CreateUserService::new(db, redis_url)
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let service = CreateUserService::new(db, redis_url)?;
 
-    let user = service.create_user(&payload.email, &payload.password, &payload.name, payload.role).await
-        .map_err(|_| StatusCode::BAD_REQUEST)?;
+    let user = service.create_user(&payload.email, &payload.password, &payload.name, payload.role).await?;
 
     let response = CreateUserResponseDto {
         user: user.into(),
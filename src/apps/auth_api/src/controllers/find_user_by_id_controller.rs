@@ -1,6 +1,7 @@
-use axum::{extract::{Path, State}, http::StatusCode, response::Json as JsonResponse};
+use axum::{extract::{Path, State}, response::Json as JsonResponse};
 use crate::services::find_user_by_id_service::FindUserByIdService;
 use crate::dto::UserResponseDto;
+use api_rust::errors::AppError;
 use sea_orm::DatabaseConnection;
 
 pub struct FindUserByIdController;
@@ -14,43 +15,39 @@ impl FindUserByIdController {
 pub async fn get_user_by_id(
     Path(user_id): Path<String>,
     State(db): State<DatabaseConnection>,
-) -> Result<JsonResponse<UserResponseDto>, StatusCode> {
+) -> Result<JsonResponse<UserResponseDto>, AppError> {
     let redis_url = std::env::var("REDIS_URL")
         .unwrap_or_else(|_| "redis://localhost:6379".to_string());
 
-    let service = FindUserByIdService::new(db, redis_url)
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let service = FindUserByIdService::new(db, redis_url)?;
 
-    let user = service.find_by_id(&user_id).await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let user = service.find_by_id(&user_id).await?;
 
     match user {
         Some(user) => {
             let response: UserResponseDto = user.into();
             Ok(JsonResponse(response))
         }
-        None => Err(StatusCode::NOT_FOUND),
+        None => Err(AppError::NotFound(format!("Usuário {} não encontrado", user_id))),
     }
 }
 
 pub async fn get_user_by_email(
     Path(email): Path<String>,
     State(db): State<DatabaseConnection>,
-) -> Result<JsonResponse<UserResponseDto>, StatusCode> {
+) -> Result<JsonResponse<UserResponseDto>, AppError> {
     let redis_url = std::env::var("REDIS_URL")
         .unwrap_or_else(|_| "redis://localhost:6379".to_string());
 
-    let service = FindUserByIdService::new(db, redis_url)
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let service = FindUserByIdService::new(db, redis_url)?;
 
-    let user = service.find_by_email(&email).await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let user = service.find_by_email(&email).await?;
 
     match user {
         Some(user) => {
             let response: UserResponseDto = user.into();
             Ok(JsonResponse(response))
         }
-        None => Err(StatusCode::NOT_FOUND),
+        None => Err(AppError::NotFound(format!("Usuário com email {} não encontrado", email))),
     }
 } 
\ No newline at end of file
@@ -1,17 +1,22 @@
-use axum::{extract::{Query, State}, http::StatusCode, response::Json as JsonResponse};
+use axum::{extract::State, response::Json as JsonResponse};
 use crate::services::find_users_service::FindUsersService;
 use crate::dto::UserResponseDto;
+use api_rust::errors::AppError;
+use api_rust::libs::shared::modules::validation::{not_blank, ValidatedQuery};
 use serde::{Deserialize, Serialize};
 use sea_orm::DatabaseConnection;
+use validator::Validate;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate)]
 pub struct ListUsersQuery {
+    #[validate(range(min = 1, max = 100))]
     pub limit: Option<u64>,
     pub offset: Option<u64>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate)]
 pub struct RoleQuery {
+    #[validate(custom(function = "not_blank"), length(max = 50))]
     pub role: String,
 }
 
@@ -35,20 +40,17 @@ impl FindUsersController {
 }
 
 pub async fn list_users(
-    Query(query): Query<ListUsersQuery>,
+    ValidatedQuery(query): ValidatedQuery<ListUsersQuery>,
     State(db): State<DatabaseConnection>,
-) -> Result<JsonResponse<ListUsersResponseDto>, StatusCode> {
+) -> Result<JsonResponse<ListUsersResponseDto>, AppError> {
     let redis_url = std::env::var("REDIS_URL")
         .unwrap_or_else(|_| "redis://localhost:6379".to_string());
 
-    let service = FindUsersService::new(db, redis_url)
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let service = FindUsersService::new(db, redis_url)?;
 
-    let users = service.find_all(query.limit, query.offset).await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let users = service.find_all(query.limit, query.offset).await?;
 
-    let total = service.count().await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let total = service.count().await?;
 
     let response = ListUsersResponseDto {
         users: users.into_iter().map(|u| u.into()).collect(),
@@ -59,17 +61,15 @@ pub async fn list_users(
 }
 
 pub async fn get_users_by_role(
-    Query(query): Query<RoleQuery>,
+    ValidatedQuery(query): ValidatedQuery<RoleQuery>,
     State(db): State<DatabaseConnection>,
-) -> Result<JsonResponse<Vec<UserResponseDto>>, StatusCode> {
+) -> Result<JsonResponse<Vec<UserResponseDto>>, AppError> {
     let redis_url = std::env::var("REDIS_URL")
         .unwrap_or_else(|_| "redis://localhost:6379".to_string());
 
-    let service = FindUsersService::new(db, redis_url)
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let service = FindUsersService::new(db, redis_url)?;
 
-    let users = service.find_by_role(&query.role).await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let users = service.find_by_role(&query.role).await?;
 
     let response: Vec<UserResponseDto> = users.into_iter().map(|u| u.into()).collect();
     Ok(JsonResponse(response))
@@ -77,15 +77,13 @@ pub async fn get_users_by_role(
 
 pub async fn count_users(
     State(db): State<DatabaseConnection>,
-) -> Result<JsonResponse<CountUsersResponseDto>, StatusCode> {
+) -> Result<JsonResponse<CountUsersResponseDto>, AppError> {
     let redis_url = std::env::var("REDIS_URL")
         .unwrap_or_else(|_| "redis://localhost:6379".to_string());
 
-    let service = FindUsersService::new(db, redis_url)
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let service = FindUsersService::new(db, redis_url)?;
 
-    let count = service.count().await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let count = service.count().await?;
 
     let response = CountUsersResponseDto { count };
     Ok(JsonResponse(response))
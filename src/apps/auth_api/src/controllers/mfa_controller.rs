@@ -0,0 +1,158 @@
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::Json as JsonResponse,
+};
+use sea_orm::DatabaseConnection;
+use crate::dto::{
+    MfaChallengeEnrollRequestDto, MfaCodeRequestDto, MfaEnrollmentResponseDto, MfaStatusResponseDto,
+    RecoveryCodesResponseDto,
+};
+use crate::services::mfa_service::{MfaEnrollment, MfaService};
+use api_rust::errors::AppError;
+use api_rust::libs::shared::modules::validation::ValidatedJson;
+use api_rust::libs::shared::modules::openapi::{Conflict, InvalidRequest, Unauthorized};
+use api_rust::libs::shared::modules::auth::middleware::CurrentClaims;
+
+impl From<MfaEnrollment> for MfaEnrollmentResponseDto {
+    fn from(enrollment: MfaEnrollment) -> Self {
+        Self {
+            secret: enrollment.secret,
+            otpauth_uri: enrollment.otpauth_uri,
+            qr_code_svg: enrollment.qr_code_svg,
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/mfa",
+    tag = "2FA",
+    summary = "Situação do 2FA da conta",
+    responses((status = 200, body = MfaStatusResponseDto)),
+)]
+pub async fn status(
+    State(db): State<DatabaseConnection>,
+    CurrentClaims(claims): CurrentClaims,
+) -> Result<JsonResponse<MfaStatusResponseDto>, AppError> {
+    let service = MfaService::new(db)?;
+
+    let status = service.status(&claims.sub, &claims.access_groups).await?;
+
+    Ok(JsonResponse(MfaStatusResponseDto {
+        enabled: status.enabled,
+        required: status.required,
+        recovery_codes_remaining: status.recovery_codes_remaining,
+    }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/mfa/enroll",
+    tag = "2FA",
+    summary = "Iniciar o cadastro do 2FA",
+    responses(
+        (status = 200, body = MfaEnrollmentResponseDto),
+        (status = 409, response = Conflict),
+    ),
+)]
+pub async fn enroll(
+    State(db): State<DatabaseConnection>,
+    CurrentClaims(claims): CurrentClaims,
+) -> Result<JsonResponse<MfaEnrollmentResponseDto>, AppError> {
+    let service = MfaService::new(db)?;
+
+    Ok(JsonResponse(service.start_enrollment(&claims.sub).await?.into()))
+}
+
+// Cadastro durante o login, para contas obrigadas a ter 2FA; a confirmação
+// é o próprio POST /auth/login/mfa
+#[utoipa::path(
+    post,
+    path = "/login/mfa/enroll",
+    tag = "2FA",
+    summary = "Iniciar o cadastro do 2FA durante o login",
+    request_body = MfaChallengeEnrollRequestDto,
+    responses(
+        (status = 200, body = MfaEnrollmentResponseDto),
+        (status = 401, response = Unauthorized),
+        (status = 422, response = InvalidRequest),
+    ),
+)]
+pub async fn enroll_from_challenge(
+    State(db): State<DatabaseConnection>,
+    ValidatedJson(payload): ValidatedJson<MfaChallengeEnrollRequestDto>,
+) -> Result<JsonResponse<MfaEnrollmentResponseDto>, AppError> {
+    let service = MfaService::new(db)?;
+
+    Ok(JsonResponse(service.start_enrollment_from_challenge(&payload.mfa_token).await?.into()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/mfa/enroll/confirm",
+    tag = "2FA",
+    summary = "Confirmar o cadastro do 2FA com o primeiro código",
+    request_body = MfaCodeRequestDto,
+    responses(
+        (status = 200, body = RecoveryCodesResponseDto),
+        (status = 422, response = InvalidRequest),
+    ),
+)]
+pub async fn confirm_enrollment(
+    State(db): State<DatabaseConnection>,
+    CurrentClaims(claims): CurrentClaims,
+    ValidatedJson(payload): ValidatedJson<MfaCodeRequestDto>,
+) -> Result<JsonResponse<RecoveryCodesResponseDto>, AppError> {
+    let service = MfaService::new(db)?;
+
+    let recovery_codes = service.confirm_enrollment(&claims.sub, &payload.code).await?;
+
+    Ok(JsonResponse(RecoveryCodesResponseDto { recovery_codes }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/mfa/recovery-codes",
+    tag = "2FA",
+    summary = "Gerar novos códigos de recuperação",
+    request_body = MfaCodeRequestDto,
+    responses(
+        (status = 200, body = RecoveryCodesResponseDto),
+        (status = 422, response = InvalidRequest),
+    ),
+)]
+pub async fn regenerate_recovery_codes(
+    State(db): State<DatabaseConnection>,
+    CurrentClaims(claims): CurrentClaims,
+    ValidatedJson(payload): ValidatedJson<MfaCodeRequestDto>,
+) -> Result<JsonResponse<RecoveryCodesResponseDto>, AppError> {
+    let service = MfaService::new(db)?;
+
+    let recovery_codes = service.regenerate_recovery_codes(&claims.sub, &payload.code).await?;
+
+    Ok(JsonResponse(RecoveryCodesResponseDto { recovery_codes }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/mfa",
+    tag = "2FA",
+    summary = "Desativar o 2FA",
+    request_body = MfaCodeRequestDto,
+    responses(
+        (status = 204, description = "2FA desativado"),
+        (status = 422, response = InvalidRequest),
+    ),
+)]
+pub async fn disable(
+    State(db): State<DatabaseConnection>,
+    CurrentClaims(claims): CurrentClaims,
+    ValidatedJson(payload): ValidatedJson<MfaCodeRequestDto>,
+) -> Result<StatusCode, AppError> {
+    let service = MfaService::new(db)?;
+
+    service.disable(&claims.sub, &claims.access_groups, &payload.code).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
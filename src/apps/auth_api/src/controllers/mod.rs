@@ -1,11 +1,15 @@
+pub mod access_group_controller;
 pub mod auth_controller;
+pub mod mfa_controller;
 pub mod user_controller;
 pub mod find_users_controller;
 pub mod find_user_by_id_controller;
 pub mod create_user_controller;
 
 pub use auth_controller::*;
-pub use user_controller::*;
+// Os handlers de user_controller têm os mesmos nomes dos controllers
+// find_*/create_user; só os DTOs vão para o nível do módulo
+pub use user_controller::{UpdateUserRequestDto, UpdateUserResponseDto};
 pub use find_users_controller::*;
 pub use find_user_by_id_controller::*;
 pub use create_user_controller::*;
@@ -1,199 +1,301 @@
 use axum::{
-    extract::{Json, Path, State},
-    http::StatusCode,
+    extract::{Path, State},
     response::Json as JsonResponse,
 };
 use sea_orm::DatabaseConnection;
 use crate::services::user_service::UserService;
 use crate::dto::UserResponseDto;
+use api_rust::errors::AppError;
+use api_rust::libs::shared::modules::auth::permissions::{PermissionResolver, PermissionSet, GROUPS_MANAGE};
+use api_rust::libs::shared::modules::openapi::{Conflict, InvalidRequest, NotFound};
+use api_rust::libs::shared::modules::validation::{not_blank, ValidatedJson};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate, ToSchema)]
 pub struct UpdateUserRequestDto {
+    #[validate(custom(function = "not_blank"), length(max = 100))]
     pub name: Option<String>,
+    #[validate(email, length(max = 254))]
     pub email: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct UpdateUserResponseDto {
     pub user: UserResponseDto,
     pub message: String,
 }
 
-pub struct UserController;
-
-impl UserController {
-    pub async fn list_users(
-        State(db): State<DatabaseConnection>,
-    ) -> Result<JsonResponse<Vec<UserResponseDto>>, StatusCode> {
-        let user_service = UserService::new(db, "redis://localhost:6379".to_string())
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-        
-        match user_service.list_users(None, None).await {
-            Ok(users) => {
-                let response: Vec<UserResponseDto> = users
-                    .into_iter()
-                    .map(|user| UserResponseDto {
-                        id: user.id,
-                        email: user.email,
-                        name: user.name,
-                        role: crate::dto::UserRoleDto::from(user.role.as_str()),
-                    })
-                    .collect();
-                Ok(JsonResponse(response))
-            }
-            Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
-        }
+// users:manage não basta para mexer numa conta com mais permissões que as do
+// chamador (trocar o e-mail de um admin e pedir a redefinição de senha, por
+// exemplo)
+async fn require_authority_over(
+    user_service: &UserService,
+    db: &DatabaseConnection,
+    permissions: &PermissionSet,
+    user_id: &str,
+) -> Result<(), AppError> {
+    if user_service.get_user_by_id(user_id).await?.is_none() {
+        return Err(AppError::NotFound(format!("Usuário {} não encontrado", user_id)));
     }
 
-    pub async fn count_users(
-        State(db): State<DatabaseConnection>,
-    ) -> Result<JsonResponse<serde_json::Value>, StatusCode> {
-        let user_service = UserService::new(db, "redis://localhost:6379".to_string())
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-        
-        match user_service.count_users().await {
-            Ok(count) => {
-                let response = serde_json::json!({
-                    "total_users": count
-                });
-                Ok(JsonResponse(response))
-            }
-            Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    let target = PermissionResolver::shared().for_user(db, user_id).await?;
+    permissions.require_covers(&target)
+}
+
+#[utoipa::path(
+    get,
+    path = "/users",
+    tag = "Usuários",
+    summary = "Listar usuários",
+    responses((status = 200, body = Vec<UserResponseDto>)),
+)]
+pub async fn list_users(
+    State(db): State<DatabaseConnection>,
+) -> Result<JsonResponse<Vec<UserResponseDto>>, AppError> {
+    let user_service = UserService::new(db, "redis://localhost:6379".to_string())?;
+    
+    match user_service.list_users(None, None).await {
+        Ok(users) => {
+            let response: Vec<UserResponseDto> = users
+                .into_iter()
+                .map(|user| UserResponseDto {
+                    id: user.id,
+                    email: user.email,
+                    name: user.name,
+                    role: crate::dto::UserRoleDto::from(user.role.as_str()),
+                })
+                .collect();
+            Ok(JsonResponse(response))
         }
+        Err(e) => Err(e),
     }
+}
 
-    pub async fn get_users_by_role(
-        State(db): State<DatabaseConnection>,
-        Path(role): Path<String>,
-    ) -> Result<JsonResponse<Vec<UserResponseDto>>, StatusCode> {
-        let user_service = UserService::new(db, "redis://localhost:6379".to_string())
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-        
-        match user_service.get_users_by_role(&role).await {
-            Ok(users) => {
-                let response: Vec<UserResponseDto> = users
-                    .into_iter()
-                    .map(|user| UserResponseDto {
-                        id: user.id,
-                        email: user.email,
-                        name: user.name,
-                        role: crate::dto::UserRoleDto::from(user.role.as_str()),
-                    })
-                    .collect();
-                Ok(JsonResponse(response))
-            }
-            Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+#[utoipa::path(
+    get,
+    path = "/users/count",
+    tag = "Usuários",
+    summary = "Contar usuários",
+    responses((status = 200, description = "`{ \"total_users\": n }`", body = Object)),
+)]
+pub async fn count_users(
+    State(db): State<DatabaseConnection>,
+) -> Result<JsonResponse<serde_json::Value>, AppError> {
+    let user_service = UserService::new(db, "redis://localhost:6379".to_string())?;
+    
+    match user_service.count_users().await {
+        Ok(count) => {
+            let response = serde_json::json!({
+                "total_users": count
+            });
+            Ok(JsonResponse(response))
         }
+        Err(e) => Err(e),
     }
+}
 
-    pub async fn get_user_by_id(
-        State(db): State<DatabaseConnection>,
-        Path(user_id): Path<String>,
-    ) -> Result<JsonResponse<UserResponseDto>, StatusCode> {
-        let user_service = UserService::new(db, "redis://localhost:6379".to_string())
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-        
-        match user_service.get_user_by_id(&user_id).await {
-            Ok(Some(user)) => {
-                let response = UserResponseDto {
+#[utoipa::path(
+    get,
+    path = "/users/by-role/{role}",
+    tag = "Usuários",
+    summary = "Listar usuários de um papel",
+    params(("role" = String, Path, description = "Papel (`admin` ou `viewer`)")),
+    responses((status = 200, body = Vec<UserResponseDto>)),
+)]
+pub async fn get_users_by_role(
+    State(db): State<DatabaseConnection>,
+    Path(role): Path<String>,
+) -> Result<JsonResponse<Vec<UserResponseDto>>, AppError> {
+    let user_service = UserService::new(db, "redis://localhost:6379".to_string())?;
+    
+    match user_service.get_users_by_role(&role).await {
+        Ok(users) => {
+            let response: Vec<UserResponseDto> = users
+                .into_iter()
+                .map(|user| UserResponseDto {
                     id: user.id,
                     email: user.email,
                     name: user.name,
                     role: crate::dto::UserRoleDto::from(user.role.as_str()),
-                };
-                Ok(JsonResponse(response))
-            }
-            Ok(None) => Err(StatusCode::NOT_FOUND),
-            Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+                })
+                .collect();
+            Ok(JsonResponse(response))
         }
+        Err(e) => Err(e),
     }
+}
 
-    pub async fn get_user_by_email(
-        State(db): State<DatabaseConnection>,
-        Path(email): Path<String>,
-    ) -> Result<JsonResponse<UserResponseDto>, StatusCode> {
-        let user_service = UserService::new(db, "redis://localhost:6379".to_string())
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-        
-        match user_service.get_user_by_email(&email).await {
-            Ok(Some(user)) => {
-                let response = UserResponseDto {
-                    id: user.id,
-                    email: user.email,
-                    name: user.name,
-                    role: crate::dto::UserRoleDto::from(user.role.as_str()),
-                };
-                Ok(JsonResponse(response))
-            }
-            Ok(None) => Err(StatusCode::NOT_FOUND),
-            Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+#[utoipa::path(
+    get,
+    path = "/users/{user_id}",
+    tag = "Usuários",
+    summary = "Buscar usuário pelo id",
+    params(("user_id" = String, Path, description = "Id do usuário")),
+    responses(
+        (status = 200, body = UserResponseDto),
+        (status = 404, response = NotFound),
+    ),
+)]
+pub async fn get_user_by_id(
+    State(db): State<DatabaseConnection>,
+    Path(user_id): Path<String>,
+) -> Result<JsonResponse<UserResponseDto>, AppError> {
+    let user_service = UserService::new(db, "redis://localhost:6379".to_string())?;
+    
+    match user_service.get_user_by_id(&user_id).await {
+        Ok(Some(user)) => {
+            let response = UserResponseDto {
+                id: user.id,
+                email: user.email,
+                name: user.name,
+                role: crate::dto::UserRoleDto::from(user.role.as_str()),
+            };
+            Ok(JsonResponse(response))
         }
+        Ok(None) => Err(AppError::NotFound(format!("Usuário {} não encontrado", user_id))),
+        Err(e) => Err(e),
     }
+}
 
-    pub async fn create_user(
-        State(db): State<DatabaseConnection>,
-        Json(payload): Json<crate::dto::CreateUserRequestDto>,
-    ) -> Result<JsonResponse<UserResponseDto>, StatusCode> {
-        let user_service = UserService::new(db, "redis://localhost:6379".to_string())
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-        
-        match user_service.create_user(payload).await {
-            Ok(user) => {
-                let response = UserResponseDto {
+#[utoipa::path(
+    get,
+    path = "/users/email/{email}",
+    tag = "Usuários",
+    summary = "Buscar usuário pelo e-mail",
+    params(("email" = String, Path, description = "E-mail do usuário")),
+    responses(
+        (status = 200, body = UserResponseDto),
+        (status = 404, response = NotFound),
+    ),
+)]
+pub async fn get_user_by_email(
+    State(db): State<DatabaseConnection>,
+    Path(email): Path<String>,
+) -> Result<JsonResponse<UserResponseDto>, AppError> {
+    let user_service = UserService::new(db, "redis://localhost:6379".to_string())?;
+    
+    match user_service.get_user_by_email(&email).await {
+        Ok(Some(user)) => {
+            let response = UserResponseDto {
+                id: user.id,
+                email: user.email,
+                name: user.name,
+                role: crate::dto::UserRoleDto::from(user.role.as_str()),
+            };
+            Ok(JsonResponse(response))
+        }
+        Ok(None) => Err(AppError::NotFound(format!("Usuário com email {} não encontrado", email))),
+        Err(e) => Err(e),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/users",
+    tag = "Usuários",
+    summary = "Criar usuário",
+    request_body = crate::dto::CreateUserRequestDto,
+    responses(
+        (status = 200, body = UserResponseDto),
+        (status = 409, response = Conflict),
+        (status = 422, response = InvalidRequest),
+    ),
+)]
+pub async fn create_user(
+    State(db): State<DatabaseConnection>,
+    permissions: PermissionSet,
+    ValidatedJson(payload): ValidatedJson<crate::dto::CreateUserRequestDto>,
+) -> Result<JsonResponse<UserResponseDto>, AppError> {
+    // Papel admin só por quem já pode atribuir grupos
+    if payload.role.as_deref().is_some_and(|role| role.eq_ignore_ascii_case("admin")) {
+        permissions.require(GROUPS_MANAGE)?;
+    }
+
+    let user_service = UserService::new(db, "redis://localhost:6379".to_string())?;
+    
+    match user_service.create_user(payload).await {
+        Ok(user) => {
+            let response = UserResponseDto {
+                id: user.id,
+                email: user.email,
+                name: user.name,
+                role: crate::dto::UserRoleDto::from(user.role.as_str()),
+            };
+            Ok(JsonResponse(response))
+        }
+        Err(e) => Err(e),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/users/{user_id}",
+    tag = "Usuários",
+    summary = "Alterar nome ou e-mail de um usuário",
+    params(("user_id" = String, Path, description = "Id do usuário")),
+    request_body = UpdateUserRequestDto,
+    responses(
+        (status = 200, body = UpdateUserResponseDto),
+        (status = 404, response = NotFound),
+        (status = 422, response = InvalidRequest),
+    ),
+)]
+pub async fn update_user(
+    State(db): State<DatabaseConnection>,
+    permissions: PermissionSet,
+    Path(user_id): Path<String>,
+    ValidatedJson(payload): ValidatedJson<UpdateUserRequestDto>,
+) -> Result<JsonResponse<UpdateUserResponseDto>, AppError> {
+    let user_service = UserService::new(db.clone(), "redis://localhost:6379".to_string())?;
+    require_authority_over(&user_service, &db, &permissions, &user_id).await?;
+
+    match user_service.update_user(&user_id, payload.name, payload.email).await {
+        Ok(Some(user)) => {
+            let response = UpdateUserResponseDto {
+                user: UserResponseDto {
                     id: user.id,
                     email: user.email,
                     name: user.name,
                     role: crate::dto::UserRoleDto::from(user.role.as_str()),
-                };
-                Ok(JsonResponse(response))
-            }
-            Err(_) => Err(StatusCode::BAD_REQUEST),
+                },
+                message: "Usuário atualizado com sucesso".to_string(),
+            };
+            Ok(JsonResponse(response))
         }
+        Ok(None) => Err(AppError::NotFound(format!("Usuário {} não encontrado", user_id))),
+        Err(e) => Err(e),
     }
+}
 
-    pub async fn update_user(
-        State(db): State<DatabaseConnection>,
-        Path(user_id): Path<String>,
-        Json(payload): Json<UpdateUserRequestDto>,
-    ) -> Result<JsonResponse<UpdateUserResponseDto>, StatusCode> {
-        let user_service = UserService::new(db, "redis://localhost:6379".to_string())
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-        
-        match user_service.update_user(&user_id, payload.name, payload.email).await {
-            Ok(Some(user)) => {
-                let response = UpdateUserResponseDto {
-                    user: UserResponseDto {
-                        id: user.id,
-                        email: user.email,
-                        name: user.name,
-                        role: crate::dto::UserRoleDto::from(user.role.as_str()),
-                    },
-                    message: "Usuário atualizado com sucesso".to_string(),
-                };
-                Ok(JsonResponse(response))
-            }
-            Ok(None) => Err(StatusCode::NOT_FOUND),
-            Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
-        }
-    }
+#[utoipa::path(
+    delete,
+    path = "/users/{user_id}",
+    tag = "Usuários",
+    summary = "Remover usuário",
+    params(("user_id" = String, Path, description = "Id do usuário")),
+    responses(
+        (status = 200, description = "`{ \"message\": ... }`", body = Object),
+        (status = 404, response = NotFound),
+    ),
+)]
+pub async fn delete_user(
+    State(db): State<DatabaseConnection>,
+    permissions: PermissionSet,
+    Path(user_id): Path<String>,
+) -> Result<JsonResponse<serde_json::Value>, AppError> {
+    let user_service = UserService::new(db.clone(), "redis://localhost:6379".to_string())?;
+    require_authority_over(&user_service, &db, &permissions, &user_id).await?;
 
-    pub async fn delete_user(
-        State(db): State<DatabaseConnection>,
-        Path(user_id): Path<String>,
-    ) -> Result<JsonResponse<serde_json::Value>, StatusCode> {
-        let user_service = UserService::new(db, "redis://localhost:6379".to_string())
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-        
-        match user_service.delete_user(&user_id).await {
-            Ok(true) => {
-                let response = serde_json::json!({
-                    "message": "Usuário deletado com sucesso"
-                });
-                Ok(JsonResponse(response))
-            }
-            Ok(false) => Err(StatusCode::NOT_FOUND),
-            Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    match user_service.delete_user(&user_id).await {
+        Ok(true) => {
+            let response = serde_json::json!({
+                "message": "Usuário deletado com sucesso"
+            });
+            Ok(JsonResponse(response))
         }
+        Ok(false) => Err(AppError::NotFound(format!("Usuário {} não encontrado", user_id))),
+        Err(e) => Err(e),
     }
-} 
\ No newline at end of file
+}
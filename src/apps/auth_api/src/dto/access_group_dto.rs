@@ -0,0 +1,77 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+use api_rust::libs::shared::database::repositories::access_groups::AccessGroup;
+use api_rust::libs::shared::modules::auth::permissions::{parse_permissions, PermissionSet};
+use api_rust::libs::shared::modules::validation::not_blank;
+
+// O formato de cada permissão é conferido pelo serviço, com parse_permissions
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateAccessGroupRequestDto {
+    #[validate(custom(function = "not_blank"), length(max = 100))]
+    pub name: String,
+    #[validate(length(max = 500))]
+    pub description: Option<String>,
+    #[serde(default)]
+    #[validate(length(max = 100, message = "No máximo 100 permissões por grupo"))]
+    pub permissions: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateAccessGroupRequestDto {
+    #[validate(custom(function = "not_blank"), length(max = 100))]
+    pub name: Option<String>,
+    #[validate(length(max = 500))]
+    pub description: Option<String>,
+}
+
+// Substitui a lista inteira de permissões do grupo
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdatePermissionsRequestDto {
+    #[validate(length(max = 100, message = "No máximo 100 permissões por grupo"))]
+    pub permissions: Vec<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AccessGroupResponseDto {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub permissions: Vec<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<AccessGroup> for AccessGroupResponseDto {
+    fn from(group: AccessGroup) -> Self {
+        // JSON inválido no banco aparece como grupo sem permissões, que é
+        // como o resolver o trata
+        let permissions = group
+            .permissions
+            .as_deref()
+            .and_then(|raw| parse_permissions(raw).ok())
+            .unwrap_or_default();
+
+        Self {
+            id: group.id,
+            name: group.name,
+            description: group.description,
+            permissions,
+            created_at: group.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PermissionsResponseDto {
+    pub permissions: Vec<String>,
+}
+
+impl From<&PermissionSet> for PermissionsResponseDto {
+    fn from(permissions: &PermissionSet) -> Self {
+        Self {
+            permissions: permissions.iter().map(str::to_string).collect(),
+        }
+    }
+}
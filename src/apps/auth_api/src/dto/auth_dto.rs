@@ -1,25 +1,92 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+use super::mfa_dto::MfaChallengeResponseDto;
 use super::user_dto::UserResponseDto;
+use api_rust::libs::shared::modules::auth::refresh_tokens::Session;
+use api_rust::libs::shared::modules::validation::not_blank;
 
-#[derive(Debug, Deserialize)]
+// Par de tokens emitido pelo serviço compartilhado
+pub use api_rust::libs::shared::modules::token_service::TokenPair;
+
+// Tamanho, classes de caractere etc. da senha nova ficam com a
+// PasswordPolicy, que também conhece o e-mail e o nome da conta
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
 pub struct LoginRequestDto {
+    #[validate(email)]
     pub email: String,
+    #[validate(length(min = 1, message = "Informe a senha"))]
     pub password: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate, ToSchema)]
 pub struct RegisterRequestDto {
+    #[validate(email, length(max = 254))]
     pub email: String,
+    #[validate(length(min = 1, message = "Informe a senha"))]
     pub password: String,
+    #[validate(custom(function = "not_blank"), length(max = 100))]
     pub name: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate, ToSchema)]
 pub struct RefreshTokenRequestDto {
+    #[validate(custom(function = "not_blank"))]
     pub refresh_token: String,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct ForgotPasswordRequestDto {
+    #[validate(email)]
+    pub email: String,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct ResetPasswordRequestDto {
+    #[validate(custom(function = "not_blank"))]
+    pub token: String,
+    #[validate(length(min = 1, message = "Informe a nova senha"))]
+    pub new_password: String,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct ChangePasswordRequestDto {
+    #[validate(length(min = 1, message = "Informe a senha atual"))]
+    pub current_password: String,
+    #[validate(length(min = 1, message = "Informe a nova senha"))]
+    pub new_password: String,
+}
+
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct VerifyEmailQuery {
+    #[validate(custom(function = "not_blank"))]
+    pub token: String,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct ResendVerificationRequestDto {
+    #[validate(email)]
+    pub email: String,
+}
+
+// Resposta do POST /auth/login: a sessão ou, com 2FA, o desafio a completar
+// em POST /auth/login/mfa
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(untagged)]
+pub enum LoginResultDto {
+    Session(TokenPair),
+    MfaRequired(MfaChallengeResponseDto),
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MessageResponseDto {
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
 pub struct LoginResponseDto {
     pub access_token: String,
     pub refresh_token: String,
@@ -28,22 +95,55 @@ pub struct LoginResponseDto {
     pub user: UserResponseDto,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct RegisterResponseDto {
     pub user: UserResponseDto,
     pub message: String,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct RefreshTokenResponseDto {
     pub access_token: String,
     pub expires_in: i64,
 }
 
-#[derive(Debug, Clone)]
-pub struct TokenPair {
-    pub access_token: String,
-    pub refresh_token: String,
-    pub expires_in: i64,
-    pub refresh_expires_in: i64,
-} 
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SessionResponseDto {
+    pub id: String,
+    pub device: Option<String>,
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    // Sessão do token usado nesta requisição
+    pub current: bool,
+}
+
+impl SessionResponseDto {
+    pub fn from_session(session: Session, current_session_id: Option<&str>) -> Self {
+        Self {
+            current: current_session_id == Some(session.id.as_str()),
+            id: session.id,
+            device: session.device,
+            ip: session.ip,
+            user_agent: session.user_agent,
+            created_at: session.created_at,
+            last_seen_at: session.last_used_at,
+            expires_at: session.expires_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LogoutResponseDto {
+    pub message: String,
+    pub revoked_sessions: usize,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ChangePasswordResponseDto {
+    pub message: String,
+    // Outras sessões encerradas; a atual continua ativa
+    pub revoked_sessions: usize,
+}
@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+use super::auth_dto::TokenPair;
+use api_rust::libs::shared::modules::validation::not_blank;
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct MfaLoginRequestDto {
+    #[validate(custom(function = "not_blank"))]
+    pub mfa_token: String,
+    // Código do aplicativo (6 dígitos) ou de recuperação
+    #[validate(custom(function = "not_blank"), length(max = 32))]
+    pub code: String,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct MfaChallengeEnrollRequestDto {
+    #[validate(custom(function = "not_blank"))]
+    pub mfa_token: String,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct MfaCodeRequestDto {
+    #[validate(custom(function = "not_blank"), length(max = 32))]
+    pub code: String,
+}
+
+// Resposta do login quando a conta usa (ou precisa cadastrar) 2FA
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MfaChallengeResponseDto {
+    pub mfa_required: bool,
+    pub mfa_token: String,
+    pub expires_in: i64,
+    pub enrollment_required: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MfaLoginResponseDto {
+    #[serde(flatten)]
+    pub tokens: TokenPair,
+    // Só quando o 2FA foi cadastrado neste login
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recovery_codes: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MfaEnrollmentResponseDto {
+    pub secret: String,
+    pub otpauth_uri: String,
+    // QR code da otpauth_uri, em SVG
+    pub qr_code_svg: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RecoveryCodesResponseDto {
+    pub recovery_codes: Vec<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MfaStatusResponseDto {
+    pub enabled: bool,
+    pub required: bool,
+    pub recovery_codes_remaining: u64,
+}
@@ -1,5 +1,9 @@
+pub mod access_group_dto;
 pub mod auth_dto;
+pub mod mfa_dto;
 pub mod user_dto;
 
+pub use access_group_dto::*;
 pub use auth_dto::*;
+pub use mfa_dto::*;
 pub use user_dto::*; 
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::ValidationError;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct UserResponseDto {
     pub id: String,
     pub email: String,
@@ -8,7 +10,7 @@ pub struct UserResponseDto {
     pub role: UserRoleDto,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
 pub enum UserRoleDto {
     Admin,
     Viewer,
@@ -32,3 +34,12 @@ impl ToString for UserRoleDto {
         }
     }
 }
+
+// Papéis aceitos na criação de usuário, sem diferenciar maiúsculas
+pub fn validate_role(role: &str) -> Result<(), ValidationError> {
+    if ["admin", "viewer"].iter().any(|known| role.eq_ignore_ascii_case(known)) {
+        return Ok(());
+    }
+
+    Err(ValidationError::new("role").with_message("Papel deve ser admin ou viewer".into()))
+}
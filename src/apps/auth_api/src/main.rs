@@ -1,7 +1,9 @@
-use axum::Router;
+use axum::{Extension, Router};
 use tracing_subscriber;
 use std::net::SocketAddr;
-use sea_orm::{Database, DatabaseConnection};
+use sea_orm::DatabaseConnection;
+use api_rust::libs::shared::database::connection;
+use api_rust::libs::shared::modules::rate_limit::RateLimitLayer;
 use tokio::net::TcpListener;
 
 #[tokio::main]
@@ -19,36 +21,66 @@ async fn main() {
 
     // Configurar conexão com banco de dados
     let database_url = std::env::var("DATABASE_URL")
-        .unwrap_or_else(|_| "sqlserver://localhost:1433/streaming_db".to_string());
+        .unwrap_or_else(|_| "sqlite://rust_cast.db?mode=rwc".to_string());
     
-    let db: DatabaseConnection = Database::connect(&database_url).await
+    // O banco (SQLite, Postgres...) é escolhido pelo esquema da URL
+    let db: DatabaseConnection = connection::connect(&database_url).await
         .expect("Falha ao conectar com banco de dados");
 
     // Configurar endereço
     let addr = SocketAddr::from(([127, 0, 0, 1], port));
 
-    // Criar router com state
+    // Criar router com state; a conexão também vai nas extensions para o
+    // AuthGuard resolver permissões
     let app = Router::new()
-        .nest("/auth", auth_api::routes::create_router())
+        .nest("/auth", Router::from(auth_api::routes::create_router()))
         .route("/", axum::routing::get(|| async { "Auth API - Running" }))
-        .with_state(db);
+        .with_state(db.clone())
+        .layer(Extension(db))
+        // Políticas por rota do [rate_limit] do config/project.toml
+        .layer(RateLimitLayer::shared());
 
     // Logs automáticos
     tracing::info!("🚀 Auth API iniciando em http://{}", addr);
     tracing::info!("📱 Endpoints disponíveis:");
     tracing::info!("   - POST /auth/login");
+    tracing::info!("   - POST /auth/login/mfa");
+    tracing::info!("   - POST /auth/login/mfa/enroll");
     tracing::info!("   - POST /auth/register");
     tracing::info!("   - POST /auth/refresh");
+    tracing::info!("   - GET  /auth/mfa");
+    tracing::info!("   - POST /auth/mfa/enroll");
+    tracing::info!("   - POST /auth/mfa/enroll/confirm");
+    tracing::info!("   - POST /auth/mfa/recovery-codes");
+    tracing::info!("   - DELETE /auth/mfa");
+    tracing::info!("   - GET  /auth/verify-email");
+    tracing::info!("   - POST /auth/verify-email/resend");
+    tracing::info!("   - POST /auth/password/forgot");
+    tracing::info!("   - POST /auth/password/reset");
+    tracing::info!("   - POST /auth/password/change");
+    tracing::info!("   - POST /auth/logout");
+    tracing::info!("   - POST /auth/logout-all");
+    tracing::info!("   - GET  /auth/sessions");
+    tracing::info!("   - DELETE /auth/sessions/{{id}}");
     tracing::info!("   - GET  /auth/health");
-    tracing::info!("   - GET  /auth/users/:user_id");
-    tracing::info!("   - PUT  /auth/users/:user_id");
-    tracing::info!("   - DELETE /auth/users/:user_id");
+    tracing::info!("   - GET  /auth/.well-known/jwks.json");
+    tracing::info!("   - GET  /auth/users/{{user_id}}");
+    tracing::info!("   - PUT  /auth/users/{{user_id}}");
+    tracing::info!("   - DELETE /auth/users/{{user_id}}");
+    tracing::info!("   - DELETE /auth/users/{{user_id}}/lockout");
+    tracing::info!("   - GET  /auth/me/permissions");
+    tracing::info!("   - GET  /auth/access-groups");
+    tracing::info!("   - POST /auth/access-groups");
+    tracing::info!("   - PUT  /auth/access-groups/{{group_id}}/permissions");
+    tracing::info!("   - PUT  /auth/users/{{user_id}}/access-groups/{{group_id}}");
+    tracing::info!("   - DELETE /auth/users/{{user_id}}/access-groups/{{group_id}}");
     tracing::info!("   - GET  /");
 
     // ✅ Nova forma no Axum 0.8
     let listener = TcpListener::bind(addr).await.unwrap();
     tracing::info!("✅ Auth API pronta e rodando!");
-    axum::serve(listener, app)
+    // ConnectInfo: IP do cliente registrado nas sessões
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
         .await
         .unwrap();
 }
@@ -1,21 +1,75 @@
-use axum::{routing::{post, get, put, delete}, Router};
+use axum::middleware;
 use sea_orm::DatabaseConnection;
+use utoipa_axum::{router::OpenApiRouter, routes};
+use api_rust::libs::shared::logging::correlation::correlation_id;
+use api_rust::libs::shared::modules::auth::access_control::{allow_unverified, public, require_permission};
+use api_rust::libs::shared::modules::auth::permissions::{GROUPS_MANAGE, USERS_MANAGE, USERS_READ};
+use api_rust::libs::shared::modules::openapi::GuardedRoutes;
+use crate::controllers::access_group_controller;
+use crate::controllers::auth_controller;
+use crate::controllers::mfa_controller;
+use crate::controllers::user_controller;
 
-pub fn create_router() -> Router<DatabaseConnection> {
-    Router::new()
+// O caminho e o método de cada rota vêm do `#[utoipa::path]` do handler; o
+// mesmo router gera o documento OpenAPI
+pub fn create_router() -> OpenApiRouter<DatabaseConnection> {
+    OpenApiRouter::new()
         // Rotas de autenticação
-        .route("/login", post(crate::controllers::auth_controller::login))
-        .route("/register", post(crate::controllers::auth_controller::register))
-        .route("/refresh", post(crate::controllers::auth_controller::refresh_token))
-        .route("/health", get(crate::controllers::auth_controller::health))
-        
+        .routes(routes!(auth_controller::login).with_guard(public()))
+        .routes(routes!(auth_controller::register).with_guard(public()))
+        .routes(routes!(auth_controller::refresh_token).with_guard(public()))
+        .routes(routes!(auth_controller::health).with_guard(public()))
+        .routes(routes!(auth_controller::jwks).with_guard(public()))
+
+        // Autenticação em dois fatores
+        .routes(routes!(auth_controller::login_mfa).with_guard(public()))
+        .routes(routes!(mfa_controller::enroll_from_challenge).with_guard(public()))
+        .routes(routes!(mfa_controller::status).with_guard(allow_unverified()))
+        .routes(routes!(mfa_controller::disable).with_guard(allow_unverified()))
+        .routes(routes!(mfa_controller::enroll).with_guard(allow_unverified()))
+        .routes(routes!(mfa_controller::confirm_enrollment).with_guard(allow_unverified()))
+        .routes(routes!(mfa_controller::regenerate_recovery_codes).with_guard(allow_unverified()))
+
+        // Confirmação de e-mail
+        .routes(routes!(auth_controller::verify_email).with_guard(public()))
+        .routes(routes!(auth_controller::resend_verification).with_guard(public()))
+
+        // Senha
+        .routes(routes!(auth_controller::forgot_password).with_guard(public()))
+        .routes(routes!(auth_controller::reset_password).with_guard(public()))
+        .routes(routes!(auth_controller::change_password).with_guard(allow_unverified()))
+
+        // Sessões do próprio usuário
+        .routes(routes!(auth_controller::logout).with_guard(allow_unverified()))
+        .routes(routes!(auth_controller::logout_all).with_guard(allow_unverified()))
+        .routes(routes!(auth_controller::list_sessions).with_guard(allow_unverified()))
+        .routes(routes!(auth_controller::revoke_session).with_guard(allow_unverified()))
+        .routes(routes!(access_group_controller::my_permissions).with_guard(allow_unverified()))
+
         // Rotas de usuários (protegidas)
-        .route("/users", get(crate::controllers::user_controller::list_users))
-        .route("/users/count", get(crate::controllers::user_controller::count_users))
-        .route("/users/by-role", get(crate::controllers::user_controller::get_users_by_role))
-        .route("/users/:user_id", get(crate::controllers::user_controller::get_user_by_id))
-        .route("/users/email/:email", get(crate::controllers::user_controller::get_user_by_email))
-        .route("/users", post(crate::controllers::user_controller::create_user))
-        .route("/users/:user_id", put(crate::controllers::user_controller::update_user))
-        .route("/users/:user_id", delete(crate::controllers::user_controller::delete_user))
-} 
\ No newline at end of file
+        .routes(routes!(user_controller::list_users).with_guard(require_permission(USERS_READ)))
+        .routes(routes!(user_controller::count_users).with_guard(require_permission(USERS_READ)))
+        .routes(routes!(user_controller::get_users_by_role).with_guard(require_permission(USERS_READ)))
+        .routes(routes!(user_controller::get_user_by_id).with_guard(require_permission(USERS_READ)))
+        .routes(routes!(user_controller::get_user_by_email).with_guard(require_permission(USERS_READ)))
+        .routes(routes!(user_controller::create_user).with_guard(require_permission(USERS_MANAGE)))
+        .routes(routes!(user_controller::update_user).with_guard(require_permission(USERS_MANAGE)))
+        .routes(routes!(user_controller::delete_user).with_guard(require_permission(USERS_MANAGE)))
+        .routes(routes!(auth_controller::revoke_user_sessions).with_guard(require_permission(USERS_MANAGE)))
+        .routes(routes!(auth_controller::unlock_user).with_guard(require_permission(USERS_MANAGE)))
+
+        // Grupos de acesso e permissões; atribuir grupo é groups:manage, e
+        // não users:manage, para que gerir usuários não permita se promover
+        .routes(routes!(access_group_controller::known_permissions).with_guard(require_permission(GROUPS_MANAGE)))
+        .routes(routes!(access_group_controller::list_groups).with_guard(require_permission(GROUPS_MANAGE)))
+        .routes(routes!(access_group_controller::create_group).with_guard(require_permission(GROUPS_MANAGE)))
+        .routes(routes!(access_group_controller::update_group).with_guard(require_permission(GROUPS_MANAGE)))
+        .routes(routes!(access_group_controller::delete_group).with_guard(require_permission(GROUPS_MANAGE)))
+        .routes(routes!(access_group_controller::update_group_permissions).with_guard(require_permission(GROUPS_MANAGE)))
+        .routes(routes!(access_group_controller::list_user_groups).with_guard(require_permission(USERS_READ)))
+        .routes(routes!(access_group_controller::assign_group).with_guard(require_permission(GROUPS_MANAGE)))
+        .routes(routes!(access_group_controller::revoke_group).with_guard(require_permission(GROUPS_MANAGE)))
+
+        // Id de correlação usado nos erros problem+json
+        .layer(middleware::from_fn(correlation_id))
+}
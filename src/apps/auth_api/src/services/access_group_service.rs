@@ -0,0 +1,192 @@
+use sea_orm::DatabaseConnection;
+use crate::dto::{AccessGroupResponseDto, CreateAccessGroupRequestDto, UpdateAccessGroupRequestDto};
+use api_rust::errors::AppError;
+use api_rust::libs::shared::database::repositories::access_groups::{
+    AccessGroupsRepository, CreateAccessGroupRequest, UpdateAccessGroupRequest,
+};
+use api_rust::libs::shared::database::repositories::users::UsersRepository;
+use api_rust::libs::shared::modules::auth::permissions::{is_valid_permission, PermissionResolver};
+
+// Gestão dos grupos de acesso e dos vínculos com usuários. Toda mudança
+// invalida o cache de permissões dos usuários afetados.
+pub struct AccessGroupService {
+    db: DatabaseConnection,
+    permissions: &'static PermissionResolver,
+}
+
+impl AccessGroupService {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self {
+            db,
+            permissions: PermissionResolver::shared(),
+        }
+    }
+
+    fn repository(&self) -> AccessGroupsRepository {
+        AccessGroupsRepository::new(self.db.clone())
+    }
+
+    pub async fn list_groups(&self) -> Result<Vec<AccessGroupResponseDto>, AppError> {
+        let groups = self.repository().find_all().await?;
+        Ok(groups.into_iter().map(AccessGroupResponseDto::from).collect())
+    }
+
+    pub async fn create_group(&self, request: CreateAccessGroupRequestDto) -> Result<AccessGroupResponseDto, AppError> {
+        let name = validate_name(&request.name)?;
+        let permissions = permissions_json(&request.permissions)?;
+        self.ensure_name_available(&name, None).await?;
+
+        let group = self
+            .repository()
+            .create(CreateAccessGroupRequest {
+                name,
+                description: request.description,
+                permissions: Some(permissions),
+            })
+            .await?;
+
+        Ok(group.into())
+    }
+
+    pub async fn update_group(&self, group_id: &str, request: UpdateAccessGroupRequestDto) -> Result<AccessGroupResponseDto, AppError> {
+        let name = match request.name.as_deref() {
+            Some(name) => {
+                let name = validate_name(name)?;
+                self.ensure_name_available(&name, Some(group_id)).await?;
+                Some(name)
+            }
+            None => None,
+        };
+
+        let group = self
+            .repository()
+            .update(group_id, UpdateAccessGroupRequest {
+                name,
+                description: request.description,
+                permissions: None,
+            })
+            .await?
+            .ok_or_else(|| group_not_found(group_id))?;
+
+        Ok(group.into())
+    }
+
+    pub async fn set_permissions(&self, group_id: &str, permissions: &[String]) -> Result<AccessGroupResponseDto, AppError> {
+        let permissions = permissions_json(permissions)?;
+
+        let group = self
+            .repository()
+            .update(group_id, UpdateAccessGroupRequest {
+                name: None,
+                description: None,
+                permissions: Some(permissions),
+            })
+            .await?
+            .ok_or_else(|| group_not_found(group_id))?;
+
+        self.permissions.invalidate_group(group_id).await;
+
+        Ok(group.into())
+    }
+
+    pub async fn delete_group(&self, group_id: &str) -> Result<(), AppError> {
+        if !self.repository().delete(group_id).await? {
+            return Err(group_not_found(group_id));
+        }
+
+        self.permissions.invalidate_group(group_id).await;
+
+        Ok(())
+    }
+
+    pub async fn user_groups(&self, user_id: &str) -> Result<Vec<AccessGroupResponseDto>, AppError> {
+        self.ensure_user_exists(user_id).await?;
+
+        let groups = self.repository().find_by_user(user_id).await?;
+        Ok(groups.into_iter().map(AccessGroupResponseDto::from).collect())
+    }
+
+    // `assigned_by` é quem fez a atribuição (sub do token do admin)
+    pub async fn assign(&self, user_id: &str, group_id: &str, assigned_by: &str) -> Result<Vec<AccessGroupResponseDto>, AppError> {
+        self.ensure_user_exists(user_id).await?;
+
+        let repository = self.repository();
+
+        if repository.find_by_id(group_id).await?.is_none() {
+            return Err(group_not_found(group_id));
+        }
+
+        if repository.is_assigned(user_id, group_id).await? {
+            return Err(AppError::Conflict(format!(
+                "Usuário {} já pertence ao grupo {}",
+                user_id, group_id
+            )));
+        }
+
+        repository.assign(user_id, group_id, Some(assigned_by)).await?;
+        self.permissions.invalidate_user(user_id).await;
+
+        tracing::info!("Grupo {} atribuído ao usuário {} por {}", group_id, user_id, assigned_by);
+
+        self.user_groups(user_id).await
+    }
+
+    pub async fn unassign(&self, user_id: &str, group_id: &str, revoked_by: &str) -> Result<(), AppError> {
+        if !self.repository().unassign(user_id, group_id).await? {
+            return Err(AppError::NotFound(format!(
+                "Usuário {} não pertence ao grupo {}",
+                user_id, group_id
+            )));
+        }
+
+        self.permissions.invalidate_user(user_id).await;
+
+        tracing::info!("Grupo {} removido do usuário {} por {}", group_id, user_id, revoked_by);
+
+        Ok(())
+    }
+
+    async fn ensure_user_exists(&self, user_id: &str) -> Result<(), AppError> {
+        match UsersRepository::new(self.db.clone()).find_by_id(user_id).await? {
+            Some(_) => Ok(()),
+            None => Err(AppError::NotFound(format!("Usuário {} não encontrado", user_id))),
+        }
+    }
+
+    async fn ensure_name_available(&self, name: &str, group_id: Option<&str>) -> Result<(), AppError> {
+        match self.repository().find_by_name(name).await? {
+            Some(existing) if Some(existing.id.as_str()) != group_id => {
+                Err(AppError::Conflict(format!("Já existe um grupo chamado {}", name)))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+fn group_not_found(group_id: &str) -> AppError {
+    AppError::NotFound(format!("Grupo {} não encontrado", group_id))
+}
+
+fn validate_name(name: &str) -> Result<String, AppError> {
+    let name = name.trim();
+
+    if name.is_empty() {
+        return Err(AppError::Validation("Nome do grupo é obrigatório".to_string()));
+    }
+
+    Ok(name.to_string())
+}
+
+// Valida e serializa no formato canônico da coluna (lista ordenada)
+fn permissions_json(permissions: &[String]) -> Result<String, AppError> {
+    let mut permissions: Vec<&str> = permissions.iter().map(|p| p.trim()).collect();
+
+    if let Some(unknown) = permissions.iter().find(|p| !is_valid_permission(p)) {
+        return Err(AppError::Validation(format!("Permissão desconhecida: {}", unknown)));
+    }
+
+    permissions.sort_unstable();
+    permissions.dedup();
+
+    Ok(serde_json::to_string(&permissions)?)
+}
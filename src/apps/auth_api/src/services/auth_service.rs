@@ -1,165 +1,231 @@
 use sea_orm::DatabaseConnection;
 use crate::dto::TokenPair;
-use crate::services::user_service::UserService;
-use jsonwebtoken::{encode, EncodingKey, Header};
-use chrono::{Duration, Utc};
-use uuid::Uuid;
+use api_rust::errors::AppError;
+use api_rust::libs::shared::modules::auth::access_control::load_user_with_access;
+use api_rust::libs::shared::modules::auth::email_verification::UnverifiedLoginPolicy;
+use api_rust::libs::shared::modules::auth::login_throttle::LoginThrottle;
+use api_rust::libs::shared::modules::auth::password_policy::PasswordPolicy;
+use api_rust::libs::shared::logging::{log_authentication_failure, log_authentication_success};
+use api_rust::libs::shared::modules::auth::refresh_tokens::{ClientInfo, RefreshTokenStore, Session};
+use api_rust::libs::shared::modules::auth::revocation::RevocationList;
+use api_rust::libs::shared::modules::token_service::{Claims, TokenSubject};
 
 // Importar o repositório de usuários
 use api_rust::libs::shared::database::repositories::users::{UsersRepository, LoginRequest as RepoLoginRequest, CreateUserRequest};
+use super::email_verification_service::EmailVerificationService;
+use super::mfa_service::{MfaChallenge, MfaService};
+
+// Resultado do login: contas com 2FA recebem um desafio no lugar dos tokens
+pub enum LoginOutcome {
+    Session(TokenPair),
+    MfaRequired(MfaChallenge),
+}
+
+// Resultado do cadastro: com a política `block`, a conta só recebe tokens
+// depois de confirmar o e-mail
+pub enum RegisterOutcome {
+    Session(TokenPair),
+    PendingVerification,
+}
 
 pub struct AuthService {
-    user_service: UserService,
+    db: DatabaseConnection,
+    sessions: &'static RefreshTokenStore,
+    revocations: &'static RevocationList,
+    policy: UnverifiedLoginPolicy,
+    throttle: &'static LoginThrottle,
 }
 
 impl AuthService {
-    pub fn new(db: DatabaseConnection, jwt_secret: String) -> Result<Self, jsonwebtoken::errors::Error> {
-        let redis_url = std::env::var("REDIS_URL")
-            .unwrap_or_else(|_| "redis://localhost:6379".to_string());
-        
-        let user_service = UserService::new(db, redis_url)
-            .map_err(|e| jsonwebtoken::errors::Error::from(
-                jsonwebtoken::errors::ErrorKind::InvalidToken
-            ))?;
-        
-        Ok(Self {
-            user_service,
-        })
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self {
+            db,
+            sessions: RefreshTokenStore::shared(),
+            revocations: RevocationList::shared(),
+            policy: UnverifiedLoginPolicy::shared(),
+            throttle: LoginThrottle::shared(),
+        }
     }
 
-    pub async fn login(&self, email: &str, password: &str) -> Result<TokenPair, String> {
-        let users_repo = UsersRepository::new(self.user_service.db.clone());
-        
+    pub async fn login(&self, email: &str, password: &str, client: &ClientInfo) -> Result<LoginOutcome, AppError> {
+        let users_repo = UsersRepository::new(self.db.clone());
+        let ip = client.remote_ip.as_deref();
+
+        // Conta ou IP bloqueados não chegam a conferir a senha
+        self.throttle.check(email, ip).await?;
+
         // Criar request para o repositório
         let login_request = RepoLoginRequest {
             email: email.to_string(),
             password: password.to_string(),
         };
-        
+
         // Tentar autenticar usando o repositório
-        match users_repo.authenticate(&login_request).await {
-            Ok(Some(user)) => {
-                // Gerar tokens para o usuário autenticado
-                let token_pair = self.generate_token_pair(&user.id, &user.email)
-                    .map_err(|e| format!("Erro ao gerar tokens: {}", e))?;
+        match users_repo.authenticate(&login_request).await? {
+            Some(user) => {
+                log_authentication_success(&user.id);
+
+                // Checado só depois da senha, para não revelar o estado da conta
+                if !user.is_email_verified() && !self.policy.issues_tokens_to_unverified() {
+                    return Err(AppError::Forbidden("Confirme o seu e-mail antes de entrar".to_string()));
+                }
+
+                let subject = self.load_subject(&user.id).await?;
+                let mfa = MfaService::new(self.db.clone())?;
+
+                if mfa.is_enabled(&user.id).await? {
+                    return Ok(LoginOutcome::MfaRequired(mfa.issue_challenge(&user.id, false).await?));
+                }
+
+                if mfa.is_required_for(&subject.access_groups) {
+                    return Ok(LoginOutcome::MfaRequired(mfa.issue_challenge(&user.id, true).await?));
+                }
 
-                Ok(token_pair)
+                // Com 2FA as falhas só são zeradas depois do código certo
+                self.throttle.record_success(email).await;
+
+                let tokens = self.sessions.start_session(&subject, client).await?;
+                Ok(LoginOutcome::Session(tokens))
+            }
+            None => {
+                self.throttle.record_failure(email, ip).await;
+                log_authentication_failure(email, "credenciais inválidas");
+
+                Err(AppError::Auth("Credenciais inválidas".to_string()))
             }
-            Ok(None) => Err("Credenciais inválidas".to_string()),
-            Err(e) => Err(format!("Erro na autenticação: {}", e)),
         }
     }
 
-    pub async fn register(&self, email: &str, password: &str, name: &str, role: Option<String>) -> Result<TokenPair, String> {
-        let users_repo = UsersRepository::new(self.user_service.db.clone());
-        
+    // Desbloqueio manual de uma conta bloqueada por tentativas de login.
+    // Devolve se havia bloqueio ou espera.
+    pub async fn unlock_account(&self, user_id: &str) -> Result<bool, AppError> {
+        let user = UsersRepository::new(self.db.clone())
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Usuário {} não encontrado", user_id)))?;
+
+        self.throttle.unlock(&user.email).await
+    }
+
+    // Segundo passo do login: troca o desafio e o código pelos tokens. Se o
+    // 2FA foi cadastrado neste passo, devolve também os códigos de recuperação.
+    pub async fn complete_mfa_login(
+        &self,
+        mfa_token: &str,
+        code: &str,
+        client: &ClientInfo,
+    ) -> Result<(TokenPair, Option<Vec<String>>), AppError> {
+        let login = MfaService::new(self.db.clone())?
+            .complete_challenge(mfa_token, code, client.remote_ip.as_deref())
+            .await?;
+
+        let subject = self.load_subject(&login.user_id).await?;
+        self.throttle.record_success(&subject.email).await;
+
+        let tokens = self.sessions.start_session(&subject, client).await?;
+
+        Ok((tokens, login.recovery_codes))
+    }
+
+    pub async fn register(
+        &self,
+        email: &str,
+        password: &str,
+        name: &str,
+        role: Option<String>,
+        client: &ClientInfo,
+    ) -> Result<RegisterOutcome, AppError> {
+        PasswordPolicy::shared().validate("password", password, email, name)?;
+
+        let users_repo = UsersRepository::new(self.db.clone());
+
+        if users_repo.find_by_email(email).await?.is_some() {
+            return Err(AppError::Conflict("Email já cadastrado".to_string()));
+        }
+
         // Criar request para o repositório
         let create_request = CreateUserRequest {
             email: email.to_string(),
             password: password.to_string(),
             name: name.to_string(),
             role,
+            email_verified: false,
         };
-        
+
         // Criar usuário usando o repositório
-        match users_repo.create(create_request).await {
-            Ok(user) => {
-                // Gerar tokens para o usuário criado
-                let token_pair = self.generate_token_pair(&user.id, &user.email)
-                    .map_err(|e| format!("Erro ao gerar tokens: {}", e))?;
+        let user = users_repo.create(create_request).await?;
 
-                Ok(token_pair)
-            }
-            Err(e) => Err(format!("Erro ao criar usuário: {}", e)),
-        }
-    }
+        EmailVerificationService::new(self.db.clone())?
+            .send_verification(&user)
+            .await?;
 
-    pub async fn refresh_token(&self, refresh_token: &str) -> Result<TokenPair, String> {
-        // TODO: Implementar refresh real com validação do token
-        // Por enquanto, refresh mock
-        let claims = self.validate_refresh_token(refresh_token)
-            .map_err(|e| format!("Token de refresh inválido: {}", e))?;
+        if !self.policy.issues_tokens_to_unverified() {
+            return Ok(RegisterOutcome::PendingVerification);
+        }
 
-        let token_pair = self.generate_token_pair(&claims.sub, &claims.email)
-            .map_err(|e| format!("Erro ao gerar tokens: {}", e))?;
+        let subject = self.load_subject(&user.id).await?;
+        let tokens = self.sessions.start_session(&subject, client).await?;
 
-        Ok(token_pair)
+        Ok(RegisterOutcome::Session(tokens))
     }
 
-    fn generate_token_pair(&self, user_id: &str, email: &str) -> Result<TokenPair, jsonwebtoken::errors::Error> {
-        let now = Utc::now();
-        let access_exp = now + Duration::hours(1); // 1 hora
-        let refresh_exp = now + Duration::days(7);  // 7 dias
-
-        let jwt_secret = std::env::var("JWT_ACCESS_SECRET")
-            .unwrap_or_else(|_| "default_secret".to_string());
+    // Troca o refresh token por um novo par na mesma sessão. O token antigo
+    // só deixa de valer quando o novo é gravado; reapresentá-lo depois disso
+    // revoga a sessão.
+    pub async fn refresh_token(&self, refresh_token: &str, client: &ClientInfo) -> Result<TokenPair, AppError> {
+        let claims = self.sessions.validate(refresh_token).await?;
+
+        // Role e grupos são recarregados: o novo par reflete mudanças feitas
+        // depois da emissão do refresh token
+        let mut subject = self.load_subject(&claims.sub).await?;
+        subject.session_id = claims.sid.clone();
+
+        // Sessões abertas antes de a conta passar a exigir 2FA não renovam
+        let mfa = MfaService::new(self.db.clone())?;
+        if mfa.is_required_for(&subject.access_groups) && !mfa.is_enabled(&subject.user_id).await? {
+            return Err(AppError::Auth(
+                "Autenticação em dois fatores obrigatória; entre novamente".to_string(),
+            ));
+        }
 
-        // Access Token
-        let access_claims = Claims {
-            sub: user_id.to_string(),
-            email: email.to_string(),
-            exp: access_exp.timestamp(),
-            iat: now.timestamp(),
-            jti: Uuid::new_v4().to_string(),
-            token_type: "access".to_string(),
-        };
+        self.sessions.rotate(&claims, &subject, client).await
+    }
 
-        let access_token = encode(
-            &Header::default(),
-            &access_claims,
-            &EncodingKey::from_secret(jwt_secret.as_ref()),
-        )?;
+    // Encerra a sessão do token apresentado. O próprio access token entra na
+    // denylist, mesmo que não pertença a nenhuma sessão.
+    pub async fn logout(&self, claims: &Claims) -> Result<(), AppError> {
+        self.revocations.revoke_token(&claims.jti, claims.exp).await?;
 
-        // Refresh Token
-        let refresh_claims = Claims {
-            sub: user_id.to_string(),
-            email: email.to_string(),
-            exp: refresh_exp.timestamp(),
-            iat: now.timestamp(),
-            jti: Uuid::new_v4().to_string(),
-            token_type: "refresh".to_string(),
-        };
+        if let Some(session_id) = &claims.sid {
+            self.sessions.revoke_session(&claims.sub, session_id).await?;
+        }
 
-        let refresh_token = encode(
-            &Header::default(),
-            &refresh_claims,
-            &EncodingKey::from_secret(jwt_secret.as_ref()),
-        )?;
-
-        Ok(TokenPair {
-            access_token,
-            refresh_token,
-            expires_in: access_exp.timestamp(),
-            refresh_expires_in: refresh_exp.timestamp(),
-        })
+        Ok(())
     }
 
-    fn validate_refresh_token(&self, token: &str) -> Result<Claims, jsonwebtoken::errors::Error> {
-        let jwt_secret = std::env::var("JWT_ACCESS_SECRET")
-            .unwrap_or_else(|_| "default_secret".to_string());
+    // Encerra todas as sessões do usuário, em todos os dispositivos
+    pub async fn logout_all(&self, user_id: &str) -> Result<usize, AppError> {
+        self.sessions.revoke_all(user_id).await
+    }
 
-        let token_data = jsonwebtoken::decode::<Claims>(
-            token,
-            &jsonwebtoken::DecodingKey::from_secret(jwt_secret.as_ref()),
-            &jsonwebtoken::Validation::default(),
-        )?;
+    pub async fn list_sessions(&self, user_id: &str) -> Result<Vec<Session>, AppError> {
+        self.sessions.list_sessions(user_id).await
+    }
 
-        if token_data.claims.token_type != "refresh" {
-            return Err(jsonwebtoken::errors::Error::from(
-                jsonwebtoken::errors::ErrorKind::InvalidToken
-            ));
+    pub async fn revoke_session(&self, user_id: &str, session_id: &str) -> Result<(), AppError> {
+        if self.sessions.revoke_session(user_id, session_id).await? {
+            Ok(())
+        } else {
+            Err(AppError::NotFound(format!("Sessão {} não encontrada", session_id)))
         }
+    }
 
-        Ok(token_data.claims)
+    // Role e grupos de acesso atuais do usuário
+    async fn load_subject(&self, user_id: &str) -> Result<TokenSubject, AppError> {
+        let user = load_user_with_access(&self.db, user_id)
+            .await?
+            .ok_or_else(|| AppError::Auth("Usuário não encontrado".to_string()))?;
+
+        Ok(TokenSubject::from(&user))
     }
 }
-
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
-struct Claims {
-    pub sub: String,        // User ID
-    pub email: String,      // User email
-    pub exp: i64,           // Expiration time
-    pub iat: i64,           // Issued at
-    pub jti: String,        // JWT ID (unique identifier)
-    pub token_type: String, // "access" or "refresh"
-} 
\ No newline at end of file
@@ -1,6 +1,8 @@
 use sea_orm::DatabaseConnection;
 use crate::models::user::User;
 use crate::services::user_service::UserService;
+use api_rust::errors::AppError;
+use api_rust::libs::shared::modules::auth::password_policy::PasswordPolicy;
 use chrono::Utc;
 
 pub struct CreateUserService {
@@ -13,7 +15,9 @@ impl CreateUserService {
         Ok(Self { user_service })
     }
 
-    pub async fn create_user(&self, email: &str, password: &str, name: &str, role: Option<String>) -> Result<User, String> {
+    pub async fn create_user(&self, email: &str, password: &str, name: &str, role: Option<String>) -> Result<User, AppError> {
+        PasswordPolicy::shared().validate("password", password, email, name)?;
+
         // Por enquanto, criar usuário mock
         // TODO: Implementar criação real no banco
         let user = User {
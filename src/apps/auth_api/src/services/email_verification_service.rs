@@ -0,0 +1,99 @@
+use std::sync::Arc;
+use chrono::{Duration, Utc};
+use sea_orm::DatabaseConnection;
+use api_rust::config::Config;
+use api_rust::errors::AppError;
+use api_rust::libs::shared::database::repositories::users::{User, UsersRepository};
+use api_rust::libs::shared::modules::auth::one_time_tokens::{OneTimeTokens, EMAIL_VERIFICATION};
+use api_rust::libs::shared::modules::mailer::{self, EmailMessage, Mailer};
+
+// Confirmação do e-mail informado no cadastro
+pub struct EmailVerificationService {
+    db: DatabaseConnection,
+    tokens: OneTimeTokens,
+    mailer: Arc<dyn Mailer>,
+    verification_url: String,
+    token_ttl: Duration,
+    resend_limit: usize,
+    resend_window: Duration,
+}
+
+impl EmailVerificationService {
+    pub fn new(db: DatabaseConnection) -> Result<Self, AppError> {
+        let config = Config::from_env()?;
+
+        Ok(Self {
+            tokens: OneTimeTokens::new(db.clone()),
+            db,
+            mailer: mailer::shared(),
+            verification_url: config.email_verification_url,
+            token_ttl: Duration::hours(config.email_verification_ttl_hours as i64),
+            resend_limit: config.email_verification_resend_limit,
+            resend_window: Duration::minutes(config.email_verification_resend_window_minutes as i64),
+        })
+    }
+
+    // Emite um token novo (o anterior deixa de valer) e envia o link. Falha
+    // no envio só vai para o log: o usuário pode pedir o reenvio.
+    pub async fn send_verification(&self, user: &User) -> Result<(), AppError> {
+        let token = self.tokens.issue(&user.id, EMAIL_VERIFICATION, self.token_ttl).await?;
+        let separator = if self.verification_url.contains('?') { '&' } else { '?' };
+        let link = format!("{}{}token={}", self.verification_url, separator, token);
+
+        let message = EmailMessage {
+            to: user.email.clone(),
+            subject: "Confirme o seu e-mail".to_string(),
+            body: format!(
+                "Olá, {}.\n\n\
+                 Para ativar a sua conta, confirme o seu e-mail pelo link abaixo \
+                 (válido por {} horas):\n\n{}\n\n\
+                 Se você não criou uma conta, ignore este e-mail.",
+                user.name,
+                self.token_ttl.num_hours(),
+                link
+            ),
+        };
+
+        if let Err(e) = self.mailer.send(&message).await {
+            tracing::warn!("Erro ao enviar confirmação de e-mail para o usuário {}: {}", user.id, e);
+        }
+
+        Ok(())
+    }
+
+    pub async fn verify(&self, token: &str) -> Result<(), AppError> {
+        let user_id = self
+            .tokens
+            .consume(EMAIL_VERIFICATION, token)
+            .await?
+            .ok_or_else(|| AppError::Validation("Link de confirmação inválido ou expirado".to_string()))?;
+
+        UsersRepository::new(self.db.clone()).mark_email_verified(&user_id).await?;
+        tracing::info!("E-mail do usuário {} confirmado", user_id);
+
+        Ok(())
+    }
+
+    // Contas inexistentes ou já confirmadas recebem a mesma resposta, sem
+    // e-mail. O envio do cadastro conta para o limite; acima dele o pedido é
+    // ignorado em silêncio, já que um 429 só para contas pendentes diria
+    // quais e-mails estão cadastrados.
+    pub async fn resend(&self, email: &str) -> Result<(), AppError> {
+        let user = match UsersRepository::new(self.db.clone()).find_by_email(email.trim()).await? {
+            Some(user) if !user.is_email_verified() => user,
+            _ => return Ok(()),
+        };
+
+        let issued = self
+            .tokens
+            .issued_since(&user.id, EMAIL_VERIFICATION, Utc::now() - self.resend_window)
+            .await?;
+
+        if issued.len() >= self.resend_limit {
+            tracing::info!("Limite de reenvios da confirmação atingido para o usuário {}", user.id);
+            return Ok(());
+        }
+
+        self.send_verification(&user).await
+    }
+}
@@ -1,6 +1,7 @@
 use sea_orm::DatabaseConnection;
 use crate::models::user::User;
 use crate::services::user_service::UserService;
+use api_rust::errors::AppError;
 
 pub struct FindUserByIdService {
     user_service: UserService,
@@ -12,11 +13,11 @@ impl FindUserByIdService {
         Ok(Self { user_service })
     }
 
-    pub async fn find_by_id(&self, user_id: &str) -> Result<Option<User>, String> {
+    pub async fn find_by_id(&self, user_id: &str) -> Result<Option<User>, AppError> {
         self.user_service.get_user_by_id(user_id).await
     }
 
-    pub async fn find_by_email(&self, email: &str) -> Result<Option<User>, String> {
+    pub async fn find_by_email(&self, email: &str) -> Result<Option<User>, AppError> {
         self.user_service.get_user_by_email(email).await
     }
 } 
\ No newline at end of file
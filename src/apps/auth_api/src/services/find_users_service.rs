@@ -1,6 +1,7 @@
 use sea_orm::DatabaseConnection;
 use crate::models::user::User;
 use crate::services::user_service::UserService;
+use api_rust::errors::AppError;
 
 pub struct FindUsersService {
     user_service: UserService,
@@ -12,15 +13,15 @@ impl FindUsersService {
         Ok(Self { user_service })
     }
 
-    pub async fn find_all(&self, limit: Option<u64>, offset: Option<u64>) -> Result<Vec<User>, String> {
+    pub async fn find_all(&self, limit: Option<u64>, offset: Option<u64>) -> Result<Vec<User>, AppError> {
         self.user_service.find_all(limit, offset).await
     }
 
-    pub async fn find_by_role(&self, role: &str) -> Result<Vec<User>, String> {
+    pub async fn find_by_role(&self, role: &str) -> Result<Vec<User>, AppError> {
         self.user_service.find_by_role(role).await
     }
 
-    pub async fn count(&self) -> Result<u64, String> {
+    pub async fn count(&self) -> Result<u64, AppError> {
         self.user_service.count().await
     }
 } 
\ No newline at end of file
@@ -0,0 +1,306 @@
+use chrono::{Duration, Utc};
+use sea_orm::DatabaseConnection;
+use api_rust::config::Config;
+use api_rust::errors::AppError;
+use api_rust::libs::shared::database::repositories::user_mfa::{UserMfa, UserMfaRepository};
+use api_rust::libs::shared::database::repositories::users::UsersRepository;
+use api_rust::libs::shared::modules::auth::access_control::AccessGroupEnum;
+use api_rust::libs::shared::modules::auth::login_throttle::LoginThrottle;
+use api_rust::libs::shared::modules::auth::mfa::{generate_recovery_codes, recovery_code_hash, MfaPolicy};
+use api_rust::libs::shared::modules::auth::one_time_tokens::{OneTimeTokens, MFA_CHALLENGE};
+use api_rust::libs::shared::modules::auth::totp::{self, Totp};
+
+pub struct MfaStatus {
+    pub enabled: bool,
+    pub required: bool,
+    pub recovery_codes_remaining: u64,
+}
+
+// Dados para cadastrar o segredo no aplicativo autenticador
+pub struct MfaEnrollment {
+    pub secret: String,
+    pub otpauth_uri: String,
+    pub qr_code_svg: String,
+}
+
+// Segundo passo do login, devolvido no lugar dos tokens
+pub struct MfaChallenge {
+    pub mfa_token: String,
+    pub expires_in: i64,
+    // Conta obrigada a ter 2FA que ainda não cadastrou: o token de desafio
+    // também serve para o cadastro
+    pub enrollment_required: bool,
+}
+
+// Resultado do segundo passo: o usuário e, se o 2FA foi cadastrado agora,
+// os códigos de recuperação
+pub struct MfaLogin {
+    pub user_id: String,
+    pub recovery_codes: Option<Vec<String>>,
+}
+
+// Autenticação em dois fatores por TOTP
+pub struct MfaService {
+    db: DatabaseConnection,
+    repository: UserMfaRepository,
+    tokens: OneTimeTokens,
+    policy: MfaPolicy,
+    throttle: &'static LoginThrottle,
+    issuer: String,
+    challenge_ttl: Duration,
+    max_attempts: i32,
+    recovery_code_count: usize,
+}
+
+impl MfaService {
+    pub fn new(db: DatabaseConnection) -> Result<Self, AppError> {
+        let config = Config::from_env()?;
+
+        Ok(Self {
+            repository: UserMfaRepository::new(db.clone()),
+            tokens: OneTimeTokens::new(db.clone()),
+            db,
+            policy: MfaPolicy::shared(),
+            throttle: LoginThrottle::shared(),
+            issuer: config.mfa_issuer,
+            challenge_ttl: Duration::seconds(config.mfa_challenge_ttl_seconds as i64),
+            max_attempts: config.mfa_max_attempts.max(1),
+            recovery_code_count: config.mfa_recovery_codes.max(1),
+        })
+    }
+
+    pub fn is_required_for(&self, groups: &[AccessGroupEnum]) -> bool {
+        self.policy.is_required_for(groups)
+    }
+
+    pub async fn is_enabled(&self, user_id: &str) -> Result<bool, AppError> {
+        Ok(self.enabled(user_id).await?.is_some())
+    }
+
+    pub async fn status(&self, user_id: &str, groups: &[AccessGroupEnum]) -> Result<MfaStatus, AppError> {
+        let enabled = self.is_enabled(user_id).await?;
+        let recovery_codes_remaining = if enabled {
+            self.repository.count_unused_recovery_codes(user_id).await?
+        } else {
+            0
+        };
+
+        Ok(MfaStatus {
+            enabled,
+            required: self.is_required_for(groups),
+            recovery_codes_remaining,
+        })
+    }
+
+    // Gera um segredo novo, pendente até `confirm_enrollment`
+    pub async fn start_enrollment(&self, user_id: &str) -> Result<MfaEnrollment, AppError> {
+        if self.is_enabled(user_id).await? {
+            return Err(AppError::Conflict("A autenticação em dois fatores já está ativa".to_string()));
+        }
+
+        let user = UsersRepository::new(self.db.clone())
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Usuário {} não encontrado", user_id)))?;
+
+        let secret = Totp::generate_secret();
+        self.repository.save_pending(user_id, &secret).await?;
+
+        let otpauth_uri = totp::provisioning_uri(&secret, &self.issuer, &user.email);
+        let qr_code_svg = totp::qr_code_svg(&otpauth_uri)?;
+
+        Ok(MfaEnrollment {
+            secret,
+            otpauth_uri,
+            qr_code_svg,
+        })
+    }
+
+    // O primeiro código válido habilita o 2FA; devolve os códigos de
+    // recuperação, exibidos só desta vez
+    pub async fn confirm_enrollment(&self, user_id: &str, code: &str) -> Result<Vec<String>, AppError> {
+        let mfa = match self.repository.find(user_id).await? {
+            Some(mfa) if mfa.enabled_at.is_none() => mfa,
+            Some(_) => return Err(AppError::Conflict("A autenticação em dois fatores já está ativa".to_string())),
+            None => return Err(AppError::Validation("Nenhum cadastro de 2FA pendente".to_string())),
+        };
+
+        if !self.accept_totp(&mfa, code).await? {
+            return Err(AppError::Validation("Código de verificação inválido".to_string()));
+        }
+
+        let codes = generate_recovery_codes(self.recovery_code_count);
+        let hashes: Vec<String> = codes.iter().map(|code| recovery_code_hash(code)).collect();
+
+        if !self.repository.enable(user_id, &hashes).await? {
+            return Err(AppError::Conflict("A autenticação em dois fatores já está ativa".to_string()));
+        }
+
+        tracing::info!("2FA habilitado para o usuário {}", user_id);
+
+        Ok(codes)
+    }
+
+    // Novos códigos de recuperação; exige um código do aplicativo
+    pub async fn regenerate_recovery_codes(&self, user_id: &str, code: &str) -> Result<Vec<String>, AppError> {
+        let mfa = self.require_enabled(user_id).await?;
+
+        if !self.accept_totp(&mfa, code).await? {
+            return Err(AppError::Forbidden("Código de verificação inválido".to_string()));
+        }
+
+        let codes = generate_recovery_codes(self.recovery_code_count);
+        let hashes: Vec<String> = codes.iter().map(|code| recovery_code_hash(code)).collect();
+        self.repository.replace_recovery_codes(user_id, &hashes).await?;
+
+        Ok(codes)
+    }
+
+    // Desativa o 2FA com um código do aplicativo ou de recuperação. Contas
+    // obrigadas pela política não podem desativar.
+    pub async fn disable(&self, user_id: &str, groups: &[AccessGroupEnum], code: &str) -> Result<(), AppError> {
+        if self.is_required_for(groups) {
+            return Err(AppError::Forbidden(
+                "A autenticação em dois fatores é obrigatória para esta conta".to_string(),
+            ));
+        }
+
+        let mfa = self.require_enabled(user_id).await?;
+
+        if !self.accept_code(&mfa, code).await? {
+            return Err(AppError::Forbidden("Código de verificação inválido".to_string()));
+        }
+
+        self.repository.delete(user_id).await?;
+        tracing::info!("2FA desativado para o usuário {}", user_id);
+
+        Ok(())
+    }
+
+    // Primeiro passo do login: a senha já foi conferida
+    pub async fn issue_challenge(&self, user_id: &str, enrollment_required: bool) -> Result<MfaChallenge, AppError> {
+        let mfa_token = self.tokens.issue(user_id, MFA_CHALLENGE, self.challenge_ttl).await?;
+
+        Ok(MfaChallenge {
+            mfa_token,
+            expires_in: self.challenge_ttl.num_seconds(),
+            enrollment_required,
+        })
+    }
+
+    // Cadastro do 2FA pelo token de desafio, para contas obrigadas que ainda
+    // não têm o segundo fator
+    pub async fn start_enrollment_from_challenge(&self, mfa_token: &str) -> Result<MfaEnrollment, AppError> {
+        let user_id = self.challenge_owner(mfa_token).await?;
+        self.start_enrollment(&user_id).await
+    }
+
+    // Segundo passo do login. Com 2FA ativo, aceita código do aplicativo ou
+    // de recuperação; com cadastro pendente, o código confirma o cadastro.
+    // Cada erro conta como falha de login da conta e do IP. Os erros se
+    // acumulam entre desafios até um código certo: a partir de
+    // `max_attempts`, os desafios abertos são cancelados e a conta é
+    // bloqueada a cada novo erro, então refazer o login com a senha não
+    // libera mais tentativas.
+    pub async fn complete_challenge(&self, mfa_token: &str, code: &str, ip: Option<&str>) -> Result<MfaLogin, AppError> {
+        let user_id = self.challenge_owner(mfa_token).await?;
+
+        let email = UsersRepository::new(self.db.clone())
+            .find_by_id(&user_id)
+            .await?
+            .map(|user| user.email)
+            .ok_or_else(|| AppError::Auth("Desafio de 2FA inválido ou expirado".to_string()))?;
+
+        self.throttle.check(&email, ip).await?;
+
+        let mfa = self
+            .repository
+            .find(&user_id)
+            .await?
+            .ok_or_else(|| AppError::Validation("Cadastre a autenticação em dois fatores antes de continuar".to_string()))?;
+
+        let pending = mfa.enabled_at.is_none();
+        let accepted = if pending {
+            self.accept_totp(&mfa, code).await?
+        } else {
+            self.accept_code(&mfa, code).await?
+        };
+
+        if !accepted {
+            let failures = self.repository.register_failure(&user_id).await?;
+            self.throttle.record_failure(&email, ip).await;
+            tracing::warn!("Código de 2FA inválido para o usuário {} ({} tentativa(s))", user_id, failures);
+
+            if failures >= self.max_attempts {
+                self.tokens.revoke_all(&user_id, MFA_CHALLENGE).await?;
+                self.throttle.lock_account(&email).await;
+                return Err(AppError::Auth("Muitas tentativas; tente novamente mais tarde".to_string()));
+            }
+
+            return Err(AppError::Auth("Código de verificação inválido".to_string()));
+        }
+
+        // Só uma requisição consome o desafio
+        if self.tokens.consume(MFA_CHALLENGE, mfa_token).await?.is_none() {
+            return Err(AppError::Auth("Desafio de 2FA inválido ou expirado".to_string()));
+        }
+
+        let recovery_codes = if pending {
+            let codes = generate_recovery_codes(self.recovery_code_count);
+            let hashes: Vec<String> = codes.iter().map(|code| recovery_code_hash(code)).collect();
+            self.repository.enable(&user_id, &hashes).await?;
+            tracing::info!("2FA habilitado no login para o usuário {}", user_id);
+            Some(codes)
+        } else {
+            None
+        };
+
+        Ok(MfaLogin { user_id, recovery_codes })
+    }
+
+    async fn challenge_owner(&self, mfa_token: &str) -> Result<String, AppError> {
+        self.tokens
+            .peek(MFA_CHALLENGE, mfa_token)
+            .await?
+            .ok_or_else(|| AppError::Auth("Desafio de 2FA inválido ou expirado".to_string()))
+    }
+
+    async fn enabled(&self, user_id: &str) -> Result<Option<UserMfa>, AppError> {
+        Ok(self.repository.find(user_id).await?.filter(|mfa| mfa.enabled_at.is_some()))
+    }
+
+    async fn require_enabled(&self, user_id: &str) -> Result<UserMfa, AppError> {
+        self.enabled(user_id)
+            .await?
+            .ok_or_else(|| AppError::Validation("A autenticação em dois fatores não está ativa".to_string()))
+    }
+
+    // Código do aplicativo, ainda não usado
+    async fn accept_totp(&self, mfa: &UserMfa, code: &str) -> Result<bool, AppError> {
+        let step = match Totp::from_base32(&mfa.totp_secret)?.verify(code, Utc::now()) {
+            Some(step) => step,
+            None => return Ok(false),
+        };
+
+        Ok(self.repository.record_step(&mfa.user_id, step).await?)
+    }
+
+    // Código do aplicativo ou de recuperação
+    async fn accept_code(&self, mfa: &UserMfa, code: &str) -> Result<bool, AppError> {
+        if self.accept_totp(mfa, code).await? {
+            return Ok(true);
+        }
+
+        let consumed = self
+            .repository
+            .consume_recovery_code(&mfa.user_id, &recovery_code_hash(code))
+            .await?;
+
+        if consumed {
+            self.repository.reset_failures(&mfa.user_id).await?;
+            tracing::info!("Código de recuperação usado pelo usuário {}", mfa.user_id);
+        }
+
+        Ok(consumed)
+    }
+}
@@ -1,10 +1,18 @@
+pub mod access_group_service;
 pub mod auth_service;
+pub mod email_verification_service;
+pub mod mfa_service;
+pub mod password_service;
 pub mod user_service;
 pub mod find_users_service;
 pub mod find_user_by_id_service;
 pub mod create_user_service;
 
+pub use access_group_service::*;
 pub use auth_service::*;
+pub use email_verification_service::*;
+pub use mfa_service::*;
+pub use password_service::*;
 pub use user_service::*;
 pub use find_users_service::*;
 pub use find_user_by_id_service::*;
@@ -0,0 +1,182 @@
+use std::sync::Arc;
+use chrono::Duration;
+use sea_orm::DatabaseConnection;
+use api_rust::config::Config;
+use api_rust::errors::AppError;
+use api_rust::libs::shared::database::repositories::users::{User, UsersRepository};
+use api_rust::libs::shared::modules::auth::one_time_tokens::{OneTimeTokens, PASSWORD_RESET};
+use api_rust::libs::shared::modules::auth::password_policy::PasswordPolicy;
+use api_rust::libs::shared::modules::auth::refresh_tokens::RefreshTokenStore;
+use api_rust::libs::shared::modules::mailer::{self, EmailMessage, Mailer};
+use api_rust::libs::shared::modules::token_service::Claims;
+
+// Esqueci minha senha, redefinição pelo link do e-mail e troca de senha
+// pelo próprio usuário
+pub struct PasswordService {
+    db: DatabaseConnection,
+    tokens: OneTimeTokens,
+    sessions: &'static RefreshTokenStore,
+    mailer: Arc<dyn Mailer>,
+    reset_url: String,
+    reset_ttl: Duration,
+}
+
+impl PasswordService {
+    pub fn new(db: DatabaseConnection) -> Result<Self, AppError> {
+        let config = Config::from_env()?;
+
+        Ok(Self {
+            tokens: OneTimeTokens::new(db.clone()),
+            db,
+            sessions: RefreshTokenStore::shared(),
+            mailer: mailer::shared(),
+            reset_url: config.password_reset_url,
+            reset_ttl: Duration::minutes(config.password_reset_ttl_minutes as i64),
+        })
+    }
+
+    // A resposta é a mesma exista ou não a conta, para não revelar quais
+    // e-mails estão cadastrados
+    pub async fn forgot_password(&self, email: &str) -> Result<(), AppError> {
+        let user = match UsersRepository::new(self.db.clone()).find_by_email(email.trim()).await? {
+            Some(user) => user,
+            None => {
+                tracing::info!("Redefinição de senha pedida para e-mail não cadastrado");
+                return Ok(());
+            }
+        };
+
+        let token = self.tokens.issue(&user.id, PASSWORD_RESET, self.reset_ttl).await?;
+        let separator = if self.reset_url.contains('?') { '&' } else { '?' };
+        let link = format!("{}{}token={}", self.reset_url, separator, token);
+
+        let message = EmailMessage {
+            to: user.email.clone(),
+            subject: "Redefinição de senha".to_string(),
+            body: format!(
+                "Olá, {}.\n\n\
+                 Recebemos um pedido para redefinir a sua senha. Use o link abaixo \
+                 em até {} minutos:\n\n{}\n\n\
+                 Se você não fez esse pedido, ignore este e-mail: a sua senha continua a mesma.",
+                user.name,
+                self.reset_ttl.num_minutes(),
+                link
+            ),
+        };
+
+        // Falha no envio não muda a resposta; o usuário pode pedir de novo
+        if let Err(e) = self.mailer.send(&message).await {
+            tracing::warn!("Erro ao enviar e-mail de redefinição para o usuário {}: {}", user.id, e);
+        }
+
+        Ok(())
+    }
+
+    // Troca a senha com o token do e-mail e encerra todas as sessões: quem
+    // esqueceu a senha pode ter perdido o acesso para outra pessoa
+    pub async fn reset_password(&self, token: &str, new_password: &str) -> Result<(), AppError> {
+        let invalid_token = || AppError::Validation("Token de redefinição inválido ou expirado".to_string());
+        let users_repo = UsersRepository::new(self.db.clone());
+
+        // A política depende do e-mail e do nome da conta; o token só é
+        // consumido depois que a nova senha passa
+        let user_id = self.tokens.peek(PASSWORD_RESET, token).await?.ok_or_else(invalid_token)?;
+
+        let user = users_repo
+            .find_by_id(&user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Usuário {} não encontrado", user_id)))?;
+
+        PasswordPolicy::shared().validate("new_password", new_password, &user.email, &user.name)?;
+
+        if self.tokens.consume(PASSWORD_RESET, token).await?.is_none() {
+            return Err(invalid_token());
+        }
+
+        if !users_repo.update_password(&user_id, new_password).await? {
+            return Err(AppError::NotFound(format!("Usuário {} não encontrado", user_id)));
+        }
+
+        // A senha já mudou: se o Redis falhar, as sessões expiram sozinhas
+        if let Err(e) = self.sessions.revoke_all(&user_id).await {
+            tracing::warn!("Senha redefinida, mas as sessões do usuário {} não foram encerradas: {}", user_id, e);
+        }
+
+        self.notify_password_changed(&user).await;
+
+        Ok(())
+    }
+
+    // Exige a senha atual. A sessão usada na troca continua ativa; as demais
+    // são encerradas. Devolve quantas sessões foram encerradas.
+    pub async fn change_password(&self, claims: &Claims, current_password: &str, new_password: &str) -> Result<usize, AppError> {
+        let users_repo = UsersRepository::new(self.db.clone());
+
+        let user = users_repo
+            .find_by_id(&claims.sub)
+            .await?
+            .ok_or_else(|| AppError::Auth("Usuário não encontrado".to_string()))?;
+
+        if !UsersRepository::verify_password(&user, current_password).await? {
+            return Err(AppError::Forbidden("Senha atual incorreta".to_string()));
+        }
+
+        if current_password == new_password {
+            return Err(AppError::invalid_field(
+                "new_password",
+                "password_unchanged",
+                "A nova senha deve ser diferente da atual",
+            ));
+        }
+
+        PasswordPolicy::shared().validate("new_password", new_password, &user.email, &user.name)?;
+
+        users_repo.update_password(&user.id, new_password).await?;
+
+        // Links de redefinição pedidos antes da troca deixam de valer
+        self.tokens.revoke_all(&user.id, PASSWORD_RESET).await?;
+
+        let revoked_sessions = match self.revoke_other_sessions(&user.id, claims.sid.as_deref()).await {
+            Ok(revoked) => revoked,
+            Err(e) => {
+                tracing::warn!("Senha trocada, mas as outras sessões do usuário {} não foram encerradas: {}", user.id, e);
+                0
+            }
+        };
+
+        self.notify_password_changed(&user).await;
+
+        Ok(revoked_sessions)
+    }
+
+    async fn revoke_other_sessions(&self, user_id: &str, current_session_id: Option<&str>) -> Result<usize, AppError> {
+        let mut revoked = 0;
+
+        for session in self.sessions.list_sessions(user_id).await? {
+            if Some(session.id.as_str()) != current_session_id
+                && self.sessions.revoke_session(user_id, &session.id).await?
+            {
+                revoked += 1;
+            }
+        }
+
+        Ok(revoked)
+    }
+
+    async fn notify_password_changed(&self, user: &User) {
+        let message = EmailMessage {
+            to: user.email.clone(),
+            subject: "Sua senha foi alterada".to_string(),
+            body: format!(
+                "Olá, {}.\n\n\
+                 A senha da sua conta acabou de ser alterada. Se não foi você, \
+                 redefina a senha agora pelo \"Esqueci minha senha\".",
+                user.name
+            ),
+        };
+
+        if let Err(e) = self.mailer.send(&message).await {
+            tracing::warn!("Erro ao enviar aviso de troca de senha para o usuário {}: {}", user.id, e);
+        }
+    }
+}
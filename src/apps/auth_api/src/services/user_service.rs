@@ -3,9 +3,11 @@ use crate::models::user::User;
 use crate::dto::{UserRoleDto, CreateUserRequestDto};
 use chrono::Utc;
 use uuid::Uuid;
+use api_rust::errors::AppError;
 
 // Importar o repositório de usuários
 use api_rust::libs::shared::database::repositories::users::{UsersRepository, CreateUserRequest, UpdateUserRequest};
+use api_rust::libs::shared::modules::auth::password_policy::PasswordPolicy;
 
 pub struct UserService {
     db: DatabaseConnection,
@@ -16,7 +18,7 @@ impl UserService {
         Ok(Self { db })
     }
 
-    pub async fn list_users(&self, limit: Option<u64>, offset: Option<u64>) -> Result<Vec<User>, String> {
+    pub async fn list_users(&self, limit: Option<u64>, offset: Option<u64>) -> Result<Vec<User>, AppError> {
         let users_repo = UsersRepository::new(self.db.clone());
         
         match users_repo.find_all(limit, offset).await {
@@ -34,20 +36,20 @@ impl UserService {
                 
                 Ok(api_users)
             }
-            Err(e) => Err(format!("Erro ao buscar usuários: {}", e)),
+            Err(e) => Err(e.into()),
         }
     }
 
-    pub async fn count_users(&self) -> Result<u64, String> {
+    pub async fn count_users(&self) -> Result<u64, AppError> {
         let users_repo = UsersRepository::new(self.db.clone());
         
         match users_repo.count().await {
             Ok(count) => Ok(count),
-            Err(e) => Err(format!("Erro ao contar usuários: {}", e)),
+            Err(e) => Err(e.into()),
         }
     }
 
-    pub async fn get_users_by_role(&self, role: &str) -> Result<Vec<User>, String> {
+    pub async fn get_users_by_role(&self, role: &str) -> Result<Vec<User>, AppError> {
         let users_repo = UsersRepository::new(self.db.clone());
         
         match users_repo.find_by_role(role).await {
@@ -65,11 +67,11 @@ impl UserService {
                 
                 Ok(api_users)
             }
-            Err(e) => Err(format!("Erro ao buscar usuários por role: {}", e)),
+            Err(e) => Err(e.into()),
         }
     }
 
-    pub async fn get_user_by_id(&self, user_id: &str) -> Result<Option<User>, String> {
+    pub async fn get_user_by_id(&self, user_id: &str) -> Result<Option<User>, AppError> {
         let users_repo = UsersRepository::new(self.db.clone());
         
         match users_repo.find_by_id(user_id).await {
@@ -88,11 +90,11 @@ impl UserService {
                 Ok(Some(api_user))
             }
             Ok(None) => Ok(None),
-            Err(e) => Err(format!("Erro ao buscar usuário: {}", e)),
+            Err(e) => Err(e.into()),
         }
     }
 
-    pub async fn get_user_by_email(&self, email: &str) -> Result<Option<User>, String> {
+    pub async fn get_user_by_email(&self, email: &str) -> Result<Option<User>, AppError> {
         let users_repo = UsersRepository::new(self.db.clone());
         
         match users_repo.find_by_email(email).await {
@@ -111,12 +113,18 @@ impl UserService {
                 Ok(Some(api_user))
             }
             Ok(None) => Ok(None),
-            Err(e) => Err(format!("Erro ao buscar usuário por email: {}", e)),
+            Err(e) => Err(e.into()),
         }
     }
 
-    pub async fn create_user(&self, request: CreateUserRequestDto) -> Result<User, String> {
+    pub async fn create_user(&self, request: CreateUserRequestDto) -> Result<User, AppError> {
+        PasswordPolicy::shared().validate("password", &request.password, &request.email, &request.name)?;
+
         let users_repo = UsersRepository::new(self.db.clone());
+
+        if users_repo.find_by_email(&request.email).await?.is_some() {
+            return Err(AppError::Conflict("Email já cadastrado".to_string()));
+        }
         
         // Converter DTO da API para DTO do repositório
         let repo_request = CreateUserRequest {
@@ -124,6 +132,7 @@ impl UserService {
             password: request.password,
             name: request.name,
             role: request.role,
+            email_verified: true,
         };
         
         match users_repo.create(repo_request).await {
@@ -141,11 +150,11 @@ impl UserService {
                 
                 Ok(api_user)
             }
-            Err(e) => Err(format!("Erro ao criar usuário: {}", e)),
+            Err(e) => Err(e.into()),
         }
     }
 
-    pub async fn update_user(&self, user_id: &str, name: Option<String>, email: Option<String>) -> Result<Option<User>, String> {
+    pub async fn update_user(&self, user_id: &str, name: Option<String>, email: Option<String>) -> Result<Option<User>, AppError> {
         let users_repo = UsersRepository::new(self.db.clone());
         
         // Converter para DTO do repositório
@@ -171,16 +180,16 @@ impl UserService {
                 Ok(Some(api_user))
             }
             Ok(None) => Ok(None),
-            Err(e) => Err(format!("Erro ao atualizar usuário: {}", e)),
+            Err(e) => Err(e.into()),
         }
     }
 
-    pub async fn delete_user(&self, user_id: &str) -> Result<bool, String> {
+    pub async fn delete_user(&self, user_id: &str) -> Result<bool, AppError> {
         let users_repo = UsersRepository::new(self.db.clone());
         
         match users_repo.delete(user_id).await {
             Ok(success) => Ok(success),
-            Err(e) => Err(format!("Erro ao deletar usuário: {}", e)),
+            Err(e) => Err(e.into()),
         }
     }
 } 
\ No newline at end of file
@@ -1,13 +1,17 @@
 use axum::{
     extract::{Path, State},
-    http::StatusCode,
     response::Json as JsonResponse,
 };
 use sea_orm::DatabaseConnection;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 use crate::services::catalog_service::CatalogService;
+use api_rust::errors::AppError;
+use api_rust::libs::shared::modules::openapi::{InvalidRequest, NotFound};
+use api_rust::libs::shared::modules::validation::{not_blank, ValidatedQuery};
+use validator::Validate;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct VideoCatalogResponse {
     pub id: String,
     pub title: String,
@@ -17,57 +21,155 @@ pub struct VideoCatalogResponse {
     pub thumbnail_url: String,
 }
 
-pub struct CatalogController;
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FeaturedQuery {
+    #[validate(range(min = 1, max = 100))]
+    pub limit: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SearchQuery {
+    #[validate(custom(function = "not_blank"), length(max = 100))]
+    pub q: String,
+    #[validate(range(min = 1, max = 100))]
+    pub limit: Option<u64>,
+}
 
-impl CatalogController {
-    pub async fn get_videos(
-        State(db): State<DatabaseConnection>,
-    ) -> Result<JsonResponse<Vec<VideoCatalogResponse>>, StatusCode> {
-        let catalog_service = CatalogService::new(db);
-        
-        match catalog_service.get_videos_with_cache().await {
-            Ok(videos) => {
-                let response: Vec<VideoCatalogResponse> = videos
-                    .into_iter()
-                    .map(|video| VideoCatalogResponse {
-                        id: video.id,
-                        title: video.title,
-                        description: video.description,
-                        duration: video.duration,
-                        genre: video.genre,
-                        thumbnail_url: video.thumbnail_url,
-                    })
-                    .collect();
-                Ok(JsonResponse(response))
-            }
-            Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+impl From<crate::models::video::Video> for VideoCatalogResponse {
+    fn from(video: crate::models::video::Video) -> Self {
+        Self {
+            id: video.id,
+            title: video.title,
+            description: video.description,
+            duration: video.duration,
+            genre: video.genre,
+            thumbnail_url: video.thumbnail_url,
         }
     }
+}
 
-    pub async fn get_video_by_id(
-        State(db): State<DatabaseConnection>,
-        Path(video_id): Path<String>,
-    ) -> Result<JsonResponse<VideoCatalogResponse>, StatusCode> {
-        let catalog_service = CatalogService::new(db);
-        
-        match catalog_service.get_video_by_id(&video_id).await {
-            Ok(Some(video)) => {
-                let response = VideoCatalogResponse {
+#[utoipa::path(
+    get,
+    path = "/videos",
+    tag = "Catálogo",
+    summary = "Listar o catálogo",
+    responses((status = 200, body = Vec<VideoCatalogResponse>)),
+)]
+pub async fn get_videos(
+    State(db): State<DatabaseConnection>,
+) -> Result<JsonResponse<Vec<VideoCatalogResponse>>, AppError> {
+    let catalog_service = CatalogService::new(db).await;
+    
+    match catalog_service.get_videos_with_cache().await {
+        Ok(videos) => {
+            let response: Vec<VideoCatalogResponse> = videos
+                .into_iter()
+                .map(|video| VideoCatalogResponse {
                     id: video.id,
                     title: video.title,
                     description: video.description,
                     duration: video.duration,
                     genre: video.genre,
                     thumbnail_url: video.thumbnail_url,
-                };
-                Ok(JsonResponse(response))
-            }
-            Ok(None) => Err(StatusCode::NOT_FOUND),
-            Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+                })
+                .collect();
+            Ok(JsonResponse(response))
         }
+        Err(e) => Err(e),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/videos/featured",
+    tag = "Catálogo",
+    summary = "Vídeos em destaque",
+    params(FeaturedQuery),
+    responses(
+        (status = 200, body = Vec<VideoCatalogResponse>),
+        (status = 422, response = InvalidRequest),
+    ),
+)]
+pub async fn get_featured_videos(
+    State(db): State<DatabaseConnection>,
+    ValidatedQuery(query): ValidatedQuery<FeaturedQuery>,
+) -> Result<JsonResponse<Vec<VideoCatalogResponse>>, AppError> {
+    let catalog_service = CatalogService::new(db).await;
+
+    match catalog_service.get_featured_videos(query.limit).await {
+        Ok(videos) => Ok(JsonResponse(videos.into_iter().map(Into::into).collect())),
+        Err(e) => Err(e),
     }
+}
+
+#[utoipa::path(
+    get,
+    path = "/videos/search",
+    tag = "Catálogo",
+    summary = "Buscar no catálogo",
+    params(SearchQuery),
+    responses(
+        (status = 200, body = Vec<VideoCatalogResponse>),
+        (status = 422, response = InvalidRequest),
+    ),
+)]
+pub async fn search_videos(
+    State(db): State<DatabaseConnection>,
+    ValidatedQuery(query): ValidatedQuery<SearchQuery>,
+) -> Result<JsonResponse<Vec<VideoCatalogResponse>>, AppError> {
+    let catalog_service = CatalogService::new(db).await;
+
+    match catalog_service.search_videos(&query.q, query.limit).await {
+        Ok(videos) => Ok(JsonResponse(videos.into_iter().map(Into::into).collect())),
+        Err(e) => Err(e),
+    }
+}
 
-    pub async fn health() -> JsonResponse<&'static str> {
-        JsonResponse("Viewer API - Healthy")
+#[utoipa::path(
+    get,
+    path = "/videos/{video_id}",
+    operation_id = "catalog_get_video_by_id",
+    tag = "Catálogo",
+    summary = "Buscar vídeo do catálogo pelo id",
+    params(("video_id" = String, Path, description = "Id do vídeo")),
+    responses(
+        (status = 200, body = VideoCatalogResponse),
+        (status = 404, response = NotFound),
+    ),
+)]
+pub async fn get_video_by_id(
+    State(db): State<DatabaseConnection>,
+    Path(video_id): Path<String>,
+) -> Result<JsonResponse<VideoCatalogResponse>, AppError> {
+    let catalog_service = CatalogService::new(db).await;
+    
+    match catalog_service.get_video_by_id(&video_id).await {
+        Ok(Some(video)) => {
+            let response = VideoCatalogResponse {
+                id: video.id,
+                title: video.title,
+                description: video.description,
+                duration: video.duration,
+                genre: video.genre,
+                thumbnail_url: video.thumbnail_url,
+            };
+            Ok(JsonResponse(response))
+        }
+        Ok(None) => Err(AppError::NotFound(format!("Vídeo {} não encontrado", video_id))),
+        Err(e) => Err(e),
     }
 }
+
+#[utoipa::path(
+    get,
+    path = "/health",
+    operation_id = "viewer_health",
+    tag = "Health",
+    summary = "Saúde da Viewer API",
+    responses((status = 200, body = String)),
+)]
+pub async fn health() -> JsonResponse<&'static str> {
+    JsonResponse("Viewer API - Healthy")
+}
@@ -1,6 +1,9 @@
-use axum::Router;
+use axum::{Extension, Router};
 use std::net::SocketAddr;
 use tracing_subscriber;
+use sea_orm::DatabaseConnection;
+use api_rust::libs::shared::database::connection;
+use api_rust::libs::shared::modules::rate_limit::RateLimitLayer;
 use tokio::net::TcpListener;
 
 #[tokio::main]
@@ -8,10 +11,25 @@ async fn main() {
     // Configurar logging automático
     tracing_subscriber::fmt::init();
 
-    // Criar router
+    // Carregar configuração do .env
+    dotenvy::dotenv().ok();
+
+    // Configurar conexão com banco de dados
+    let database_url = std::env::var("DATABASE_URL")
+        .unwrap_or_else(|_| "sqlite://rust_cast.db?mode=rwc".to_string());
+
+    let db: DatabaseConnection = connection::connect(&database_url).await
+        .expect("Falha ao conectar com banco de dados");
+
+    // Criar router com state; a conexão também vai nas extensions para o
+    // AuthGuard resolver permissões
     let app = Router::new()
-        .nest("/viewer", viewer_api::routes::create_router())
-        .route("/", axum::routing::get(|| async { "Viewer API - Running" }));
+        .nest("/viewer", Router::from(viewer_api::routes::create_router()))
+        .route("/", axum::routing::get(|| async { "Viewer API - Running" }))
+        .with_state(db.clone())
+        .layer(Extension(db))
+        // Políticas por rota do [rate_limit] do config/project.toml
+        .layer(RateLimitLayer::shared());
 
     // Configurar endereço
     let addr = SocketAddr::from(([127, 0, 0, 1], 3003));
@@ -20,13 +38,15 @@ async fn main() {
     tracing::info!("🚀 Viewer API iniciando em http://{}", addr);
     tracing::info!("👁️  Endpoints disponíveis:");
     tracing::info!("   - GET  /viewer/videos (com cache)");
+    tracing::info!("   - GET  /viewer/videos/featured (com cache)");
+    tracing::info!("   - GET  /viewer/videos/search?q= (com cache)");
     tracing::info!("   - GET  /viewer/health");
     tracing::info!("   - GET  /");
 
     // ✅ Nova forma no Axum 0.8
     let listener = TcpListener::bind(addr).await.unwrap();
     tracing::info!("✅ Viewer API pronta e rodando!");
-    axum::serve(listener, app)
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
         .await
         .unwrap();
 }
@@ -1,10 +1,23 @@
-use axum::{routing::get, Router};
+use axum::{middleware, routing::get};
 use sea_orm::DatabaseConnection;
+use utoipa_axum::{router::OpenApiRouter, routes};
+use api_rust::libs::shared::logging::correlation::correlation_id;
+use api_rust::libs::shared::modules::auth::access_control::public;
+use api_rust::libs::shared::modules::openapi::GuardedRoutes;
+use crate::controllers::catalog_controller;
 
-pub fn create_router() -> Router<DatabaseConnection> {
-    Router::new()
-        .route("/videos", get(crate::controllers::catalog_controller::get_videos))
-        .route("/videos/:video_id", get(crate::controllers::catalog_controller::get_video_by_id))
-        .route("/health", get(crate::controllers::catalog_controller::health))
-        .route("/", get(|| async { "Viewer API - Running" }))
+// O caminho e o método de cada rota vêm do `#[utoipa::path]` do handler; o
+// mesmo router gera o documento OpenAPI
+pub fn create_router() -> OpenApiRouter<DatabaseConnection> {
+    OpenApiRouter::new()
+        // Catálogo aberto a visitantes; com token, as claims seguem anexadas
+        .routes(routes!(catalog_controller::get_videos).with_guard(public()))
+        .routes(routes!(catalog_controller::get_featured_videos).with_guard(public()))
+        .routes(routes!(catalog_controller::search_videos).with_guard(public()))
+        .routes(routes!(catalog_controller::get_video_by_id).with_guard(public()))
+        .routes(routes!(catalog_controller::health).with_guard(public()))
+        .route("/", get(|| async { "Viewer API - Running" }).route_layer(public()))
+
+        // Id de correlação usado nos erros problem+json
+        .layer(middleware::from_fn(correlation_id))
 }
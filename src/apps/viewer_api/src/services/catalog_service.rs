@@ -1,79 +1,113 @@
 use sea_orm::DatabaseConnection;
 use crate::models::video::Video;
 use std::time::Duration;
+use api_rust::errors::AppError;
 
 // Importar o repositório de vídeos
 use api_rust::libs::shared::database::repositories::videos::{VideosRepository, Video as RepoVideo};
+use api_rust::libs::shared::cache::catalog_cache::CatalogCache;
 
 pub struct CatalogService {
     db: DatabaseConnection,
-    cache_ttl: Duration,
+    cache: CatalogCache,
 }
 
 impl CatalogService {
-    pub fn new(db: DatabaseConnection) -> Self {
-        Self {
-            db,
-            cache_ttl: Duration::from_secs(300), // 5 minutos
+    pub async fn new(db: DatabaseConnection) -> Self {
+        // Sem Redis o cache segue funcionando só em memória
+        let cache = CatalogCache::shared(Duration::from_secs(300)); // 5 minutos
+
+        Self { db, cache }
+    }
+
+    // Converter do repositório para o modelo da API
+    fn to_api_video(repo_video: RepoVideo) -> Video {
+        Video {
+            id: repo_video.id,
+            title: repo_video.title,
+            description: repo_video.description,
+            duration: repo_video.duration_seconds as u32,
+            genre: "Geral".to_string(), // TODO: Adicionar campo genre no repositório
+            thumbnail_url: repo_video.thumbnail_url.unwrap_or_else(|| "".to_string()),
         }
     }
 
-    pub async fn get_videos(&self) -> Result<Vec<Video>, String> {
+    pub async fn get_videos(&self) -> Result<Vec<Video>, AppError> {
         let videos_repo = VideosRepository::new(self.db.clone());
-        
-        match videos_repo.find_all(None, None).await {
-            Ok(repo_videos) => {
-                // Converter do repositório para o modelo da API
-                let api_videos: Vec<Video> = repo_videos.into_iter().map(|repo_video| Video {
-                    id: repo_video.id,
-                    title: repo_video.title,
-                    description: repo_video.description,
-                    duration: repo_video.duration_seconds as u32,
-                    genre: "Geral".to_string(), // TODO: Adicionar campo genre no repositório
-                    thumbnail_url: repo_video.thumbnail_url.unwrap_or_else(|| "".to_string()),
-                }).collect();
-                
-                Ok(api_videos)
-            }
-            Err(e) => Err(format!("Erro ao buscar vídeos: {}", e)),
-        }
+
+        let repo_videos = videos_repo.find_all(None, None).await
+            .map_err(AppError::from)?;
+
+        Ok(repo_videos.into_iter().map(Self::to_api_video).collect())
     }
 
-    pub async fn get_videos_with_cache(&self) -> Result<Vec<Video>, String> {
-        let cache_key = "videos:catalog";
-        
-        // TODO: Implementar cache real com Redis
-        // Por enquanto, sempre busca do banco
-        println!("🔍 Verificando cache para chave: {}", cache_key);
-        println!("💾 Cache MISS - buscando do banco de dados");
-        
-        let videos = self.get_videos().await?;
-        
-        // TODO: Salvar no cache Redis
-        println!("💾 Salvando resultado no cache");
-        
-        Ok(videos)
+    // As leituras abaixo passam pelo cache em duas camadas: MISSes concorrentes
+    // da mesma chave resultam em uma única consulta ao banco
+    pub async fn get_videos_with_cache(&self) -> Result<Vec<Video>, AppError> {
+        let db = self.db.clone();
+
+        let repo_videos = self.cache
+            .videos(None, None, move || {
+                let videos_repo = VideosRepository::new(db.clone());
+                async move {
+                    videos_repo.find_all(None, None).await
+                        .map_err(AppError::from)
+                }
+            })
+            .await?;
+
+        Ok(repo_videos.into_iter().map(Self::to_api_video).collect())
     }
 
-    pub async fn get_video_by_id(&self, video_id: &str) -> Result<Option<Video>, String> {
-        let videos_repo = VideosRepository::new(self.db.clone());
-        
-        match videos_repo.find_by_id(video_id).await {
-            Ok(Some(repo_video)) => {
-                // Converter do repositório para o modelo da API
-                let api_video = Video {
-                    id: repo_video.id,
-                    title: repo_video.title,
-                    description: repo_video.description,
-                    duration: repo_video.duration_seconds as u32,
-                    genre: "Geral".to_string(), // TODO: Adicionar campo genre no repositório
-                    thumbnail_url: repo_video.thumbnail_url.unwrap_or_else(|| "".to_string()),
-                };
-                
-                Ok(Some(api_video))
-            }
-            Ok(None) => Ok(None),
-            Err(e) => Err(format!("Erro ao buscar vídeo: {}", e)),
-        }
+    pub async fn get_featured_videos(&self, limit: Option<u64>) -> Result<Vec<Video>, AppError> {
+        let db = self.db.clone();
+
+        let repo_videos = self.cache
+            .featured(limit, move || {
+                let videos_repo = VideosRepository::new(db.clone());
+                async move {
+                    videos_repo.find_featured(limit).await
+                        .map_err(AppError::from)
+                }
+            })
+            .await?;
+
+        Ok(repo_videos.into_iter().map(Self::to_api_video).collect())
+    }
+
+    pub async fn search_videos(&self, title: &str, limit: Option<u64>) -> Result<Vec<Video>, AppError> {
+        let db = self.db.clone();
+        let term = title.to_string();
+
+        let repo_videos = self.cache
+            .search(title, limit, move || {
+                let videos_repo = VideosRepository::new(db.clone());
+                let term = term.clone();
+                async move {
+                    videos_repo.find_by_title(&term, limit).await
+                        .map_err(AppError::from)
+                }
+            })
+            .await?;
+
+        Ok(repo_videos.into_iter().map(Self::to_api_video).collect())
+    }
+
+    pub async fn get_video_by_id(&self, video_id: &str) -> Result<Option<Video>, AppError> {
+        let db = self.db.clone();
+        let id = video_id.to_string();
+
+        let repo_video = self.cache
+            .video(video_id, move || {
+                let videos_repo = VideosRepository::new(db.clone());
+                let id = id.clone();
+                async move {
+                    videos_repo.find_by_id(&id).await
+                        .map_err(AppError::from)
+                }
+            })
+            .await?;
+
+        Ok(repo_video.map(Self::to_api_video))
     }
 }
@@ -0,0 +1,57 @@
+// Runner de migrações: cargo run --bin migrate -- [status|up]
+
+use api_rust::{
+    config::Config,
+    libs::shared::database::connection::create_connection,
+    libs::shared::database::migrations::{MigrationState, Migrator},
+};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    tracing_subscriber::fmt::init();
+
+    let command = std::env::args().nth(1).unwrap_or_else(|| "status".to_string());
+    let config = Config::from_env()?;
+    let db = create_connection(&config).await?;
+    let migrator = Migrator::new(db, &config.migrations_dir);
+
+    match command.as_str() {
+        "status" => {
+            println!("📊 Status das Migrações ({})", config.migrations_dir);
+            println!("=======================");
+
+            for status in migrator.status().await? {
+                let label = match status.state {
+                    MigrationState::Applied => "✅ aplicada",
+                    MigrationState::Pending => "⏳ pendente",
+                    MigrationState::Modified => "❌ alterada após aplicar",
+                    MigrationState::MissingOnDisk => "❌ ausente em disco",
+                };
+                let applied_at = status
+                    .applied_at
+                    .map(|at| format!(" em {}", at.format("%Y-%m-%d %H:%M:%S")))
+                    .unwrap_or_default();
+
+                println!("  {} {} - {}{}", status.version, status.name, label, applied_at);
+            }
+        }
+        "up" => {
+            let applied = migrator.up().await?;
+
+            if applied.is_empty() {
+                println!("✅ Nenhuma migração pendente");
+            } else {
+                println!("✅ {} migração(ões) aplicada(s): {}", applied.len(), applied.join(", "));
+            }
+        }
+        _ => {
+            println!("Uso: migrate [status|up]");
+            println!("");
+            println!("  status   - Mostrar migrações aplicadas, pendentes e alteradas");
+            println!("  up       - Aplicar as migrações pendentes em ordem");
+            std::process::exit(1);
+        }
+    }
+
+    Ok(())
+}
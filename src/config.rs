@@ -1,11 +1,24 @@
 use std::net::SocketAddr;
+use std::sync::OnceLock;
 use serde::Deserialize;
-use crate::errors::Result;
+use crate::errors::{AppError, Result};
+use crate::libs::shared::modules::auth::password_policy::PasswordPolicySettings;
+use crate::libs::shared::modules::rate_limit::RateLimitSettings;
+
+static SHARED_PROJECT_CONFIG: OnceLock<ProjectConfig> = OnceLock::new();
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct Config {
     pub database_url: String,
+    pub run_migrations: bool,
+    pub migrations_dir: String,
     pub redis_url: String,
+    pub redis_connect_timeout_ms: u64,
+    pub redis_response_timeout_ms: u64,
+    pub redis_reconnect_retries: usize,
+    pub cache_memory_capacity: usize,
+    pub cache_memory_ttl_secs: u64,
+    pub cache_early_refresh_beta: f64,
     pub api_port: u16,
     pub log_level: String,
     pub auth_api_port: u16,
@@ -15,6 +28,36 @@ pub struct Config {
     pub jwt_refresh_secret: String,
     pub jwt_access_expiry_hours: u64,
     pub jwt_refresh_expiry_days: u64,
+    pub jwt_algorithm: String,
+    pub jwt_key_id: String,
+    pub jwt_private_key_path: String,
+    pub jwt_public_key_path: String,
+    pub jwt_previous_public_keys: String,
+    pub mail_from: String,
+    pub mail_outbox_dir: String,
+    pub password_reset_url: String,
+    pub password_reset_ttl_minutes: u64,
+    pub unverified_login_policy: String,
+    pub email_verification_url: String,
+    pub email_verification_ttl_hours: u64,
+    pub email_verification_resend_limit: usize,
+    pub email_verification_resend_window_minutes: u64,
+    pub mfa_issuer: String,
+    pub mfa_required_for_admins: bool,
+    pub mfa_challenge_ttl_seconds: u64,
+    pub mfa_max_attempts: i32,
+    pub mfa_recovery_codes: usize,
+    pub login_max_failures_per_account: i64,
+    pub login_max_failures_per_ip: i64,
+    pub login_failure_window_minutes: u64,
+    pub login_lockout_minutes: u64,
+    pub login_backoff_base_seconds: u64,
+    pub login_backoff_max_seconds: u64,
+    pub trusted_proxies: String,
+    pub rate_limit_api_keys: String,
+    pub password_argon2_memory_kib: u32,
+    pub password_argon2_iterations: u32,
+    pub password_argon2_parallelism: u32,
 }
 
 impl Config {
@@ -23,9 +66,39 @@ impl Config {
 
         Ok(Config {
             database_url: std::env::var("DATABASE_URL")
-                .unwrap_or_else(|_| "sqlserver://localhost:1433/rust_cast_db".to_string()),
+                .unwrap_or_else(|_| "sqlite://rust_cast.db?mode=rwc".to_string()),
+            run_migrations: std::env::var("RUN_MIGRATIONS")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .unwrap_or(false),
+            migrations_dir: std::env::var("MIGRATIONS_DIR")
+                .unwrap_or_else(|_| "config/migrations".to_string()),
             redis_url: std::env::var("REDIS_URL")
                 .unwrap_or_else(|_| "redis://localhost:6379".to_string()),
+            redis_connect_timeout_ms: std::env::var("REDIS_CONNECT_TIMEOUT_MS")
+                .unwrap_or_else(|_| "2000".to_string())
+                .parse()
+                .unwrap_or(2000),
+            redis_response_timeout_ms: std::env::var("REDIS_RESPONSE_TIMEOUT_MS")
+                .unwrap_or_else(|_| "500".to_string())
+                .parse()
+                .unwrap_or(500),
+            redis_reconnect_retries: std::env::var("REDIS_RECONNECT_RETRIES")
+                .unwrap_or_else(|_| "6".to_string())
+                .parse()
+                .unwrap_or(6),
+            cache_memory_capacity: std::env::var("CACHE_MEMORY_CAPACITY")
+                .unwrap_or_else(|_| "1000".to_string())
+                .parse()
+                .unwrap_or(1000),
+            cache_memory_ttl_secs: std::env::var("CACHE_MEMORY_TTL_SECS")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()
+                .unwrap_or(30),
+            cache_early_refresh_beta: std::env::var("CACHE_EARLY_REFRESH_BETA")
+                .unwrap_or_else(|_| "1.0".to_string())
+                .parse()
+                .unwrap_or(1.0),
             api_port: std::env::var("API_PORT")
                 .unwrap_or_else(|_| "8000".to_string())
                 .parse()
@@ -55,6 +128,94 @@ impl Config {
                 .unwrap_or_else(|_| "7".to_string())
                 .parse()
                 .unwrap_or(7),
+            jwt_algorithm: std::env::var("JWT_ALGORITHM")
+                .unwrap_or_else(|_| "EdDSA".to_string()),
+            jwt_key_id: std::env::var("JWT_KEY_ID").unwrap_or_default(),
+            jwt_private_key_path: std::env::var("JWT_PRIVATE_KEY_PATH").unwrap_or_default(),
+            jwt_public_key_path: std::env::var("JWT_PUBLIC_KEY_PATH").unwrap_or_default(),
+            jwt_previous_public_keys: std::env::var("JWT_PREVIOUS_PUBLIC_KEYS").unwrap_or_default(),
+            mail_from: std::env::var("MAIL_FROM")
+                .unwrap_or_else(|_| "RustCast <no-reply@rustcast.local>".to_string()),
+            mail_outbox_dir: std::env::var("MAIL_OUTBOX_DIR")
+                .unwrap_or_else(|_| "var/outbox".to_string()),
+            password_reset_url: std::env::var("PASSWORD_RESET_URL")
+                .unwrap_or_else(|_| "http://localhost:3000/reset-password".to_string()),
+            password_reset_ttl_minutes: std::env::var("PASSWORD_RESET_TTL_MINUTES")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()
+                .unwrap_or(30),
+            unverified_login_policy: std::env::var("UNVERIFIED_LOGIN_POLICY")
+                .unwrap_or_else(|_| "limited".to_string()),
+            email_verification_url: std::env::var("EMAIL_VERIFICATION_URL")
+                .unwrap_or_else(|_| "http://localhost:3001/auth/verify-email".to_string()),
+            email_verification_ttl_hours: std::env::var("EMAIL_VERIFICATION_TTL_HOURS")
+                .unwrap_or_else(|_| "24".to_string())
+                .parse()
+                .unwrap_or(24),
+            email_verification_resend_limit: std::env::var("EMAIL_VERIFICATION_RESEND_LIMIT")
+                .unwrap_or_else(|_| "3".to_string())
+                .parse()
+                .unwrap_or(3),
+            email_verification_resend_window_minutes: std::env::var("EMAIL_VERIFICATION_RESEND_WINDOW_MINUTES")
+                .unwrap_or_else(|_| "60".to_string())
+                .parse()
+                .unwrap_or(60),
+            mfa_issuer: std::env::var("MFA_ISSUER")
+                .unwrap_or_else(|_| "RustCast".to_string()),
+            mfa_required_for_admins: std::env::var("MFA_REQUIRED_FOR_ADMINS")
+                .unwrap_or_else(|_| "true".to_string())
+                .parse()
+                .unwrap_or(true),
+            mfa_challenge_ttl_seconds: std::env::var("MFA_CHALLENGE_TTL_SECONDS")
+                .unwrap_or_else(|_| "300".to_string())
+                .parse()
+                .unwrap_or(300),
+            mfa_max_attempts: std::env::var("MFA_MAX_ATTEMPTS")
+                .unwrap_or_else(|_| "5".to_string())
+                .parse()
+                .unwrap_or(5),
+            mfa_recovery_codes: std::env::var("MFA_RECOVERY_CODES")
+                .unwrap_or_else(|_| "10".to_string())
+                .parse()
+                .unwrap_or(10),
+            login_max_failures_per_account: std::env::var("LOGIN_MAX_FAILURES_PER_ACCOUNT")
+                .unwrap_or_else(|_| "5".to_string())
+                .parse()
+                .unwrap_or(5),
+            login_max_failures_per_ip: std::env::var("LOGIN_MAX_FAILURES_PER_IP")
+                .unwrap_or_else(|_| "20".to_string())
+                .parse()
+                .unwrap_or(20),
+            login_failure_window_minutes: std::env::var("LOGIN_FAILURE_WINDOW_MINUTES")
+                .unwrap_or_else(|_| "15".to_string())
+                .parse()
+                .unwrap_or(15),
+            login_lockout_minutes: std::env::var("LOGIN_LOCKOUT_MINUTES")
+                .unwrap_or_else(|_| "15".to_string())
+                .parse()
+                .unwrap_or(15),
+            login_backoff_base_seconds: std::env::var("LOGIN_BACKOFF_BASE_SECONDS")
+                .unwrap_or_else(|_| "1".to_string())
+                .parse()
+                .unwrap_or(1),
+            login_backoff_max_seconds: std::env::var("LOGIN_BACKOFF_MAX_SECONDS")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()
+                .unwrap_or(30),
+            trusted_proxies: std::env::var("TRUSTED_PROXIES").unwrap_or_default(),
+            rate_limit_api_keys: std::env::var("RATE_LIMIT_API_KEYS").unwrap_or_default(),
+            password_argon2_memory_kib: std::env::var("PASSWORD_ARGON2_MEMORY_KIB")
+                .unwrap_or_else(|_| "19456".to_string())
+                .parse()
+                .unwrap_or(19456),
+            password_argon2_iterations: std::env::var("PASSWORD_ARGON2_ITERATIONS")
+                .unwrap_or_else(|_| "2".to_string())
+                .parse()
+                .unwrap_or(2),
+            password_argon2_parallelism: std::env::var("PASSWORD_ARGON2_PARALLELISM")
+                .unwrap_or_else(|_| "1".to_string())
+                .parse()
+                .unwrap_or(1),
         })
     }
 
@@ -74,3 +235,44 @@ impl Config {
         SocketAddr::from(([127, 0, 0, 1], self.api_port))
     }
 }
+
+// Configuração versionada do projeto (config/project.toml, ou o arquivo de
+// PROJECT_CONFIG_PATH). Só as seções usadas pelo código são lidas; seção
+// ausente fica com o padrão.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ProjectConfig {
+    #[serde(default)]
+    pub security: SecuritySettings,
+    #[serde(default)]
+    pub rate_limit: RateLimitSettings,
+}
+
+// Seção [security]; as chaves de JWT dela ainda vêm do .env
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SecuritySettings {
+    #[serde(flatten)]
+    pub password_policy: PasswordPolicySettings,
+}
+
+impl ProjectConfig {
+    pub fn from_file(path: &str) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| AppError::Config(format!("Erro ao ler {}: {}", path, e)))?;
+
+        toml::from_str(&contents).map_err(|e| AppError::Config(format!("{} inválido: {}", path, e)))
+    }
+
+    // Lido uma vez por processo. Arquivo ausente ou inválido vira o padrão,
+    // com um aviso no log.
+    pub fn shared() -> &'static Self {
+        SHARED_PROJECT_CONFIG.get_or_init(|| {
+            let path = std::env::var("PROJECT_CONFIG_PATH")
+                .unwrap_or_else(|_| "config/project.toml".to_string());
+
+            Self::from_file(&path).unwrap_or_else(|e| {
+                tracing::warn!("{}; usando a configuração padrão do projeto", e);
+                Self::default()
+            })
+        })
+    }
+}
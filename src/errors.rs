@@ -1,4 +1,13 @@
+use std::borrow::Cow;
+use axum::{
+    http::{header, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
 use thiserror::Error;
+use utoipa::ToSchema;
+use crate::libs::shared::logging::correlation::current_correlation_id;
 
 pub type Result<T> = std::result::Result<T, AppError>;
 
@@ -15,6 +24,10 @@ pub enum AppError {
     
     #[error("Erro de validação: {0}")]
     Validation(String),
+
+    // Um erro por regra violada, listados em `errors` no corpo da resposta
+    #[error("Erro de validação: {}", summarize_fields(.0))]
+    InvalidFields(Vec<FieldError>),
     
     #[error("Erro interno: {0}")]
     Internal(String),
@@ -24,11 +37,165 @@ pub enum AppError {
     
     #[error("Recurso não encontrado: {0}")]
     NotFound(String),
+
+    #[error("Acesso negado: {0}")]
+    Forbidden(String),
+
+    #[error("Conflito: {0}")]
+    Conflict(String),
+
+    // Segundos até a próxima tentativa, enviados em Retry-After
+    #[error("Limite de requisições excedido: {0}")]
+    RateLimited(String, u64),
+}
+
+// Regra violada em um campo da requisição. `code` é estável, como o do
+// ProblemDetails; `message` é só para humanos.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct FieldError {
+    pub field: String,
+    pub code: Cow<'static, str>,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: impl Into<String>, code: impl Into<Cow<'static, str>>, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            code: code.into(),
+            message: message.into(),
+        }
+    }
+}
+
+fn summarize_fields(errors: &[FieldError]) -> String {
+    errors
+        .iter()
+        .map(|error| format!("{}: {}", error.field, error.message))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+// Corpo `application/problem+json` (RFC 7807). `code` é estável e serve para
+// o cliente decidir o que fazer; `detail` é só para humanos.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ProblemDetails {
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    pub title: String,
+    pub status: u16,
+    pub detail: String,
+    pub code: &'static str,
+    pub correlation_id: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<FieldError>,
+}
+
+impl AppError {
+    pub fn status(&self) -> StatusCode {
+        match self {
+            AppError::NotFound(_) => StatusCode::NOT_FOUND,
+            AppError::Validation(_) | AppError::InvalidFields(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            AppError::Auth(_) => StatusCode::UNAUTHORIZED,
+            AppError::Forbidden(_) => StatusCode::FORBIDDEN,
+            AppError::Conflict(_) => StatusCode::CONFLICT,
+            AppError::RateLimited(..) => StatusCode::TOO_MANY_REQUESTS,
+            AppError::Redis(_) => StatusCode::SERVICE_UNAVAILABLE,
+            AppError::Config(_) | AppError::Database(_) | AppError::Internal(_) => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::Config(_) => "CONFIG_ERROR",
+            AppError::Database(_) => "DATABASE_ERROR",
+            AppError::Redis(_) => "CACHE_UNAVAILABLE",
+            AppError::Validation(_) | AppError::InvalidFields(_) => "VALIDATION_FAILED",
+            AppError::Internal(_) => "INTERNAL_ERROR",
+            AppError::Auth(_) => "UNAUTHORIZED",
+            AppError::NotFound(_) => "NOT_FOUND",
+            AppError::Forbidden(_) => "FORBIDDEN",
+            AppError::Conflict(_) => "CONFLICT",
+            AppError::RateLimited(..) => "RATE_LIMITED",
+        }
+    }
+
+    // Mensagem mostrada ao cliente. Erros de servidor não expõem detalhes
+    // internos (SQL, URLs); o texto completo fica no log com o mesmo id.
+    fn public_detail(&self) -> String {
+        match self {
+            AppError::Validation(msg)
+            | AppError::Auth(msg)
+            | AppError::NotFound(msg)
+            | AppError::Forbidden(msg)
+            | AppError::Conflict(msg)
+            | AppError::RateLimited(msg, _) => msg.clone(),
+            AppError::InvalidFields(_) => "Um ou mais campos são inválidos".to_string(),
+            AppError::Redis(_) => "Serviço temporariamente indisponível".to_string(),
+            AppError::Config(_) | AppError::Database(_) | AppError::Internal(_) => {
+                "Erro interno do servidor".to_string()
+            }
+        }
+    }
+
+    pub fn to_problem(&self, correlation_id: String) -> ProblemDetails {
+        let status = self.status();
+
+        ProblemDetails {
+            problem_type: "about:blank".to_string(),
+            title: status.canonical_reason().unwrap_or("Error").to_string(),
+            status: status.as_u16(),
+            detail: self.public_detail(),
+            code: self.code(),
+            correlation_id,
+            errors: match self {
+                AppError::InvalidFields(errors) => errors.clone(),
+                _ => Vec::new(),
+            },
+        }
+    }
+
+    // Atalho para um único campo inválido
+    pub fn invalid_field(field: impl Into<String>, code: impl Into<Cow<'static, str>>, message: impl Into<String>) -> Self {
+        AppError::InvalidFields(vec![FieldError::new(field, code, message)])
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        let correlation_id = current_correlation_id();
+        let status = self.status();
+
+        if status.is_server_error() {
+            tracing::error!("[{}] {}: {}", correlation_id, self.code(), self);
+        } else {
+            tracing::warn!("[{}] {}: {}", correlation_id, self.code(), self);
+        }
+
+        let mut response = (status, Json(self.to_problem(correlation_id))).into_response();
+        response.headers_mut().insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/problem+json"),
+        );
+
+        if let AppError::RateLimited(_, retry_after) = self {
+            response.headers_mut().insert(header::RETRY_AFTER, HeaderValue::from(retry_after.max(1)));
+        }
+
+        response
+    }
 }
 
 impl From<sea_orm::DbErr> for AppError {
     fn from(err: sea_orm::DbErr) -> Self {
-        AppError::Database(err.to_string())
+        match err.sql_err() {
+            Some(sea_orm::SqlErr::UniqueConstraintViolation(_)) => {
+                AppError::Conflict("Registro já existe".to_string())
+            }
+            _ => AppError::Database(err.to_string()),
+        }
     }
 }
 
@@ -55,3 +222,21 @@ impl From<std::num::ParseIntError> for AppError {
         AppError::Config(err.to_string())
     }
 }
+
+impl From<redis::RedisError> for AppError {
+    fn from(err: redis::RedisError) -> Self {
+        AppError::Redis(err.to_string())
+    }
+}
+
+impl From<jsonwebtoken::errors::Error> for AppError {
+    fn from(err: jsonwebtoken::errors::Error) -> Self {
+        AppError::Auth(err.to_string())
+    }
+}
+
+impl From<bcrypt::BcryptError> for AppError {
+    fn from(err: bcrypt::BcryptError) -> Self {
+        AppError::Internal(err.to_string())
+    }
+}
@@ -1,4 +1,4 @@
-use sea_orm::{DatabaseConnection, Database as SeaDatabase};
+use crate::libs::shared::database::connection::connect;
 use crate::config::Config;
 use crate::errors::Result;
 
@@ -10,7 +10,7 @@ pub struct Database {
 
 impl Database {
     pub async fn new(config: &Config) -> Result<Self> {
-        let connection = SeaDatabase::connect(&config.database_url).await?;
+        let connection = connect(&config.database_url).await?;
         
         Ok(Self { connection })
     }
@@ -23,6 +23,6 @@ impl Database {
 
 // Função auxiliar para criar conexão
 pub async fn create_connection(database_url: &str) -> Result<DatabaseConnection> {
-    let connection = SeaDatabase::connect(database_url).await?;
+    let connection = connect(database_url).await?;
     Ok(connection)
 }
@@ -6,5 +6,4 @@ pub mod libs;
 
 pub use config::Config;
 pub use errors::Result;
-pub use libs::modules::{AuthService, Claims, User, LoginRequest, RegisterRequest, AuthResponse, UserInfo};
 pub use apps::{create_auth_router, create_admin_router, create_viewer_router};
@@ -1,6 +0,0 @@
-pub mod auth;
-pub mod jwt;
-pub mod access_control;
-
-pub use auth::{AuthService, Claims, User, LoginRequest, RegisterRequest, AuthResponse, UserInfo};
-pub use auth::{auth_middleware, auth_middleware_with_role, extract_claims};
@@ -0,0 +1,184 @@
+use redis::RedisError;
+use std::fmt::Display;
+use std::future::Future;
+use std::time::Duration;
+use super::tiered_cache::TieredCache;
+use crate::libs::shared::database::repositories::videos::{UpdateVideoRequest, Video};
+
+// Versão do formato das entradas. Incrementar sempre que o `Video` mudar de
+// forma, para que entradas antigas nunca sejam desserializadas no novo formato.
+pub const CATALOG_CACHE_VERSION: u32 = 2;
+
+// Tags usadas para invalidar o catálogo
+pub const TAG_CATALOG: &str = "catalog";
+pub const TAG_FEATURED: &str = "featured";
+pub const TAG_SEARCH: &str = "search";
+
+pub fn video_tag(video_id: &str) -> String {
+    format!("video:{}", video_id)
+}
+
+// Tags de uma listagem: a da listagem e a de cada vídeo que ela contém
+fn list_tags(list_tag: &'static str) -> impl Fn(&Vec<Video>) -> Vec<String> + Send + Sync + 'static {
+    move |videos| {
+        let mut tags = vec![list_tag.to_string()];
+        tags.extend(videos.iter().map(|video| video_tag(&video.id)));
+        tags
+    }
+}
+
+// Cache do catálogo de vídeos sobre o `TieredCache` (memória + Redis).
+//
+// Layout das chaves:
+//   catalog:v{versão}:videos:{limit}:{offset}   -> listagens paginadas  (tags: catalog, video:{id}...)
+//   catalog:v{versão}:featured:{limit}          -> destaques            (tags: featured, video:{id}...)
+//   catalog:v{versão}:search:{termo}:{limit}    -> buscas por título    (tags: search, video:{id}...)
+//   catalog:v{versão}:video:{id}                -> detalhe de um vídeo  (tags: video:{id})
+//
+// Cada listagem fica registrada na tag de cada vídeo que contém, então editar
+// um vídeo remove exatamente as entradas em que ele aparece. Remover ou
+// despublicar desloca as páginas seguintes, então aí a listagem inteira cai
+// (tag catalog). O detalhe também
+// guarda "não encontrado", para que ids inexistentes não batam sempre no banco.
+#[derive(Clone)]
+pub struct CatalogCache {
+    cache: TieredCache,
+    ttl: Duration,
+}
+
+impl CatalogCache {
+    pub fn new(cache: TieredCache, ttl: Duration) -> Self {
+        Self { cache, ttl }
+    }
+
+    // Usa o cache compartilhado do processo
+    pub fn shared(ttl: Duration) -> Self {
+        Self::new(TieredCache::shared(), ttl)
+    }
+
+    fn list_key(limit: Option<u64>, offset: Option<u64>) -> String {
+        format!(
+            "catalog:v{}:videos:{}:{}",
+            CATALOG_CACHE_VERSION,
+            limit.map(|l| l.to_string()).unwrap_or_else(|| "all".to_string()),
+            offset.unwrap_or(0),
+        )
+    }
+
+    fn featured_key(limit: Option<u64>) -> String {
+        format!(
+            "catalog:v{}:featured:{}",
+            CATALOG_CACHE_VERSION,
+            limit.map(|l| l.to_string()).unwrap_or_else(|| "default".to_string()),
+        )
+    }
+
+    fn search_key(title: &str, limit: Option<u64>) -> String {
+        format!(
+            "catalog:v{}:search:{}:{}",
+            CATALOG_CACHE_VERSION,
+            title.trim().to_lowercase(),
+            limit.map(|l| l.to_string()).unwrap_or_else(|| "default".to_string()),
+        )
+    }
+
+    fn video_key(video_id: &str) -> String {
+        format!("catalog:v{}:video:{}", CATALOG_CACHE_VERSION, video_id)
+    }
+
+    pub async fn videos<E, F, Fut>(&self, limit: Option<u64>, offset: Option<u64>, loader: F) -> Result<Vec<Video>, E>
+    where
+        E: Display + Send + 'static,
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Vec<Video>, E>> + Send + 'static,
+    {
+        self.cache
+            .get_or_load(&Self::list_key(limit, offset), self.ttl, list_tags(TAG_CATALOG), loader)
+            .await
+    }
+
+    pub async fn featured<E, F, Fut>(&self, limit: Option<u64>, loader: F) -> Result<Vec<Video>, E>
+    where
+        E: Display + Send + 'static,
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Vec<Video>, E>> + Send + 'static,
+    {
+        self.cache
+            .get_or_load(&Self::featured_key(limit), self.ttl, list_tags(TAG_FEATURED), loader)
+            .await
+    }
+
+    pub async fn search<E, F, Fut>(&self, title: &str, limit: Option<u64>, loader: F) -> Result<Vec<Video>, E>
+    where
+        E: Display + Send + 'static,
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Vec<Video>, E>> + Send + 'static,
+    {
+        self.cache
+            .get_or_load(&Self::search_key(title, limit), self.ttl, list_tags(TAG_SEARCH), loader)
+            .await
+    }
+
+    pub async fn video<E, F, Fut>(&self, video_id: &str, loader: F) -> Result<Option<Video>, E>
+    where
+        E: Display + Send + 'static,
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Option<Video>, E>> + Send + 'static,
+    {
+        let tag = video_tag(video_id);
+
+        self.cache
+            .get_or_load(&Self::video_key(video_id), self.ttl, move |_: &Option<Video>| vec![tag.clone()], loader)
+            .await
+    }
+
+    // Remove todas as entradas registradas nas tags informadas
+    pub async fn invalidate(&self, tags: &[String]) -> Result<u64, RedisError> {
+        self.cache.invalidate(tags).await
+    }
+}
+
+// Descreve uma escrita em um vídeo para calcular quais tags ela afeta
+#[derive(Debug, Default, Clone)]
+pub struct CatalogChange {
+    pub video_id: String,
+    pub created: bool,
+    pub deleted: bool,
+    pub title_changed: bool,
+    pub rating_changed: bool,
+    pub featured_changed: bool,
+    pub availability_changed: bool,
+}
+
+impl CatalogChange {
+    // Só os campos enviados na atualização contam como alterados
+    pub fn from_update(video_id: &str, request: &UpdateVideoRequest) -> Self {
+        Self {
+            video_id: video_id.to_string(),
+            title_changed: request.title.is_some(),
+            rating_changed: request.rating.is_some(),
+            featured_changed: request.is_featured.is_some(),
+            availability_changed: request.is_available.is_some(),
+            ..Default::default()
+        }
+    }
+
+    pub fn tags(&self) -> Vec<String> {
+        // Entradas que já contêm o vídeo
+        let mut tags = vec![video_tag(&self.video_id)];
+
+        // Listagens que podem passar a conter o vídeo ou cujas páginas se
+        // deslocam com a entrada ou saída dele
+        if self.created || self.deleted || self.availability_changed {
+            tags.push(TAG_CATALOG.to_string());
+        }
+        if self.created || self.availability_changed || self.title_changed {
+            tags.push(TAG_SEARCH.to_string());
+        }
+        if self.featured_changed || self.availability_changed || self.rating_changed {
+            tags.push(TAG_FEATURED.to_string());
+        }
+
+        tags
+    }
+}
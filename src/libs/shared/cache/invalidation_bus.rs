@@ -0,0 +1,208 @@
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock, RwLock};
+use std::time::Duration;
+use redis::{Client, RedisError};
+use super::redis_cache::RedisCache;
+use crate::config::Config;
+
+// Canal onde todas as instâncias publicam e escutam as mudanças
+pub const INVALIDATION_CHANNEL: &str = "cache:invalidation";
+
+const RECONNECT_MIN_DELAY: Duration = Duration::from_secs(1);
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(30);
+
+static SHARED_BUS: OnceLock<InvalidationBus> = OnceLock::new();
+
+pub fn user_tag(user_id: &str) -> String {
+    format!("user:{}", user_id)
+}
+
+pub fn access_group_tag(group_id: &str) -> String {
+    format!("access_group:{}", group_id)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CacheEntity {
+    Video,
+    User,
+    AccessGroup,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CacheAction {
+    Created,
+    Updated,
+    Deleted,
+}
+
+// Mudança em uma entidade, com as tags de cache que ela afeta
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEvent {
+    pub entity: CacheEntity,
+    pub id: String,
+    pub action: CacheAction,
+    pub tags: Vec<String>,
+}
+
+impl CacheEvent {
+    pub fn video(video_id: &str, action: CacheAction, tags: Vec<String>) -> Self {
+        Self {
+            entity: CacheEntity::Video,
+            id: video_id.to_string(),
+            action,
+            tags,
+        }
+    }
+
+    pub fn user(user_id: &str, action: CacheAction) -> Self {
+        Self {
+            entity: CacheEntity::User,
+            id: user_id.to_string(),
+            action,
+            tags: vec![user_tag(user_id)],
+        }
+    }
+
+    pub fn access_group(group_id: &str, action: CacheAction) -> Self {
+        Self {
+            entity: CacheEntity::AccessGroup,
+            id: group_id.to_string(),
+            action,
+            tags: vec![access_group_tag(group_id)],
+        }
+    }
+}
+
+// Quem mantém estado local derivado do banco (ex.: a camada em memória do
+// `TieredCache`) implementa este trait para ser avisado das mudanças
+pub trait InvalidationListener: Send + Sync {
+    fn on_event(&self, event: &CacheEvent);
+
+    // Chamado a cada (re)conexão com o Redis: eventos podem ter sido perdidos
+    // enquanto a assinatura estava fora, então o estado local não é confiável
+    fn on_resync(&self);
+}
+
+// Barramento de invalidação sobre o pub/sub do Redis.
+//
+// As escritas nos repositórios publicam um `CacheEvent`; cada processo mantém
+// uma assinatura no canal e repassa os eventos aos seus listeners, inclusive
+// os publicados por ele mesmo. Publicar é sempre best-effort: se o Redis
+// estiver fora, as entradas locais expiram pelo TTL da camada em memória.
+pub struct InvalidationBus {
+    listeners: RwLock<Vec<Arc<dyn InvalidationListener>>>,
+    started: AtomicBool,
+}
+
+impl InvalidationBus {
+    fn new() -> Self {
+        Self {
+            listeners: RwLock::new(Vec::new()),
+            started: AtomicBool::new(false),
+        }
+    }
+
+    pub fn shared() -> &'static Self {
+        SHARED_BUS.get_or_init(Self::new)
+    }
+
+    // Registra um listener e garante que a assinatura deste processo está ativa
+    pub fn subscribe(&'static self, listener: Arc<dyn InvalidationListener>) {
+        self.listeners.write().unwrap().push(listener);
+        self.start();
+    }
+
+    // Publica um evento sem propagar falhas: a escrita no banco já foi feita
+    pub async fn publish(&self, event: CacheEvent) {
+        if let Err(e) = self.try_publish(&event).await {
+            tracing::warn!("Erro ao publicar invalidação de {:?} {}: {}", event.entity, event.id, e);
+        }
+    }
+
+    pub async fn try_publish(&self, event: &CacheEvent) -> Result<u64, RedisError> {
+        let redis = RedisCache::shared().await?;
+        redis.publish(INVALIDATION_CHANNEL, event).await
+    }
+
+    fn dispatch(&self, event: &CacheEvent) {
+        for listener in self.listeners.read().unwrap().iter() {
+            listener.on_event(event);
+        }
+    }
+
+    fn resync(&self) {
+        for listener in self.listeners.read().unwrap().iter() {
+            listener.on_resync();
+        }
+    }
+
+    fn start(&'static self) {
+        // Fora de um runtime tokio não há como manter a assinatura
+        let runtime = match tokio::runtime::Handle::try_current() {
+            Ok(runtime) => runtime,
+            Err(_) => return,
+        };
+
+        if self.started.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        runtime.spawn(async move {
+            let mut delay = RECONNECT_MIN_DELAY;
+
+            loop {
+                match self.listen().await {
+                    Ok(()) => {
+                        tracing::warn!("Assinatura de invalidação encerrada pelo Redis, reconectando");
+                        delay = RECONNECT_MIN_DELAY;
+                    }
+                    Err(e) => {
+                        tracing::warn!("Erro na assinatura de invalidação: {} (nova tentativa em {:?})", e, delay);
+                    }
+                }
+
+                tokio::time::sleep(delay).await;
+                delay = (delay * 2).min(RECONNECT_MAX_DELAY);
+            }
+        });
+    }
+
+    // Mantém uma conexão dedicada ao pub/sub até ela cair
+    async fn listen(&self) -> Result<(), RedisError> {
+        let config = Config::from_env()
+            .map_err(|e| RedisError::from((
+                redis::ErrorKind::InvalidClientConfig,
+                "Configuração inválida",
+                e.to_string(),
+            )))?;
+
+        let client = Client::open(config.redis_url.as_str())?;
+        let mut pubsub = client.get_async_connection().await?.into_pubsub();
+        pubsub.subscribe(INVALIDATION_CHANNEL).await?;
+
+        tracing::info!("Assinatura de invalidação ativa em {}", INVALIDATION_CHANNEL);
+        self.resync();
+
+        let mut messages = pubsub.into_on_message();
+        while let Some(message) = messages.next().await {
+            let payload: String = match message.get_payload() {
+                Ok(payload) => payload,
+                Err(e) => {
+                    tracing::warn!("Mensagem de invalidação ilegível: {}", e);
+                    continue;
+                }
+            };
+
+            match serde_json::from_str::<CacheEvent>(&payload) {
+                Ok(event) => self.dispatch(&event),
+                Err(e) => tracing::warn!("Evento de invalidação inválido: {}", e),
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,92 @@
+use lru::LruCache;
+use std::any::Any;
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+struct MemoryEntry {
+    value: Arc<dyn Any + Send + Sync>,
+    expires_at: Instant,
+    tags: Vec<String>,
+}
+
+// Cache em memória do processo, limitado em quantidade de entradas (LRU).
+// Guarda os valores já desserializados, então um HIT não paga JSON nem rede.
+pub struct MemoryCache {
+    entries: Mutex<LruCache<String, MemoryEntry>>,
+}
+
+impl MemoryCache {
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        Self {
+            entries: Mutex::new(LruCache::new(capacity)),
+        }
+    }
+
+    pub fn get<V>(&self, key: &str) -> Option<V>
+    where
+        V: Clone + Send + Sync + 'static,
+    {
+        let mut entries = self.entries.lock().unwrap();
+
+        let expired = match entries.get(key) {
+            Some(entry) if entry.expires_at > Instant::now() => {
+                return entry.value.downcast_ref::<V>().cloned();
+            }
+            Some(_) => true,
+            None => false,
+        };
+
+        if expired {
+            entries.pop(key);
+        }
+
+        None
+    }
+
+    pub fn insert<V>(&self, key: &str, value: V, ttl: Duration, tags: &[String])
+    where
+        V: Send + Sync + 'static,
+    {
+        let entry = MemoryEntry {
+            value: Arc::new(value),
+            expires_at: Instant::now() + ttl,
+            tags: tags.to_vec(),
+        };
+
+        self.entries.lock().unwrap().put(key.to_string(), entry);
+    }
+
+    pub fn remove(&self, key: &str) -> bool {
+        self.entries.lock().unwrap().pop(key).is_some()
+    }
+
+    // Remove as entradas registradas em qualquer uma das tags
+    pub fn invalidate_tags(&self, tags: &[String]) -> usize {
+        let mut entries = self.entries.lock().unwrap();
+
+        let keys: Vec<String> = entries
+            .iter()
+            .filter(|(_, entry)| entry.tags.iter().any(|tag| tags.contains(tag)))
+            .map(|(key, _)| key.clone())
+            .collect();
+
+        for key in &keys {
+            entries.pop(key);
+        }
+
+        keys.len()
+    }
+
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
@@ -1,2 +1,5 @@
 pub mod redis_cache;
 pub mod memory_cache;
+pub mod invalidation_bus;
+pub mod tiered_cache;
+pub mod catalog_cache;
@@ -1,19 +1,174 @@
-use redis::{Client, Connection, Commands, RedisError};
+use redis::{aio::ConnectionManager, AsyncCommands, Client, RedisError, RedisResult};
 use serde::{Serialize, Deserialize};
 use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::sync::OnceCell;
+use crate::config::Config;
 
+// Conexão compartilhada pelo processo inteiro (ver `RedisCache::shared`)
+static SHARED_CACHE: OnceCell<RedisCache> = OnceCell::const_new();
+
+// Momento da última falha ao conectar, para não pagar o connect_timeout
+// em toda requisição enquanto o Redis estiver fora do ar
+static LAST_CONNECT_FAILURE: Mutex<Option<Instant>> = Mutex::new(None);
+const CONNECT_RETRY_COOLDOWN: Duration = Duration::from_secs(5);
+
+// Quantidade de chaves pedidas por iteração de SCAN/SSCAN
+const SCAN_BATCH_SIZE: u64 = 500;
+
+// Tira o SET da tag do lugar (RENAME) para que a invalidação percorra só as
+// chaves registradas até aqui; registros novos criam outro SET. A cópia
+// expira sozinha se o processo cair no meio da limpeza.
+//
+//   KEYS = tag, cópia temporária
+//   ARGV = TTL da cópia em segundos
+const DETACH_TAG_SCRIPT: &str = r#"
+if redis.call('EXISTS', KEYS[1]) == 0 then
+    return 0
+end
+
+redis.call('RENAME', KEYS[1], KEYS[2])
+redis.call('EXPIRE', KEYS[2], ARGV[1])
+
+return 1
+"#;
+const DETACHED_TAG_TTL_SECONDS: u64 = 3600;
+
+#[derive(Debug, Clone)]
+pub struct RedisCacheOptions {
+    // Tempo máximo para estabelecer a conexão inicial
+    pub connect_timeout: Duration,
+    // Tempo máximo de espera pela resposta de cada comando
+    pub response_timeout: Duration,
+    // Tentativas de reconexão (backoff exponencial) após perda da conexão
+    pub reconnect_retries: usize,
+}
+
+impl Default for RedisCacheOptions {
+    fn default() -> Self {
+        Self {
+            connect_timeout: Duration::from_millis(2000),
+            response_timeout: Duration::from_millis(500),
+            reconnect_retries: 6,
+        }
+    }
+}
+
+impl RedisCacheOptions {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            connect_timeout: Duration::from_millis(config.redis_connect_timeout_ms),
+            response_timeout: Duration::from_millis(config.redis_response_timeout_ms),
+            reconnect_retries: config.redis_reconnect_retries,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RedisHealth {
+    pub status: String,
+    pub latency_ms: u128,
+    pub error: Option<String>,
+}
+
+// Cache sobre uma única conexão multiplexada e assíncrona.
+// O `ConnectionManager` reconecta sozinho em caso de queda e é barato de
+// clonar: todos os clones compartilham o mesmo socket.
+#[derive(Clone)]
 pub struct RedisCache {
-    client: Client,
+    connection: ConnectionManager,
+    response_timeout: Duration,
+}
+
+fn timeout_error(operation: &str) -> RedisError {
+    RedisError::from(std::io::Error::new(
+        std::io::ErrorKind::TimedOut,
+        format!("Timeout no Redis durante {}", operation),
+    ))
+}
+
+fn serialization_error(e: serde_json::Error) -> RedisError {
+    RedisError::from((
+        redis::ErrorKind::TypeError,
+        "Serialization failed",
+        e.to_string(),
+    ))
+}
+
+fn deserialization_error(e: serde_json::Error) -> RedisError {
+    RedisError::from((
+        redis::ErrorKind::TypeError,
+        "Deserialization failed",
+        e.to_string(),
+    ))
 }
 
 impl RedisCache {
-    pub fn new(redis_url: &str) -> Result<Self, RedisError> {
+    pub async fn connect(redis_url: &str, options: RedisCacheOptions) -> Result<Self, RedisError> {
         let client = Client::open(redis_url)?;
-        Ok(Self { client })
+
+        let connection = tokio::time::timeout(
+            options.connect_timeout,
+            client.get_connection_manager_with_backoff(2, 100, options.reconnect_retries),
+        )
+        .await
+        .map_err(|_| timeout_error("a conexão"))??;
+
+        Ok(Self {
+            connection,
+            response_timeout: options.response_timeout,
+        })
+    }
+
+    // Instância única do processo, criada na primeira chamada a partir do
+    // `Config`. Se o Redis estiver fora do ar, tenta de novo após o cooldown.
+    pub async fn shared() -> Result<Self, RedisError> {
+        if let Some(cache) = SHARED_CACHE.get() {
+            return Ok(cache.clone());
+        }
+
+        if let Some(failed_at) = *LAST_CONNECT_FAILURE.lock().unwrap() {
+            if failed_at.elapsed() < CONNECT_RETRY_COOLDOWN {
+                return Err(RedisError::from((
+                    redis::ErrorKind::IoError,
+                    "Redis indisponível",
+                    "aguardando nova tentativa de conexão".to_string(),
+                )));
+            }
+        }
+
+        let result = SHARED_CACHE
+            .get_or_try_init(|| async {
+                let config = Config::from_env()
+                    .map_err(|e| RedisError::from((
+                        redis::ErrorKind::InvalidClientConfig,
+                        "Configuração inválida",
+                        e.to_string(),
+                    )))?;
+
+                Self::connect(&config.redis_url, RedisCacheOptions::from_config(&config)).await
+            })
+            .await;
+
+        match result {
+            Ok(cache) => Ok(cache.clone()),
+            Err(e) => {
+                *LAST_CONNECT_FAILURE.lock().unwrap() = Some(Instant::now());
+                Err(e)
+            }
+        }
     }
 
-    fn get_connection(&self) -> Result<Connection, RedisError> {
-        self.client.get_connection()
+    // Aplica o timeout de resposta a um comando
+    async fn run<T, F>(&self, operation: &str, command: F) -> RedisResult<T>
+    where
+        F: Future<Output = RedisResult<T>>,
+    {
+        tokio::time::timeout(self.response_timeout, command)
+            .await
+            .map_err(|_| timeout_error(operation))?
     }
 
     pub async fn set<K, V>(&self, key: K, value: &V) -> Result<(), RedisError>
@@ -21,15 +176,10 @@ impl RedisCache {
         K: AsRef<str>,
         V: Serialize,
     {
-        let mut conn = self.get_connection()?;
-        let serialized = serde_json::to_string(value)
-            .map_err(|e| RedisError::from((
-                redis::ErrorKind::InvalidArgument,
-                "Serialization failed",
-                e.to_string(),
-            )))?;
-        
-        conn.set(key.as_ref(), serialized)
+        let serialized = serde_json::to_string(value).map_err(serialization_error)?;
+
+        let mut conn = self.connection.clone();
+        self.run("SET", conn.set::<_, _, ()>(key.as_ref(), serialized)).await
     }
 
     pub async fn get<K, V>(&self, key: K) -> Result<Option<V>, RedisError>
@@ -37,70 +187,257 @@ impl RedisCache {
         K: AsRef<str>,
         V: for<'de> Deserialize<'de>,
     {
-        let mut conn = self.get_connection()?;
-        let result: Option<String> = conn.get(key.as_ref())?;
-        
+        let mut conn = self.connection.clone();
+        let result: Option<String> = self.run("GET", conn.get(key.as_ref())).await?;
+
         if let Some(serialized) = result {
-            let deserialized = serde_json::from_str(&serialized)
-                .map_err(|e| RedisError::from((
-                    redis::ErrorKind::InvalidArgument,
-                    "Deserialization failed",
-                    e.to_string(),
-                )))?;
+            let deserialized = serde_json::from_str(&serialized).map_err(deserialization_error)?;
             Ok(Some(deserialized))
         } else {
             Ok(None)
         }
     }
 
+    // Lê várias chaves num único MGET; a ordem do resultado é a de `keys`
+    pub async fn get_many<V>(&self, keys: &[String]) -> Result<Vec<Option<V>>, RedisError>
+    where
+        V: for<'de> Deserialize<'de>,
+    {
+        if keys.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut conn = self.connection.clone();
+        let results: Vec<Option<String>> = self.run(
+            "MGET",
+            redis::cmd("MGET").arg(keys).query_async(&mut conn),
+        ).await?;
+
+        results
+            .into_iter()
+            .map(|result| match result {
+                Some(serialized) => serde_json::from_str(&serialized).map(Some).map_err(deserialization_error),
+                None => Ok(None),
+            })
+            .collect()
+    }
+
     pub async fn set_with_ttl<K, V>(&self, key: K, value: &V, ttl_seconds: u64) -> Result<(), RedisError>
     where
         K: AsRef<str>,
         V: Serialize,
     {
-        let mut conn = self.get_connection()?;
-        let serialized = serde_json::to_string(value)
-            .map_err(|e| RedisError::from((
-                redis::ErrorKind::InvalidArgument,
-                "Serialization failed",
-                e.to_string(),
-            )))?;
-        
-        let mut pipe = redis::pipe();
-        pipe.set(key.as_ref(), serialized)
-            .expire(key.as_ref(), ttl_seconds as usize);
-        
-        pipe.execute(&mut conn)?;
-        Ok(())
+        let serialized = serde_json::to_string(value).map_err(serialization_error)?;
+
+        let mut conn = self.connection.clone();
+        self.run("SETEX", conn.set_ex::<_, _, ()>(key.as_ref(), serialized, ttl_seconds)).await
     }
 
     pub async fn delete<K>(&self, key: K) -> Result<bool, RedisError>
     where
         K: AsRef<str>,
     {
-        let mut conn = self.get_connection()?;
-        let result: i32 = conn.del(key.as_ref())?;
+        let mut conn = self.connection.clone();
+        let result: i32 = self.run("DEL", conn.del(key.as_ref())).await?;
         Ok(result > 0)
     }
 
-    pub async fn clear_pattern(&self, pattern: &str) -> Result<u64, RedisError> {
-        let mut conn = self.get_connection()?;
-        let keys: Vec<String> = conn.keys(pattern)?;
-        
+    // Remove várias chaves num único DEL; devolve quantas existiam
+    pub async fn delete_many(&self, keys: &[String]) -> Result<u64, RedisError> {
         if keys.is_empty() {
             return Ok(0);
         }
-        
-        let result: i32 = conn.del(&keys)?;
-        Ok(result as u64)
+
+        let mut conn = self.connection.clone();
+        self.run("DEL", conn.del(keys)).await
+    }
+
+    // Lê e remove a chave numa única operação (GETDEL): entre dois clientes
+    // concorrentes, só um recebe o valor
+    pub async fn take<K, V>(&self, key: K) -> Result<Option<V>, RedisError>
+    where
+        K: AsRef<str>,
+        V: for<'de> Deserialize<'de>,
+    {
+        let mut conn = self.connection.clone();
+        let result: Option<String> = self.run(
+            "GETDEL",
+            redis::cmd("GETDEL").arg(key.as_ref()).query_async(&mut conn),
+        ).await?;
+
+        match result {
+            Some(serialized) => serde_json::from_str(&serialized).map(Some).map_err(deserialization_error),
+            None => Ok(None),
+        }
+    }
+
+    // Adiciona um membro a um SET e estende o TTL do SET se necessário
+    pub async fn add_to_set<K, M>(&self, key: K, member: M, ttl_seconds: u64) -> Result<(), RedisError>
+    where
+        K: AsRef<str>,
+        M: AsRef<str>,
+    {
+        let mut pipe = redis::pipe();
+        pipe.atomic()
+            .sadd(key.as_ref(), member.as_ref())
+            .ignore();
+        pipe.cmd("EXPIRE").arg(key.as_ref()).arg(ttl_seconds).arg("NX").ignore();
+        pipe.cmd("EXPIRE").arg(key.as_ref()).arg(ttl_seconds).arg("GT").ignore();
+
+        let mut conn = self.connection.clone();
+        self.run("SADD", pipe.query_async::<_, ()>(&mut conn)).await
+    }
+
+    pub async fn remove_from_set<K, M>(&self, key: K, member: M) -> Result<bool, RedisError>
+    where
+        K: AsRef<str>,
+        M: AsRef<str>,
+    {
+        let mut conn = self.connection.clone();
+        let result: i32 = self.run("SREM", conn.srem(key.as_ref(), member.as_ref())).await?;
+        Ok(result > 0)
+    }
+
+    pub async fn set_members<K>(&self, key: K) -> Result<Vec<String>, RedisError>
+    where
+        K: AsRef<str>,
+    {
+        let mut conn = self.connection.clone();
+        self.run("SMEMBERS", conn.smembers(key.as_ref())).await
+    }
+
+    // Remove as chaves que casam com o padrão usando SCAN por cursor, em
+    // lotes, para nunca bloquear o Redis como o KEYS faria
+    pub async fn clear_pattern(&self, pattern: &str) -> Result<u64, RedisError> {
+        let mut conn = self.connection.clone();
+        let mut cursor: u64 = 0;
+        let mut deleted: u64 = 0;
+
+        loop {
+            let (next_cursor, keys): (u64, Vec<String>) = self.run(
+                "SCAN",
+                redis::cmd("SCAN")
+                    .cursor_arg(cursor)
+                    .arg("MATCH")
+                    .arg(pattern)
+                    .arg("COUNT")
+                    .arg(SCAN_BATCH_SIZE)
+                    .query_async(&mut conn),
+            ).await?;
+
+            if !keys.is_empty() {
+                let removed: u64 = self.run("UNLINK", redis::cmd("UNLINK").arg(&keys).query_async(&mut conn)).await?;
+                deleted += removed;
+            }
+
+            if next_cursor == 0 {
+                break;
+            }
+            cursor = next_cursor;
+        }
+
+        Ok(deleted)
+    }
+
+    fn tag_key(tag: &str) -> String {
+        format!("tag:{}", tag)
+    }
+
+    // Grava o valor com TTL e registra a chave em cada tag (um SET por tag).
+    // O SET da tag expira junto com a chave mais longa registrada nele.
+    pub async fn set_with_tags<K, V>(&self, key: K, value: &V, ttl_seconds: u64, tags: &[String]) -> Result<(), RedisError>
+    where
+        K: AsRef<str>,
+        V: Serialize,
+    {
+        let serialized = serde_json::to_string(value).map_err(serialization_error)?;
+
+        let mut pipe = redis::pipe();
+        pipe.atomic()
+            .set_ex(key.as_ref(), serialized, ttl_seconds)
+            .ignore();
+
+        for tag in tags {
+            let tag_key = Self::tag_key(tag);
+            pipe.sadd(&tag_key, key.as_ref()).ignore();
+            pipe.cmd("EXPIRE").arg(&tag_key).arg(ttl_seconds).arg("NX").ignore();
+            pipe.cmd("EXPIRE").arg(&tag_key).arg(ttl_seconds).arg("GT").ignore();
+        }
+
+        let mut conn = self.connection.clone();
+        self.run("SET com tags", pipe.query_async::<_, ()>(&mut conn)).await
+    }
+
+    // Remove todas as chaves registradas na tag e a própria tag. Chaves
+    // registradas durante a limpeza ficam no SET novo e não são apagadas.
+    pub async fn invalidate_tag(&self, tag: &str) -> Result<u64, RedisError> {
+        let detached_key = format!("{}:invalidating:{}", Self::tag_key(tag), uuid::Uuid::new_v4());
+        let detached: i64 = self
+            .run_script(
+                &redis::Script::new(DETACH_TAG_SCRIPT),
+                &[Self::tag_key(tag), detached_key.clone()],
+                &[DETACHED_TAG_TTL_SECONDS],
+            )
+            .await?;
+
+        if detached == 0 {
+            return Ok(0);
+        }
+
+        let mut conn = self.connection.clone();
+        let mut cursor: u64 = 0;
+        let mut deleted: u64 = 0;
+
+        loop {
+            let (next_cursor, keys): (u64, Vec<String>) = self.run(
+                "SSCAN",
+                redis::cmd("SSCAN")
+                    .arg(&detached_key)
+                    .cursor_arg(cursor)
+                    .arg("COUNT")
+                    .arg(SCAN_BATCH_SIZE)
+                    .query_async(&mut conn),
+            ).await?;
+
+            if !keys.is_empty() {
+                let removed: u64 = self.run("UNLINK", redis::cmd("UNLINK").arg(&keys).query_async(&mut conn)).await?;
+                deleted += removed;
+            }
+
+            if next_cursor == 0 {
+                break;
+            }
+            cursor = next_cursor;
+        }
+
+        self.run("DEL", conn.del::<_, ()>(&detached_key)).await?;
+        Ok(deleted)
+    }
+
+    pub async fn invalidate_tags(&self, tags: &[String]) -> Result<u64, RedisError> {
+        let mut deleted = 0;
+        for tag in tags {
+            deleted += self.invalidate_tag(tag).await?;
+        }
+        Ok(deleted)
+    }
+
+    // Publica uma mensagem JSON em um canal; retorna quantos assinantes a receberam
+    pub async fn publish<V>(&self, channel: &str, message: &V) -> Result<u64, RedisError>
+    where
+        V: Serialize,
+    {
+        let payload = serde_json::to_string(message).map_err(serialization_error)?;
+        let mut conn = self.connection.clone();
+        self.run("PUBLISH", conn.publish(channel, payload)).await
     }
 
     pub async fn exists<K>(&self, key: K) -> Result<bool, RedisError>
     where
         K: AsRef<str>,
     {
-        let mut conn = self.get_connection()?;
-        let result: i32 = conn.exists(key.as_ref())?;
+        let mut conn = self.connection.clone();
+        let result: i32 = self.run("EXISTS", conn.exists(key.as_ref())).await?;
         Ok(result > 0)
     }
 
@@ -108,17 +445,51 @@ impl RedisCache {
     where
         K: AsRef<str>,
     {
-        let mut conn = self.get_connection()?;
-        conn.incr(key.as_ref(), amount)
+        let mut conn = self.connection.clone();
+        self.run("INCRBY", conn.incr(key.as_ref(), amount)).await
+    }
+
+    // Contador com janela fixa: o TTL é definido no primeiro incremento e
+    // não é renovado pelos seguintes
+    pub async fn increment_with_ttl<K>(&self, key: K, amount: i64, ttl_seconds: u64) -> Result<i64, RedisError>
+    where
+        K: AsRef<str>,
+    {
+        let mut pipe = redis::pipe();
+        pipe.atomic().incr(key.as_ref(), amount);
+        pipe.cmd("EXPIRE").arg(key.as_ref()).arg(ttl_seconds).arg("NX").ignore();
+
+        let mut conn = self.connection.clone();
+        let (count,): (i64,) = self.run("INCRBY", pipe.query_async(&mut conn)).await?;
+        Ok(count)
+    }
+
+    // Executa um script Lua (EVALSHA, com EVAL na primeira vez); o script
+    // roda de forma atômica no servidor
+    pub async fn run_script<T, A>(&self, script: &redis::Script, keys: &[String], args: &[A]) -> Result<T, RedisError>
+    where
+        T: redis::FromRedisValue,
+        A: redis::ToRedisArgs,
+    {
+        let mut invocation = script.prepare_invoke();
+        for key in keys {
+            invocation.key(key);
+        }
+        for arg in args {
+            invocation.arg(arg);
+        }
+
+        let mut conn = self.connection.clone();
+        self.run("EVALSHA", invocation.invoke_async(&mut conn)).await
     }
 
     pub async fn get_ttl<K>(&self, key: K) -> Result<Option<u64>, RedisError>
     where
         K: AsRef<str>,
     {
-        let mut conn = self.get_connection()?;
-        let result: i32 = conn.ttl(key.as_ref())?;
-        
+        let mut conn = self.connection.clone();
+        let result: i32 = self.run("TTL", conn.ttl(key.as_ref())).await?;
+
         if result > 0 {
             Ok(Some(result as u64))
         } else if result == -1 {
@@ -134,15 +505,10 @@ impl RedisCache {
         F: AsRef<str>,
         V: Serialize,
     {
-        let mut conn = self.get_connection()?;
-        let serialized = serde_json::to_string(value)
-            .map_err(|e| RedisError::from((
-                redis::ErrorKind::InvalidArgument,
-                "Serialization failed",
-                e.to_string(),
-            )))?;
-        
-        conn.hset(key.as_ref(), field.as_ref(), serialized)
+        let serialized = serde_json::to_string(value).map_err(serialization_error)?;
+
+        let mut conn = self.connection.clone();
+        self.run("HSET", conn.hset::<_, _, _, ()>(key.as_ref(), field.as_ref(), serialized)).await
     }
 
     pub async fn get_hash<K, F, V>(&self, key: K, field: F) -> Result<Option<V>, RedisError>
@@ -151,16 +517,11 @@ impl RedisCache {
         F: AsRef<str>,
         V: for<'de> Deserialize<'de>,
     {
-        let mut conn = self.get_connection()?;
-        let result: Option<String> = conn.hget(key.as_ref(), field.as_ref())?;
-        
+        let mut conn = self.connection.clone();
+        let result: Option<String> = self.run("HGET", conn.hget(key.as_ref(), field.as_ref())).await?;
+
         if let Some(serialized) = result {
-            let deserialized = serde_json::from_str(&serialized)
-                .map_err(|e| RedisError::from((
-                    redis::ErrorKind::InvalidArgument,
-                    "Deserialization failed",
-                    e.to_string(),
-                )))?;
+            let deserialized = serde_json::from_str(&serialized).map_err(deserialization_error)?;
             Ok(Some(deserialized))
         } else {
             Ok(None)
@@ -172,20 +533,15 @@ impl RedisCache {
         K: AsRef<str>,
         V: for<'de> Deserialize<'de>,
     {
-        let mut conn = self.get_connection()?;
-        let result: HashMap<String, String> = conn.hgetall(key.as_ref())?;
-        
+        let mut conn = self.connection.clone();
+        let result: HashMap<String, String> = self.run("HGETALL", conn.hgetall(key.as_ref())).await?;
+
         let mut deserialized = HashMap::new();
         for (field, serialized) in result {
-            let value: V = serde_json::from_str(&serialized)
-                .map_err(|e| RedisError::from((
-                    redis::ErrorKind::InvalidArgument,
-                    "Deserialization failed",
-                    e.to_string(),
-                )))?;
+            let value: V = serde_json::from_str(&serialized).map_err(deserialization_error)?;
             deserialized.insert(field, value);
         }
-        
+
         Ok(deserialized)
     }
 
@@ -194,18 +550,50 @@ impl RedisCache {
         K: AsRef<str>,
         F: AsRef<str>,
     {
-        let mut conn = self.get_connection()?;
-        let result: i32 = conn.hdel(key.as_ref(), field.as_ref())?;
+        let mut conn = self.connection.clone();
+        let result: i32 = self.run("HDEL", conn.hdel(key.as_ref(), field.as_ref())).await?;
         Ok(result > 0)
     }
 
     pub async fn ping(&self) -> Result<String, RedisError> {
-        let mut conn = self.get_connection()?;
-        conn.ping()
+        let mut conn = self.connection.clone();
+        self.run("PING", redis::cmd("PING").query_async(&mut conn)).await
+    }
+
+    // Sonda de saúde: executa um PING e mede a latência.
+    // Nunca retorna erro, para poder ser servida direto num endpoint /health.
+    pub async fn health(&self) -> RedisHealth {
+        let start = Instant::now();
+
+        match self.ping().await {
+            Ok(_) => RedisHealth {
+                status: "up".to_string(),
+                latency_ms: start.elapsed().as_millis(),
+                error: None,
+            },
+            Err(e) => RedisHealth {
+                status: "down".to_string(),
+                latency_ms: start.elapsed().as_millis(),
+                error: Some(e.to_string()),
+            },
+        }
+    }
+
+    // Sonda de saúde da conexão compartilhada, inclusive quando ela nem
+    // chegou a ser estabelecida
+    pub async fn shared_health() -> RedisHealth {
+        match Self::shared().await {
+            Ok(cache) => cache.health().await,
+            Err(e) => RedisHealth {
+                status: "down".to_string(),
+                latency_ms: 0,
+                error: Some(e.to_string()),
+            },
+        }
     }
 
     pub async fn flush_all(&self) -> Result<(), RedisError> {
-        let mut conn = self.get_connection()?;
-        conn.flushall()
+        let mut conn = self.connection.clone();
+        self.run("FLUSHALL", redis::cmd("FLUSHALL").query_async(&mut conn)).await
     }
 }
@@ -0,0 +1,322 @@
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::future::Future;
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use chrono::Utc;
+use redis::RedisError;
+use tokio::sync::Mutex as AsyncMutex;
+use super::invalidation_bus::{CacheEvent, InvalidationBus, InvalidationListener};
+use super::memory_cache::MemoryCache;
+use super::redis_cache::RedisCache;
+use crate::config::Config;
+use crate::libs::shared::logging::{log_cache_hit, log_cache_miss};
+
+static SHARED_TIERED_CACHE: OnceLock<TieredCache> = OnceLock::new();
+
+#[derive(Debug, Clone)]
+pub struct TieredCacheOptions {
+    // Máximo de entradas no cache em memória (0 desliga a camada)
+    pub memory_capacity: usize,
+    // TTL máximo de uma entrada em memória
+    pub memory_ttl: Duration,
+    // Agressividade da renovação antecipada (0 desliga)
+    pub early_refresh_beta: f64,
+}
+
+impl Default for TieredCacheOptions {
+    fn default() -> Self {
+        Self {
+            memory_capacity: 1000,
+            memory_ttl: Duration::from_secs(30),
+            early_refresh_beta: 1.0,
+        }
+    }
+}
+
+impl TieredCacheOptions {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            memory_capacity: config.cache_memory_capacity,
+            memory_ttl: Duration::from_secs(config.cache_memory_ttl_secs),
+            early_refresh_beta: config.cache_early_refresh_beta,
+        }
+    }
+}
+
+// Valor armazenado junto com o necessário para a renovação antecipada
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEnvelope<V> {
+    value: V,
+    // Quanto o loader levou para produzir o valor
+    delta_ms: u64,
+    // Expiração lógica (unix, em ms)
+    expires_at_ms: i64,
+    // Tags da entrada, para que a cópia promovida à memória também seja invalidável
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+struct TieredCacheInner {
+    memory: Option<MemoryCache>,
+    options: TieredCacheOptions,
+    // Um lock por chave sendo carregada, para coalescer MISSes concorrentes
+    inflight: Mutex<HashMap<String, Arc<AsyncMutex<()>>>>,
+}
+
+// Fachada de cache em duas camadas: LRU em memória (opcional) na frente do
+// Redis. Garante que MISSes concorrentes da mesma chave chamem o loader uma
+// única vez por processo e renova valores populares antes de expirarem
+// (XFetch), para que a expiração não mande todo mundo ao banco ao mesmo tempo.
+//
+// O Redis é opcional em tempo de execução: se estiver fora do ar, o cache
+// segue funcionando só com a memória e o coalescing.
+#[derive(Clone)]
+pub struct TieredCache {
+    inner: Arc<TieredCacheInner>,
+}
+
+impl TieredCache {
+    pub fn new(options: TieredCacheOptions) -> Self {
+        let memory = NonZeroUsize::new(options.memory_capacity).map(MemoryCache::new);
+
+        Self {
+            inner: Arc::new(TieredCacheInner {
+                memory,
+                options,
+                inflight: Mutex::new(HashMap::new()),
+            }),
+        }
+    }
+
+    // Instância única do processo, para que a memória e o coalescing sejam
+    // compartilhados por todas as requisições
+    pub fn shared() -> Self {
+        SHARED_TIERED_CACHE
+            .get_or_init(|| {
+                let options = Config::from_env()
+                    .map(|config| TieredCacheOptions::from_config(&config))
+                    .unwrap_or_default();
+
+                let cache = Self::new(options);
+
+                // Mudanças feitas em outras instâncias também limpam a memória local
+                InvalidationBus::shared().subscribe(Arc::new(cache.clone()));
+
+                cache
+            })
+            .clone()
+    }
+
+    pub async fn get_or_load<V, E, T, F, Fut>(
+        &self,
+        key: &str,
+        ttl: Duration,
+        tags: T,
+        loader: F,
+    ) -> Result<V, E>
+    where
+        V: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+        E: Display + Send + 'static,
+        T: Fn(&V) -> Vec<String> + Send + Sync + 'static,
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<V, E>> + Send + 'static,
+    {
+        if let Some(envelope) = self.lookup::<V>(key).await {
+            log_cache_hit(key);
+
+            if self.should_refresh_early(&envelope) {
+                self.spawn_refresh(key.to_string(), ttl, tags, loader);
+            }
+
+            return Ok(envelope.value);
+        }
+
+        log_cache_miss(key);
+        self.load_coalesced(key, ttl, &tags, &loader).await
+    }
+
+    // Remove das duas camadas as entradas registradas nas tags
+    pub async fn invalidate(&self, tags: &[String]) -> Result<u64, RedisError> {
+        let mut removed = self.invalidate_local(tags) as u64;
+
+        let redis = RedisCache::shared().await?;
+        removed += redis.invalidate_tags(tags).await?;
+
+        Ok(removed)
+    }
+
+    // Remove só da camada em memória deste processo
+    pub fn invalidate_local(&self, tags: &[String]) -> usize {
+        match &self.inner.memory {
+            Some(memory) => memory.invalidate_tags(tags),
+            None => 0,
+        }
+    }
+
+    pub fn clear_local(&self) {
+        if let Some(memory) = &self.inner.memory {
+            memory.clear();
+        }
+    }
+
+    async fn lookup<V>(&self, key: &str) -> Option<CacheEnvelope<V>>
+    where
+        V: DeserializeOwned + Clone + Send + Sync + 'static,
+    {
+        if let Some(memory) = &self.inner.memory {
+            if let Some(envelope) = memory.get::<CacheEnvelope<V>>(key) {
+                return Some(envelope);
+            }
+        }
+
+        let redis = RedisCache::shared().await.ok()?;
+        let envelope: Option<CacheEnvelope<V>> = match redis.get(key).await {
+            Ok(envelope) => envelope,
+            Err(e) => {
+                tracing::warn!("Erro ao ler {} do Redis: {}", key, e);
+                None
+            }
+        };
+
+        // Promove para a memória, sem ultrapassar a expiração original
+        if let (Some(memory), Some(envelope)) = (&self.inner.memory, &envelope) {
+            memory.insert(key, envelope.clone(), self.memory_ttl_for(envelope), &envelope.tags);
+        }
+
+        envelope
+    }
+
+    async fn load_coalesced<V, E, T, F, Fut>(&self, key: &str, ttl: Duration, tags: &T, loader: &F) -> Result<V, E>
+    where
+        V: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+        T: Fn(&V) -> Vec<String>,
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<V, E>>,
+    {
+        let lock = self.inflight_lock(key);
+        let guard = lock.lock().await;
+
+        // Quem esperou o lock encontra o valor já carregado pelo primeiro
+        let result = match self.lookup::<V>(key).await {
+            Some(envelope) => Ok(envelope.value),
+            None => self.load_and_store(key, ttl, tags, loader).await,
+        };
+
+        drop(guard);
+        self.release_inflight(key, lock);
+        result
+    }
+
+    fn spawn_refresh<V, E, T, F, Fut>(&self, key: String, ttl: Duration, tags: T, loader: F)
+    where
+        V: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+        E: Display + Send + 'static,
+        T: Fn(&V) -> Vec<String> + Send + Sync + 'static,
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<V, E>> + Send + 'static,
+    {
+        let cache = self.clone();
+
+        tokio::spawn(async move {
+            let lock = cache.inflight_lock(&key);
+
+            // Se alguém já está carregando a chave, não há o que renovar
+            if let Ok(guard) = lock.try_lock() {
+                if let Err(e) = cache.load_and_store(&key, ttl, &tags, &loader).await {
+                    tracing::warn!("Erro na renovação antecipada de {}: {}", key, e);
+                }
+                drop(guard);
+            }
+
+            cache.release_inflight(&key, lock);
+        });
+    }
+
+    async fn load_and_store<V, E, T, F, Fut>(&self, key: &str, ttl: Duration, tags: &T, loader: &F) -> Result<V, E>
+    where
+        V: Serialize + Clone + Send + Sync + 'static,
+        T: Fn(&V) -> Vec<String>,
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<V, E>>,
+    {
+        let start = Instant::now();
+        let value = loader().await?;
+
+        let envelope = CacheEnvelope {
+            value: value.clone(),
+            delta_ms: start.elapsed().as_millis() as u64,
+            expires_at_ms: Utc::now().timestamp_millis() + ttl.as_millis() as i64,
+            tags: tags(&value),
+        };
+        let tags = &envelope.tags;
+
+        if let Some(memory) = &self.inner.memory {
+            memory.insert(key, envelope.clone(), self.memory_ttl_for(&envelope), tags);
+        }
+
+        // Falha ao gravar no Redis não invalida o valor já carregado
+        match RedisCache::shared().await {
+            Ok(redis) => {
+                if let Err(e) = redis.set_with_tags(key, &envelope, ttl.as_secs(), tags).await {
+                    tracing::warn!("Erro ao salvar {} no Redis: {}", key, e);
+                }
+            }
+            Err(e) => tracing::warn!("Redis indisponível ao salvar {}: {}", key, e),
+        }
+
+        Ok(value)
+    }
+
+    // XFetch: quanto mais perto da expiração e mais caro o loader, maior a
+    // chance de uma requisição renovar o valor antes que ele expire
+    fn should_refresh_early<V>(&self, envelope: &CacheEnvelope<V>) -> bool {
+        let beta = self.inner.options.early_refresh_beta;
+        if beta <= 0.0 {
+            return false;
+        }
+
+        // random() está em [0, 1); 1 - random() evita ln(0)
+        let random: f64 = 1.0 - rand::random::<f64>();
+        let now_ms = Utc::now().timestamp_millis() as f64;
+        let gap = envelope.delta_ms as f64 * beta * -random.ln();
+
+        now_ms + gap >= envelope.expires_at_ms as f64
+    }
+
+    fn memory_ttl_for<V>(&self, envelope: &CacheEnvelope<V>) -> Duration {
+        let remaining_ms = (envelope.expires_at_ms - Utc::now().timestamp_millis()).max(0) as u64;
+        self.inner.options.memory_ttl.min(Duration::from_millis(remaining_ms))
+    }
+
+    fn inflight_lock(&self, key: &str) -> Arc<AsyncMutex<()>> {
+        self.inner.inflight
+            .lock()
+            .unwrap()
+            .entry(key.to_string())
+            .or_insert_with(|| Arc::new(AsyncMutex::new(())))
+            .clone()
+    }
+
+    // Remove o lock do mapa quando ninguém mais está esperando por ele
+    fn release_inflight(&self, key: &str, lock: Arc<AsyncMutex<()>>) {
+        let mut inflight = self.inner.inflight.lock().unwrap();
+
+        // Uma referência é do mapa e outra é a nossa
+        if Arc::strong_count(&lock) <= 2 {
+            inflight.remove(key);
+        }
+    }
+}
+
+impl InvalidationListener for TieredCache {
+    fn on_event(&self, event: &CacheEvent) {
+        self.invalidate_local(&event.tags);
+    }
+
+    fn on_resync(&self) {
+        self.clear_local();
+    }
+}
@@ -0,0 +1,51 @@
+use sea_orm::{DatabaseBackend, DbErr};
+use crate::config::Config;
+
+// Banco escolhido pelo esquema do DATABASE_URL
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DatabaseKind {
+    Sqlite,
+    Postgres,
+    SqlServer,
+}
+
+impl DatabaseKind {
+    pub fn from_url(database_url: &str) -> Result<Self, DbErr> {
+        let scheme = database_url
+            .split_once(':')
+            .map(|(scheme, _)| scheme.to_lowercase())
+            .unwrap_or_default();
+
+        match scheme.as_str() {
+            "sqlite" => Ok(Self::Sqlite),
+            "postgres" | "postgresql" => Ok(Self::Postgres),
+            "sqlserver" | "mssql" => Ok(Self::SqlServer),
+            _ => Err(DbErr::Custom(format!(
+                "DATABASE_URL com esquema '{}' não suportado (use sqlite://, postgres:// ou sqlserver://)",
+                scheme
+            ))),
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Result<Self, DbErr> {
+        Self::from_url(&config.database_url)
+    }
+
+    // Dialeto usado pelo query builder. O SeaORM 0.12 não tem driver nem
+    // dialeto para SQL Server, então não há backend para ele.
+    pub fn backend(self) -> Option<DatabaseBackend> {
+        match self {
+            Self::Sqlite => Some(DatabaseBackend::Sqlite),
+            Self::Postgres => Some(DatabaseBackend::Postgres),
+            Self::SqlServer => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Sqlite => "SQLite",
+            Self::Postgres => "PostgreSQL",
+            Self::SqlServer => "SQL Server",
+        }
+    }
+}
@@ -1,8 +1,35 @@
-use sea_orm::{DatabaseConnection, Database};
+use sea_orm::{ConnectOptions, Database, DatabaseConnection, DbErr};
+use super::backend::DatabaseKind;
 use crate::config::Config;
 
-pub async fn create_connection(config: &Config) -> Result<DatabaseConnection, sea_orm::DbErr> {
-    Database::connect(&config.database_url).await
+pub async fn create_connection(config: &Config) -> Result<DatabaseConnection, DbErr> {
+    connect(&config.database_url).await
+}
+
+// Conecta ao banco indicado pelo esquema do DATABASE_URL. Os repositórios
+// geram o SQL pelo query builder, no dialeto da conexão.
+pub async fn connect(database_url: &str) -> Result<DatabaseConnection, DbErr> {
+    let kind = DatabaseKind::from_url(database_url)?;
+
+    if kind.backend().is_none() {
+        return Err(DbErr::Custom(format!(
+            "{} não tem driver disponível no SeaORM 0.12; use sqlite:// ou postgres:// no DATABASE_URL",
+            kind.name()
+        )));
+    }
+
+    let mut options = ConnectOptions::new(database_url.to_string());
+    options.sqlx_logging(false);
+
+    // Cada conexão SQLite em memória é um banco diferente
+    if kind == DatabaseKind::Sqlite && database_url.contains(":memory:") {
+        options.max_connections(1);
+    }
+
+    let connection = Database::connect(options).await?;
+    tracing::info!("Conectado ao banco ({})", kind.name());
+
+    Ok(connection)
 }
 
 pub async fn test_connection(connection: &DatabaseConnection) -> Result<(), sea_orm::DbErr> {
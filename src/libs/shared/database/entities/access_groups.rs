@@ -0,0 +1,42 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Grupos de acesso; `permissions` guarda um JSON com as permissões do grupo
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "access_groups")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    #[sea_orm(unique)]
+    pub name: String,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub description: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub permissions: Option<String>,
+    pub created_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(has_many = "super::users_access_groups::Entity")]
+    UsersAccessGroups,
+}
+
+impl Related<super::users_access_groups::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::UsersAccessGroups.def()
+    }
+}
+
+// Usuários do grupo, via users_access_groups
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::users_access_groups::Relation::User.def()
+    }
+
+    fn via() -> Option<RelationDef> {
+        Some(super::users_access_groups::Relation::AccessGroup.def().rev())
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
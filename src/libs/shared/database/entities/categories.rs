@@ -0,0 +1,40 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Categorias (gêneros) do catálogo
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "categories")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    #[sea_orm(unique)]
+    pub name: String,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub description: Option<String>,
+    pub created_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(has_many = "super::video_categories::Entity")]
+    VideoCategories,
+}
+
+impl Related<super::video_categories::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::VideoCategories.def()
+    }
+}
+
+// Vídeos da categoria, via video_categories
+impl Related<super::videos::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::video_categories::Relation::Video.def()
+    }
+
+    fn via() -> Option<RelationDef> {
+        Some(super::video_categories::Relation::Category.def().rev())
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
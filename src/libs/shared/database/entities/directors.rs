@@ -0,0 +1,41 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Diretores
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "directors")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub name: String,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub biography: Option<String>,
+    pub birth_date: Option<Date>,
+    pub profile_picture_url: Option<String>,
+    pub created_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(has_many = "super::video_directors::Entity")]
+    VideoDirectors,
+}
+
+impl Related<super::video_directors::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::VideoDirectors.def()
+    }
+}
+
+// Vídeos em que participa, via video_directors
+impl Related<super::videos::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::video_directors::Relation::Video.def()
+    }
+
+    fn via() -> Option<RelationDef> {
+        Some(super::video_directors::Relation::Director.def().rev())
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,19 @@
+// Entidades SeaORM espelhando config/schema.sql (uma por tabela)
+pub mod prelude;
+
+pub mod users;
+pub mod categories;
+pub mod videos;
+pub mod actors;
+pub mod directors;
+pub mod video_categories;
+pub mod video_actors;
+pub mod video_directors;
+pub mod access_groups;
+pub mod users_access_groups;
+pub mod watch_history;
+pub mod favorites;
+pub mod ratings;
+pub mod user_tokens;
+pub mod user_mfa;
+pub mod user_recovery_codes;
@@ -0,0 +1,101 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Usuários da plataforma (viewers e administradores)
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "users")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    #[sea_orm(unique)]
+    pub email: String,
+    pub password_hash: String,
+    pub name: String,
+    pub role: String,
+    pub profile_picture_url: Option<String>,
+    pub subscription_status: Option<String>,
+    pub subscription_expires_at: Option<DateTimeUtc>,
+    // Nulo até o usuário abrir o link de confirmação
+    pub email_verified_at: Option<DateTimeUtc>,
+    pub created_at: DateTimeUtc,
+    pub updated_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(has_many = "super::users_access_groups::Entity")]
+    UsersAccessGroups,
+
+    #[sea_orm(has_many = "super::watch_history::Entity")]
+    WatchHistory,
+
+    #[sea_orm(has_many = "super::favorites::Entity")]
+    Favorites,
+
+    #[sea_orm(has_many = "super::ratings::Entity")]
+    Ratings,
+
+    #[sea_orm(has_many = "super::user_tokens::Entity")]
+    UserTokens,
+
+    #[sea_orm(has_one = "super::user_mfa::Entity")]
+    UserMfa,
+
+    #[sea_orm(has_many = "super::user_recovery_codes::Entity")]
+    UserRecoveryCodes,
+}
+
+impl Related<super::users_access_groups::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::UsersAccessGroups.def()
+    }
+}
+
+impl Related<super::watch_history::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::WatchHistory.def()
+    }
+}
+
+impl Related<super::favorites::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Favorites.def()
+    }
+}
+
+impl Related<super::ratings::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Ratings.def()
+    }
+}
+
+impl Related<super::user_tokens::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::UserTokens.def()
+    }
+}
+
+impl Related<super::user_mfa::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::UserMfa.def()
+    }
+}
+
+impl Related<super::user_recovery_codes::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::UserRecoveryCodes.def()
+    }
+}
+
+// Grupos de acesso do usuário, via users_access_groups
+impl Related<super::access_groups::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::users_access_groups::Relation::AccessGroup.def()
+    }
+
+    fn via() -> Option<RelationDef> {
+        Some(super::users_access_groups::Relation::User.def().rev())
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,55 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Vínculo usuário-grupo de acesso
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "users_access_groups")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub user_id: String,
+    pub access_group_id: String,
+    pub assigned_at: DateTimeUtc,
+    pub assigned_by: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::UserId",
+        to = "super::users::Column::Id",
+        on_delete = "Cascade"
+    )]
+    User,
+
+    #[sea_orm(
+        belongs_to = "super::access_groups::Entity",
+        from = "Column::AccessGroupId",
+        to = "super::access_groups::Column::Id",
+        on_delete = "Cascade"
+    )]
+    AccessGroup,
+
+    // Quem fez a atribuição; não há Related para não conflitar com `User`
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::AssignedBy",
+        to = "super::users::Column::Id"
+    )]
+    AssignedBy,
+}
+
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl Related<super::access_groups::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::AccessGroup.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
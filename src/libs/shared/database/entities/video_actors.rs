@@ -0,0 +1,48 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Vínculo vídeo-ator, com o papel no elenco
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "video_actors")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub video_id: String,
+    pub actor_id: String,
+    pub role_name: Option<String>,
+    pub is_lead: bool,
+    pub created_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::videos::Entity",
+        from = "Column::VideoId",
+        to = "super::videos::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Video,
+
+    #[sea_orm(
+        belongs_to = "super::actors::Entity",
+        from = "Column::ActorId",
+        to = "super::actors::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Actor,
+}
+
+impl Related<super::videos::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Video.def()
+    }
+}
+
+impl Related<super::actors::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Actor.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,46 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Vínculo vídeo-diretor
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "video_directors")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub video_id: String,
+    pub director_id: String,
+    pub created_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::videos::Entity",
+        from = "Column::VideoId",
+        to = "super::videos::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Video,
+
+    #[sea_orm(
+        belongs_to = "super::directors::Entity",
+        from = "Column::DirectorId",
+        to = "super::directors::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Director,
+}
+
+impl Related<super::videos::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Video.def()
+    }
+}
+
+impl Related<super::directors::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Director.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,116 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Vídeos do catálogo
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "videos")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub title: String,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub description: Option<String>,
+    pub duration_seconds: i32,
+    pub release_year: Option<i32>,
+    #[sea_orm(column_type = "Decimal(Some((3, 2)))")]
+    pub rating: f64,
+    pub thumbnail_url: Option<String>,
+    pub video_url: Option<String>,
+    pub trailer_url: Option<String>,
+    pub is_featured: bool,
+    pub is_available: bool,
+    pub created_at: DateTimeUtc,
+    pub updated_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(has_many = "super::video_categories::Entity")]
+    VideoCategories,
+
+    #[sea_orm(has_many = "super::video_actors::Entity")]
+    VideoActors,
+
+    #[sea_orm(has_many = "super::video_directors::Entity")]
+    VideoDirectors,
+
+    #[sea_orm(has_many = "super::watch_history::Entity")]
+    WatchHistory,
+
+    #[sea_orm(has_many = "super::favorites::Entity")]
+    Favorites,
+
+    #[sea_orm(has_many = "super::ratings::Entity")]
+    Ratings,
+}
+
+impl Related<super::video_categories::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::VideoCategories.def()
+    }
+}
+
+impl Related<super::video_actors::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::VideoActors.def()
+    }
+}
+
+impl Related<super::video_directors::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::VideoDirectors.def()
+    }
+}
+
+impl Related<super::watch_history::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::WatchHistory.def()
+    }
+}
+
+impl Related<super::favorites::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Favorites.def()
+    }
+}
+
+impl Related<super::ratings::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Ratings.def()
+    }
+}
+
+// Categorias do vídeo, via video_categories
+impl Related<super::categories::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::video_categories::Relation::Category.def()
+    }
+
+    fn via() -> Option<RelationDef> {
+        Some(super::video_categories::Relation::Video.def().rev())
+    }
+}
+
+// Elenco do vídeo, via video_actors
+impl Related<super::actors::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::video_actors::Relation::Actor.def()
+    }
+
+    fn via() -> Option<RelationDef> {
+        Some(super::video_actors::Relation::Video.def().rev())
+    }
+}
+
+// Direção do vídeo, via video_directors
+impl Related<super::directors::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::video_directors::Relation::Director.def()
+    }
+
+    fn via() -> Option<RelationDef> {
+        Some(super::video_directors::Relation::Video.def().rev())
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
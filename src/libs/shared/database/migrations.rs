@@ -0,0 +1,300 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use chrono::{DateTime, Utc};
+use sea_orm::sea_query::{Alias, ColumnDef, Expr, Query, Table};
+use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection, DbErr, Statement, TransactionTrait};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use crate::impl_from_row;
+use super::row_mapping::query_all_as;
+
+// Tabela onde ficam registradas as migrações aplicadas
+pub const HISTORY_TABLE: &str = "_migrations_history";
+
+#[derive(Error, Debug)]
+pub enum MigrationError {
+    #[error("Erro de banco de dados: {0}")]
+    Database(#[from] DbErr),
+
+    #[error("Erro ao ler migrações em {path}: {source}")]
+    Io { path: String, source: std::io::Error },
+
+    #[error("Migração {version} foi alterada depois de aplicada (checksum {applied} != {current})")]
+    ChecksumMismatch { version: String, applied: String, current: String },
+
+    #[error("Migração {version} está aplicada no banco mas não existe em disco")]
+    MissingOnDisk { version: String },
+
+    #[error("Migração {version} não tem a versão para {dialect} ({file})")]
+    MissingDialect { version: String, dialect: String, file: String },
+
+    #[error("Falha ao aplicar a migração {version}: {source}")]
+    Failed { version: String, source: DbErr },
+}
+
+// Uma pasta `{timestamp}-{nome}` de config/migrations
+#[derive(Debug, Clone)]
+pub struct Migration {
+    pub version: String,
+    pub name: String,
+    pub path: PathBuf,
+    pub sql: String,
+    pub checksum: String,
+}
+
+#[derive(Debug, Clone)]
+struct AppliedMigration {
+    version: String,
+    checksum: String,
+    applied_at: DateTime<Utc>,
+}
+
+impl_from_row!(AppliedMigration {
+    version,
+    checksum,
+    applied_at,
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationState {
+    Applied,
+    Pending,
+    // Aplicada, mas o arquivo mudou desde então
+    Modified,
+    // Aplicada, mas a pasta não existe mais
+    MissingOnDisk,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationStatus {
+    pub version: String,
+    pub name: String,
+    pub state: MigrationState,
+    pub applied_at: Option<DateTime<Utc>>,
+}
+
+// Runner de migrações SQL.
+//
+// Cada pasta de `dir` traz um `migration.{sqlite,postgres}.sql` por backend;
+// o `migration.sql` é T-SQL e não é executado aqui. Pasta sem a versão do
+// backend atual é erro, em vez de tentar rodar T-SQL. As pastas são aplicadas
+// em ordem de nome (o prefixo é um timestamp) e o checksum SHA-256 do arquivo
+// aplicado fica no histórico.
+pub struct Migrator {
+    db: DatabaseConnection,
+    dir: PathBuf,
+}
+
+impl Migrator {
+    pub fn new(db: DatabaseConnection, dir: impl Into<PathBuf>) -> Self {
+        Self { db, dir: dir.into() }
+    }
+
+    fn backend(&self) -> DatabaseBackend {
+        self.db.get_database_backend()
+    }
+
+    fn dialect_suffix(&self) -> &'static str {
+        match self.backend() {
+            DatabaseBackend::Sqlite => "sqlite",
+            DatabaseBackend::Postgres => "postgres",
+            DatabaseBackend::MySql => "mysql",
+        }
+    }
+
+    // Lê as migrações do disco, em ordem
+    pub fn discover(&self) -> Result<Vec<Migration>, MigrationError> {
+        let io_error = |path: &Path| {
+            let path = path.display().to_string();
+            move |source| MigrationError::Io { path, source }
+        };
+
+        let mut folders: Vec<PathBuf> = fs::read_dir(&self.dir)
+            .map_err(io_error(&self.dir))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_dir())
+            .collect();
+        folders.sort();
+
+        let mut migrations = Vec::new();
+
+        for folder in folders {
+            let folder_name = folder.file_name().unwrap_or_default().to_string_lossy().to_string();
+            let (version, name) = folder_name
+                .split_once(['-', '_'])
+                .map(|(version, name)| (version.to_string(), name.to_string()))
+                .unwrap_or_else(|| (folder_name.clone(), folder_name.clone()));
+
+            let file = folder.join(format!("migration.{}.sql", self.dialect_suffix()));
+
+            if !file.is_file() {
+                // Pasta sem SQL nenhum não é migração
+                if !folder.join("migration.sql").is_file() {
+                    continue;
+                }
+
+                return Err(MigrationError::MissingDialect {
+                    version,
+                    dialect: self.dialect_suffix().to_string(),
+                    file: file.display().to_string(),
+                });
+            }
+
+            let sql = fs::read_to_string(&file).map_err(io_error(&file))?;
+
+            migrations.push(Migration {
+                version,
+                name,
+                checksum: hex::encode(Sha256::digest(sql.as_bytes())),
+                path: file,
+                sql,
+            });
+        }
+
+        Ok(migrations)
+    }
+
+    async fn ensure_history_table(&self) -> Result<(), DbErr> {
+        let table = Table::create()
+            .table(Alias::new(HISTORY_TABLE))
+            .if_not_exists()
+            .col(ColumnDef::new(Alias::new("version")).string_len(64).not_null().primary_key())
+            .col(ColumnDef::new(Alias::new("name")).string_len(255).not_null())
+            .col(ColumnDef::new(Alias::new("checksum")).string_len(64).not_null())
+            .col(ColumnDef::new(Alias::new("applied_at")).timestamp_with_time_zone().not_null())
+            .col(ColumnDef::new(Alias::new("execution_ms")).big_integer().not_null())
+            .to_owned();
+
+        self.db.execute(self.backend().build(&table)).await?;
+        Ok(())
+    }
+
+    async fn applied(&self) -> Result<Vec<AppliedMigration>, DbErr> {
+        let query = Query::select()
+            .columns([Alias::new("version"), Alias::new("checksum"), Alias::new("applied_at")])
+            .from(Alias::new(HISTORY_TABLE))
+            .order_by(Alias::new("version"), sea_orm::Order::Asc)
+            .to_owned();
+
+        query_all_as(&self.db, self.backend().build(&query)).await
+    }
+
+    pub async fn status(&self) -> Result<Vec<MigrationStatus>, MigrationError> {
+        self.ensure_history_table().await?;
+
+        let migrations = self.discover()?;
+        let applied = self.applied().await?;
+
+        let mut statuses: Vec<MigrationStatus> = migrations
+            .iter()
+            .map(|migration| {
+                let record = applied.iter().find(|a| a.version == migration.version);
+                let state = match record {
+                    Some(record) if record.checksum == migration.checksum => MigrationState::Applied,
+                    Some(_) => MigrationState::Modified,
+                    None => MigrationState::Pending,
+                };
+
+                MigrationStatus {
+                    version: migration.version.clone(),
+                    name: migration.name.clone(),
+                    state,
+                    applied_at: record.map(|record| record.applied_at),
+                }
+            })
+            .collect();
+
+        for record in &applied {
+            if !migrations.iter().any(|m| m.version == record.version) {
+                statuses.push(MigrationStatus {
+                    version: record.version.clone(),
+                    name: String::new(),
+                    state: MigrationState::MissingOnDisk,
+                    applied_at: Some(record.applied_at),
+                });
+            }
+        }
+
+        statuses.sort_by(|a, b| a.version.cmp(&b.version));
+        Ok(statuses)
+    }
+
+    // Aplica as migrações pendentes e retorna as versões aplicadas. Não faz
+    // nada se alguma migração já aplicada tiver sido editada ou removida.
+    pub async fn up(&self) -> Result<Vec<String>, MigrationError> {
+        self.ensure_history_table().await?;
+
+        let migrations = self.discover()?;
+        let applied = self.applied().await?;
+
+        for record in &applied {
+            match migrations.iter().find(|m| m.version == record.version) {
+                Some(migration) if migration.checksum != record.checksum => {
+                    return Err(MigrationError::ChecksumMismatch {
+                        version: record.version.clone(),
+                        applied: record.checksum.clone(),
+                        current: migration.checksum.clone(),
+                    });
+                }
+                Some(_) => {}
+                None => {
+                    return Err(MigrationError::MissingOnDisk {
+                        version: record.version.clone(),
+                    });
+                }
+            }
+        }
+
+        let mut newly_applied = Vec::new();
+
+        for migration in migrations {
+            if applied.iter().any(|a| a.version == migration.version) {
+                continue;
+            }
+
+            self.apply(&migration).await.map_err(|source| MigrationError::Failed {
+                version: migration.version.clone(),
+                source,
+            })?;
+
+            tracing::info!("Migração aplicada: {}-{}", migration.version, migration.name);
+            newly_applied.push(migration.version);
+        }
+
+        Ok(newly_applied)
+    }
+
+    // Aplica uma migração e a registra no histórico na mesma transação
+    async fn apply(&self, migration: &Migration) -> Result<(), DbErr> {
+        let start = Instant::now();
+        let txn = self.db.begin().await?;
+
+        txn.execute_unprepared(&migration.sql).await?;
+
+        let insert = Query::insert()
+            .into_table(Alias::new(HISTORY_TABLE))
+            .columns([
+                Alias::new("version"),
+                Alias::new("name"),
+                Alias::new("checksum"),
+                Alias::new("applied_at"),
+                Alias::new("execution_ms"),
+            ])
+            .values_panic([
+                Expr::value(migration.version.clone()),
+                Expr::value(migration.name.clone()),
+                Expr::value(migration.checksum.clone()),
+                Expr::value(Utc::now()),
+                Expr::value(start.elapsed().as_millis() as i64),
+            ])
+            .to_owned();
+
+        let stmt: Statement = self.backend().build(&insert);
+        txn.execute(stmt).await?;
+
+        txn.commit().await
+    }
+}
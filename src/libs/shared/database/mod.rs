@@ -1,2 +1,6 @@
 pub mod repositories;
 pub mod connection;
+pub mod backend;
+pub mod row_mapping;
+pub mod entities;
+pub mod migrations;
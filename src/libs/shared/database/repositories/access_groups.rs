@@ -0,0 +1,182 @@
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, JoinType, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, RelationTrait, Set, TransactionTrait};
+use serde::Deserialize;
+use uuid::Uuid;
+use chrono::Utc;
+use crate::libs::shared::cache::invalidation_bus::{CacheAction, CacheEvent, InvalidationBus};
+use crate::libs::shared::database::entities::{access_groups, users, users_access_groups};
+
+pub type AccessGroup = access_groups::Model;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateAccessGroupRequest {
+    pub name: String,
+    pub description: Option<String>,
+    pub permissions: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateAccessGroupRequest {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub permissions: Option<String>,
+}
+
+pub struct AccessGroupsRepository {
+    db: DatabaseConnection,
+}
+
+impl AccessGroupsRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    pub async fn create(&self, request: CreateAccessGroupRequest) -> Result<AccessGroup, sea_orm::DbErr> {
+        access_groups::ActiveModel {
+            id: Set(Uuid::new_v4().to_string()),
+            name: Set(request.name),
+            description: Set(request.description),
+            permissions: Set(request.permissions),
+            created_at: Set(Utc::now()),
+        }
+        .insert(&self.db)
+        .await
+    }
+
+    pub async fn find_by_id(&self, group_id: &str) -> Result<Option<AccessGroup>, sea_orm::DbErr> {
+        access_groups::Entity::find_by_id(group_id).one(&self.db).await
+    }
+
+    pub async fn find_by_name(&self, name: &str) -> Result<Option<AccessGroup>, sea_orm::DbErr> {
+        access_groups::Entity::find()
+            .filter(access_groups::Column::Name.eq(name))
+            .one(&self.db)
+            .await
+    }
+
+    pub async fn find_all(&self) -> Result<Vec<AccessGroup>, sea_orm::DbErr> {
+        access_groups::Entity::find()
+            .order_by_asc(access_groups::Column::Name)
+            .all(&self.db)
+            .await
+    }
+
+    pub async fn update(&self, group_id: &str, request: UpdateAccessGroupRequest) -> Result<Option<AccessGroup>, sea_orm::DbErr> {
+        let group = match self.find_by_id(group_id).await? {
+            Some(group) => group,
+            None => return Ok(None),
+        };
+
+        let mut active: access_groups::ActiveModel = group.into();
+
+        if let Some(name) = request.name {
+            active.name = Set(name);
+        }
+
+        if let Some(description) = request.description {
+            active.description = Set(Some(description));
+        }
+
+        if let Some(permissions) = request.permissions {
+            active.permissions = Set(Some(permissions));
+        }
+
+        let group = active.update(&self.db).await?;
+
+        // As permissões de todos os membros podem ter mudado
+        InvalidationBus::shared()
+            .publish(CacheEvent::access_group(group_id, CacheAction::Updated))
+            .await;
+
+        Ok(Some(group))
+    }
+
+    // Remove também os vínculos: a FK de users_access_groups não tem cascade
+    pub async fn delete(&self, group_id: &str) -> Result<bool, sea_orm::DbErr> {
+        let txn = self.db.begin().await?;
+
+        users_access_groups::Entity::delete_many()
+            .filter(users_access_groups::Column::AccessGroupId.eq(group_id))
+            .exec(&txn)
+            .await?;
+
+        let result = access_groups::Entity::delete_by_id(group_id).exec(&txn).await?;
+        txn.commit().await?;
+
+        let removed = result.rows_affected > 0;
+
+        if removed {
+            InvalidationBus::shared()
+                .publish(CacheEvent::access_group(group_id, CacheAction::Deleted))
+                .await;
+        }
+
+        Ok(removed)
+    }
+
+    // Grupos de um usuário
+    pub async fn find_by_user(&self, user_id: &str) -> Result<Vec<AccessGroup>, sea_orm::DbErr> {
+        access_groups::Entity::find()
+            .join(JoinType::InnerJoin, access_groups::Relation::UsersAccessGroups.def())
+            .filter(users_access_groups::Column::UserId.eq(user_id))
+            .order_by_asc(access_groups::Column::Name)
+            .all(&self.db)
+            .await
+    }
+
+    // Membros de um grupo
+    pub async fn find_users(&self, group_id: &str) -> Result<Vec<users::Model>, sea_orm::DbErr> {
+        users::Entity::find()
+            .join(JoinType::InnerJoin, users::Relation::UsersAccessGroups.def())
+            .filter(users_access_groups::Column::AccessGroupId.eq(group_id))
+            .order_by_asc(users::Column::Name)
+            .all(&self.db)
+            .await
+    }
+
+    pub async fn is_assigned(&self, user_id: &str, group_id: &str) -> Result<bool, sea_orm::DbErr> {
+        let count = users_access_groups::Entity::find()
+            .filter(users_access_groups::Column::UserId.eq(user_id))
+            .filter(users_access_groups::Column::AccessGroupId.eq(group_id))
+            .count(&self.db)
+            .await?;
+
+        Ok(count > 0)
+    }
+
+    pub async fn assign(&self, user_id: &str, group_id: &str, assigned_by: Option<&str>) -> Result<users_access_groups::Model, sea_orm::DbErr> {
+        let assignment = users_access_groups::ActiveModel {
+            id: Set(Uuid::new_v4().to_string()),
+            user_id: Set(user_id.to_string()),
+            access_group_id: Set(group_id.to_string()),
+            assigned_at: Set(Utc::now()),
+            assigned_by: Set(assigned_by.map(str::to_string)),
+        }
+        .insert(&self.db)
+        .await?;
+
+        // O acesso do usuário mudou
+        InvalidationBus::shared()
+            .publish(CacheEvent::user(user_id, CacheAction::Updated))
+            .await;
+
+        Ok(assignment)
+    }
+
+    pub async fn unassign(&self, user_id: &str, group_id: &str) -> Result<bool, sea_orm::DbErr> {
+        let result = users_access_groups::Entity::delete_many()
+            .filter(users_access_groups::Column::UserId.eq(user_id))
+            .filter(users_access_groups::Column::AccessGroupId.eq(group_id))
+            .exec(&self.db)
+            .await?;
+
+        let removed = result.rows_affected > 0;
+
+        if removed {
+            InvalidationBus::shared()
+                .publish(CacheEvent::user(user_id, CacheAction::Updated))
+                .await;
+        }
+
+        Ok(removed)
+    }
+}
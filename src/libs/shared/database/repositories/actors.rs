@@ -0,0 +1,163 @@
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, JoinType, QueryFilter, QueryOrder, QuerySelect, RelationTrait, Set};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::{NaiveDate, Utc};
+use crate::libs::shared::database::entities::{actors, video_actors, videos};
+
+pub type Actor = actors::Model;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateActorRequest {
+    pub name: String,
+    pub biography: Option<String>,
+    pub birth_date: Option<NaiveDate>,
+    pub profile_picture_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateActorRequest {
+    pub name: Option<String>,
+    pub biography: Option<String>,
+    pub birth_date: Option<NaiveDate>,
+    pub profile_picture_url: Option<String>,
+}
+
+// Ator no elenco de um vídeo
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CastMember {
+    pub actor: Actor,
+    pub role_name: Option<String>,
+    pub is_lead: bool,
+}
+
+pub struct ActorsRepository {
+    db: DatabaseConnection,
+}
+
+impl ActorsRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    pub async fn create(&self, request: CreateActorRequest) -> Result<Actor, sea_orm::DbErr> {
+        actors::ActiveModel {
+            id: Set(Uuid::new_v4().to_string()),
+            name: Set(request.name),
+            biography: Set(request.biography),
+            birth_date: Set(request.birth_date),
+            profile_picture_url: Set(request.profile_picture_url),
+            created_at: Set(Utc::now()),
+        }
+        .insert(&self.db)
+        .await
+    }
+
+    pub async fn find_by_id(&self, actor_id: &str) -> Result<Option<Actor>, sea_orm::DbErr> {
+        actors::Entity::find_by_id(actor_id).one(&self.db).await
+    }
+
+    pub async fn find_all(&self, limit: Option<u64>, offset: Option<u64>) -> Result<Vec<Actor>, sea_orm::DbErr> {
+        actors::Entity::find()
+            .order_by_asc(actors::Column::Name)
+            .limit(limit.unwrap_or(100))
+            .offset(offset.unwrap_or(0))
+            .all(&self.db)
+            .await
+    }
+
+    pub async fn find_by_name(&self, name: &str, limit: Option<u64>) -> Result<Vec<Actor>, sea_orm::DbErr> {
+        actors::Entity::find()
+            .filter(actors::Column::Name.contains(name))
+            .order_by_asc(actors::Column::Name)
+            .limit(limit.unwrap_or(20))
+            .all(&self.db)
+            .await
+    }
+
+    pub async fn update(&self, actor_id: &str, request: UpdateActorRequest) -> Result<Option<Actor>, sea_orm::DbErr> {
+        let actor = match self.find_by_id(actor_id).await? {
+            Some(actor) => actor,
+            None => return Ok(None),
+        };
+
+        let mut active: actors::ActiveModel = actor.into();
+
+        if let Some(name) = request.name {
+            active.name = Set(name);
+        }
+
+        if let Some(biography) = request.biography {
+            active.biography = Set(Some(biography));
+        }
+
+        if let Some(birth_date) = request.birth_date {
+            active.birth_date = Set(Some(birth_date));
+        }
+
+        if let Some(profile_picture_url) = request.profile_picture_url {
+            active.profile_picture_url = Set(Some(profile_picture_url));
+        }
+
+        active.update(&self.db).await.map(Some)
+    }
+
+    pub async fn delete(&self, actor_id: &str) -> Result<bool, sea_orm::DbErr> {
+        let result = actors::Entity::delete_by_id(actor_id).exec(&self.db).await?;
+        Ok(result.rows_affected > 0)
+    }
+
+    // Elenco de um vídeo, protagonistas primeiro
+    pub async fn find_cast(&self, video_id: &str) -> Result<Vec<CastMember>, sea_orm::DbErr> {
+        let rows = video_actors::Entity::find()
+            .filter(video_actors::Column::VideoId.eq(video_id))
+            .order_by_desc(video_actors::Column::IsLead)
+            .find_also_related(actors::Entity)
+            .all(&self.db)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(link, actor)| {
+                actor.map(|actor| CastMember {
+                    actor,
+                    role_name: link.role_name,
+                    is_lead: link.is_lead,
+                })
+            })
+            .collect())
+    }
+
+    // Vídeos disponíveis em que o ator participa
+    pub async fn find_videos(&self, actor_id: &str) -> Result<Vec<videos::Model>, sea_orm::DbErr> {
+        videos::Entity::find()
+            .join(JoinType::InnerJoin, videos::Relation::VideoActors.def())
+            .filter(video_actors::Column::ActorId.eq(actor_id))
+            .filter(videos::Column::IsAvailable.eq(true))
+            .order_by_desc(videos::Column::ReleaseYear)
+            .all(&self.db)
+            .await
+    }
+
+    pub async fn attach_video(&self, video_id: &str, actor_id: &str, role_name: Option<String>, is_lead: bool) -> Result<video_actors::Model, sea_orm::DbErr> {
+        video_actors::ActiveModel {
+            id: Set(Uuid::new_v4().to_string()),
+            video_id: Set(video_id.to_string()),
+            actor_id: Set(actor_id.to_string()),
+            role_name: Set(role_name),
+            is_lead: Set(is_lead),
+            created_at: Set(Utc::now()),
+        }
+        .insert(&self.db)
+        .await
+    }
+
+    pub async fn detach_video(&self, video_id: &str, actor_id: &str) -> Result<bool, sea_orm::DbErr> {
+        let result = video_actors::Entity::delete_many()
+            .filter(video_actors::Column::VideoId.eq(video_id))
+            .filter(video_actors::Column::ActorId.eq(actor_id))
+            .exec(&self.db)
+            .await?;
+
+        Ok(result.rows_affected > 0)
+    }
+}
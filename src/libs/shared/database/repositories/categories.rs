@@ -0,0 +1,126 @@
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, JoinType, QueryFilter, QueryOrder, QuerySelect, RelationTrait, Set};
+use serde::Deserialize;
+use uuid::Uuid;
+use chrono::Utc;
+use crate::libs::shared::database::entities::{categories, video_categories, videos};
+
+pub type Category = categories::Model;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateCategoryRequest {
+    pub name: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateCategoryRequest {
+    pub name: Option<String>,
+    pub description: Option<String>,
+}
+
+pub struct CategoriesRepository {
+    db: DatabaseConnection,
+}
+
+impl CategoriesRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    pub async fn create(&self, request: CreateCategoryRequest) -> Result<Category, sea_orm::DbErr> {
+        categories::ActiveModel {
+            id: Set(Uuid::new_v4().to_string()),
+            name: Set(request.name),
+            description: Set(request.description),
+            created_at: Set(Utc::now()),
+        }
+        .insert(&self.db)
+        .await
+    }
+
+    pub async fn find_by_id(&self, category_id: &str) -> Result<Option<Category>, sea_orm::DbErr> {
+        categories::Entity::find_by_id(category_id).one(&self.db).await
+    }
+
+    pub async fn find_by_name(&self, name: &str) -> Result<Option<Category>, sea_orm::DbErr> {
+        categories::Entity::find()
+            .filter(categories::Column::Name.eq(name))
+            .one(&self.db)
+            .await
+    }
+
+    pub async fn find_all(&self) -> Result<Vec<Category>, sea_orm::DbErr> {
+        categories::Entity::find()
+            .order_by_asc(categories::Column::Name)
+            .all(&self.db)
+            .await
+    }
+
+    pub async fn update(&self, category_id: &str, request: UpdateCategoryRequest) -> Result<Option<Category>, sea_orm::DbErr> {
+        let category = match self.find_by_id(category_id).await? {
+            Some(category) => category,
+            None => return Ok(None),
+        };
+
+        let mut active: categories::ActiveModel = category.into();
+
+        if let Some(name) = request.name {
+            active.name = Set(name);
+        }
+
+        if let Some(description) = request.description {
+            active.description = Set(Some(description));
+        }
+
+        active.update(&self.db).await.map(Some)
+    }
+
+    pub async fn delete(&self, category_id: &str) -> Result<bool, sea_orm::DbErr> {
+        let result = categories::Entity::delete_by_id(category_id).exec(&self.db).await?;
+        Ok(result.rows_affected > 0)
+    }
+
+    // Categorias de um vídeo
+    pub async fn find_by_video(&self, video_id: &str) -> Result<Vec<Category>, sea_orm::DbErr> {
+        categories::Entity::find()
+            .join(JoinType::InnerJoin, categories::Relation::VideoCategories.def())
+            .filter(video_categories::Column::VideoId.eq(video_id))
+            .order_by_asc(categories::Column::Name)
+            .all(&self.db)
+            .await
+    }
+
+    // Vídeos disponíveis de uma categoria
+    pub async fn find_videos(&self, category_id: &str, limit: Option<u64>, offset: Option<u64>) -> Result<Vec<videos::Model>, sea_orm::DbErr> {
+        videos::Entity::find()
+            .join(JoinType::InnerJoin, videos::Relation::VideoCategories.def())
+            .filter(video_categories::Column::CategoryId.eq(category_id))
+            .filter(videos::Column::IsAvailable.eq(true))
+            .order_by_desc(videos::Column::CreatedAt)
+            .limit(limit.unwrap_or(100))
+            .offset(offset.unwrap_or(0))
+            .all(&self.db)
+            .await
+    }
+
+    pub async fn attach_video(&self, video_id: &str, category_id: &str) -> Result<video_categories::Model, sea_orm::DbErr> {
+        video_categories::ActiveModel {
+            id: Set(Uuid::new_v4().to_string()),
+            video_id: Set(video_id.to_string()),
+            category_id: Set(category_id.to_string()),
+            created_at: Set(Utc::now()),
+        }
+        .insert(&self.db)
+        .await
+    }
+
+    pub async fn detach_video(&self, video_id: &str, category_id: &str) -> Result<bool, sea_orm::DbErr> {
+        let result = video_categories::Entity::delete_many()
+            .filter(video_categories::Column::VideoId.eq(video_id))
+            .filter(video_categories::Column::CategoryId.eq(category_id))
+            .exec(&self.db)
+            .await?;
+
+        Ok(result.rows_affected > 0)
+    }
+}
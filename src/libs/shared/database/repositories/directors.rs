@@ -0,0 +1,142 @@
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, JoinType, QueryFilter, QueryOrder, QuerySelect, RelationTrait, Set};
+use serde::Deserialize;
+use uuid::Uuid;
+use chrono::{NaiveDate, Utc};
+use crate::libs::shared::database::entities::{directors, video_directors, videos};
+
+pub type Director = directors::Model;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateDirectorRequest {
+    pub name: String,
+    pub biography: Option<String>,
+    pub birth_date: Option<NaiveDate>,
+    pub profile_picture_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateDirectorRequest {
+    pub name: Option<String>,
+    pub biography: Option<String>,
+    pub birth_date: Option<NaiveDate>,
+    pub profile_picture_url: Option<String>,
+}
+
+pub struct DirectorsRepository {
+    db: DatabaseConnection,
+}
+
+impl DirectorsRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    pub async fn create(&self, request: CreateDirectorRequest) -> Result<Director, sea_orm::DbErr> {
+        directors::ActiveModel {
+            id: Set(Uuid::new_v4().to_string()),
+            name: Set(request.name),
+            biography: Set(request.biography),
+            birth_date: Set(request.birth_date),
+            profile_picture_url: Set(request.profile_picture_url),
+            created_at: Set(Utc::now()),
+        }
+        .insert(&self.db)
+        .await
+    }
+
+    pub async fn find_by_id(&self, director_id: &str) -> Result<Option<Director>, sea_orm::DbErr> {
+        directors::Entity::find_by_id(director_id).one(&self.db).await
+    }
+
+    pub async fn find_all(&self, limit: Option<u64>, offset: Option<u64>) -> Result<Vec<Director>, sea_orm::DbErr> {
+        directors::Entity::find()
+            .order_by_asc(directors::Column::Name)
+            .limit(limit.unwrap_or(100))
+            .offset(offset.unwrap_or(0))
+            .all(&self.db)
+            .await
+    }
+
+    pub async fn find_by_name(&self, name: &str, limit: Option<u64>) -> Result<Vec<Director>, sea_orm::DbErr> {
+        directors::Entity::find()
+            .filter(directors::Column::Name.contains(name))
+            .order_by_asc(directors::Column::Name)
+            .limit(limit.unwrap_or(20))
+            .all(&self.db)
+            .await
+    }
+
+    pub async fn update(&self, director_id: &str, request: UpdateDirectorRequest) -> Result<Option<Director>, sea_orm::DbErr> {
+        let director = match self.find_by_id(director_id).await? {
+            Some(director) => director,
+            None => return Ok(None),
+        };
+
+        let mut active: directors::ActiveModel = director.into();
+
+        if let Some(name) = request.name {
+            active.name = Set(name);
+        }
+
+        if let Some(biography) = request.biography {
+            active.biography = Set(Some(biography));
+        }
+
+        if let Some(birth_date) = request.birth_date {
+            active.birth_date = Set(Some(birth_date));
+        }
+
+        if let Some(profile_picture_url) = request.profile_picture_url {
+            active.profile_picture_url = Set(Some(profile_picture_url));
+        }
+
+        active.update(&self.db).await.map(Some)
+    }
+
+    pub async fn delete(&self, director_id: &str) -> Result<bool, sea_orm::DbErr> {
+        let result = directors::Entity::delete_by_id(director_id).exec(&self.db).await?;
+        Ok(result.rows_affected > 0)
+    }
+
+    // Diretores de um vídeo
+    pub async fn find_by_video(&self, video_id: &str) -> Result<Vec<Director>, sea_orm::DbErr> {
+        directors::Entity::find()
+            .join(JoinType::InnerJoin, directors::Relation::VideoDirectors.def())
+            .filter(video_directors::Column::VideoId.eq(video_id))
+            .order_by_asc(directors::Column::Name)
+            .all(&self.db)
+            .await
+    }
+
+    // Vídeos disponíveis dirigidos pelo diretor
+    pub async fn find_videos(&self, director_id: &str) -> Result<Vec<videos::Model>, sea_orm::DbErr> {
+        videos::Entity::find()
+            .join(JoinType::InnerJoin, videos::Relation::VideoDirectors.def())
+            .filter(video_directors::Column::DirectorId.eq(director_id))
+            .filter(videos::Column::IsAvailable.eq(true))
+            .order_by_desc(videos::Column::ReleaseYear)
+            .all(&self.db)
+            .await
+    }
+
+    pub async fn attach_video(&self, video_id: &str, director_id: &str) -> Result<video_directors::Model, sea_orm::DbErr> {
+        video_directors::ActiveModel {
+            id: Set(Uuid::new_v4().to_string()),
+            video_id: Set(video_id.to_string()),
+            director_id: Set(director_id.to_string()),
+            created_at: Set(Utc::now()),
+        }
+        .insert(&self.db)
+        .await
+    }
+
+    pub async fn detach_video(&self, video_id: &str, director_id: &str) -> Result<bool, sea_orm::DbErr> {
+        let result = video_directors::Entity::delete_many()
+            .filter(video_directors::Column::VideoId.eq(video_id))
+            .filter(video_directors::Column::DirectorId.eq(director_id))
+            .exec(&self.db)
+            .await?;
+
+        Ok(result.rows_affected > 0)
+    }
+}
@@ -0,0 +1,73 @@
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, JoinType, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, RelationTrait, Set};
+use uuid::Uuid;
+use chrono::Utc;
+use crate::libs::shared::database::entities::{favorites, videos};
+
+pub type Favorite = favorites::Model;
+
+pub struct FavoritesRepository {
+    db: DatabaseConnection,
+}
+
+impl FavoritesRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    pub async fn find(&self, user_id: &str, video_id: &str) -> Result<Option<Favorite>, sea_orm::DbErr> {
+        favorites::Entity::find()
+            .filter(favorites::Column::UserId.eq(user_id))
+            .filter(favorites::Column::VideoId.eq(video_id))
+            .one(&self.db)
+            .await
+    }
+
+    // Idempotente: favoritar de novo devolve o registro existente
+    pub async fn add(&self, user_id: &str, video_id: &str) -> Result<Favorite, sea_orm::DbErr> {
+        if let Some(favorite) = self.find(user_id, video_id).await? {
+            return Ok(favorite);
+        }
+
+        favorites::ActiveModel {
+            id: Set(Uuid::new_v4().to_string()),
+            user_id: Set(user_id.to_string()),
+            video_id: Set(video_id.to_string()),
+            added_at: Set(Utc::now()),
+        }
+        .insert(&self.db)
+        .await
+    }
+
+    pub async fn remove(&self, user_id: &str, video_id: &str) -> Result<bool, sea_orm::DbErr> {
+        let result = favorites::Entity::delete_many()
+            .filter(favorites::Column::UserId.eq(user_id))
+            .filter(favorites::Column::VideoId.eq(video_id))
+            .exec(&self.db)
+            .await?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    pub async fn is_favorite(&self, user_id: &str, video_id: &str) -> Result<bool, sea_orm::DbErr> {
+        Ok(self.find(user_id, video_id).await?.is_some())
+    }
+
+    // Vídeos favoritos do usuário, mais recentes primeiro
+    pub async fn find_videos(&self, user_id: &str, limit: Option<u64>, offset: Option<u64>) -> Result<Vec<videos::Model>, sea_orm::DbErr> {
+        videos::Entity::find()
+            .join(JoinType::InnerJoin, videos::Relation::Favorites.def())
+            .filter(favorites::Column::UserId.eq(user_id))
+            .order_by_desc(favorites::Column::AddedAt)
+            .limit(limit.unwrap_or(50))
+            .offset(offset.unwrap_or(0))
+            .all(&self.db)
+            .await
+    }
+
+    pub async fn count_for_video(&self, video_id: &str) -> Result<u64, sea_orm::DbErr> {
+        favorites::Entity::find()
+            .filter(favorites::Column::VideoId.eq(video_id))
+            .count(&self.db)
+            .await
+    }
+}
@@ -7,3 +7,5 @@ pub mod access_groups;
 pub mod watch_history;
 pub mod favorites;
 pub mod ratings;
+pub mod user_tokens;
+pub mod user_mfa;
@@ -0,0 +1,103 @@
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, FromQueryResult, QueryFilter, QueryOrder, QuerySelect, Set};
+use sea_orm::sea_query::{Alias, Expr, Func, SimpleExpr};
+use serde::Serialize;
+use uuid::Uuid;
+use chrono::Utc;
+use crate::libs::shared::database::entities::ratings;
+
+pub type Rating = ratings::Model;
+
+#[derive(Debug, Clone, Serialize, FromQueryResult)]
+pub struct RatingSummary {
+    pub average: Option<f64>,
+    pub total: i64,
+}
+
+pub struct RatingsRepository {
+    db: DatabaseConnection,
+}
+
+impl RatingsRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    pub async fn find(&self, user_id: &str, video_id: &str) -> Result<Option<Rating>, sea_orm::DbErr> {
+        ratings::Entity::find()
+            .filter(ratings::Column::UserId.eq(user_id))
+            .filter(ratings::Column::VideoId.eq(video_id))
+            .one(&self.db)
+            .await
+    }
+
+    // Cria ou substitui a avaliação do usuário para o vídeo
+    pub async fn rate(&self, user_id: &str, video_id: &str, rating: i32, comment: Option<String>) -> Result<Rating, sea_orm::DbErr> {
+        let now = Utc::now();
+
+        match self.find(user_id, video_id).await? {
+            Some(existing) => {
+                let mut active: ratings::ActiveModel = existing.into();
+                active.rating = Set(rating);
+                active.comment = Set(comment);
+                active.updated_at = Set(now);
+                active.update(&self.db).await
+            }
+            None => {
+                ratings::ActiveModel {
+                    id: Set(Uuid::new_v4().to_string()),
+                    user_id: Set(user_id.to_string()),
+                    video_id: Set(video_id.to_string()),
+                    rating: Set(rating),
+                    comment: Set(comment),
+                    created_at: Set(now),
+                    updated_at: Set(now),
+                }
+                .insert(&self.db)
+                .await
+            }
+        }
+    }
+
+    pub async fn find_by_video(&self, video_id: &str, limit: Option<u64>, offset: Option<u64>) -> Result<Vec<Rating>, sea_orm::DbErr> {
+        ratings::Entity::find()
+            .filter(ratings::Column::VideoId.eq(video_id))
+            .order_by_desc(ratings::Column::UpdatedAt)
+            .limit(limit.unwrap_or(50))
+            .offset(offset.unwrap_or(0))
+            .all(&self.db)
+            .await
+    }
+
+    pub async fn find_by_user(&self, user_id: &str) -> Result<Vec<Rating>, sea_orm::DbErr> {
+        ratings::Entity::find()
+            .filter(ratings::Column::UserId.eq(user_id))
+            .order_by_desc(ratings::Column::UpdatedAt)
+            .all(&self.db)
+            .await
+    }
+
+    // Média e quantidade de avaliações de um vídeo
+    pub async fn summary_for_video(&self, video_id: &str) -> Result<RatingSummary, sea_orm::DbErr> {
+        let summary = ratings::Entity::find()
+            .select_only()
+            // CAST evita a média inteira de colunas INT
+            .column_as(SimpleExpr::from(Func::avg(Expr::col(ratings::Column::Rating).cast_as(Alias::new("FLOAT")))), "average")
+            .column_as(ratings::Column::Id.count(), "total")
+            .filter(ratings::Column::VideoId.eq(video_id))
+            .into_model::<RatingSummary>()
+            .one(&self.db)
+            .await?;
+
+        Ok(summary.unwrap_or(RatingSummary { average: None, total: 0 }))
+    }
+
+    pub async fn delete(&self, user_id: &str, video_id: &str) -> Result<bool, sea_orm::DbErr> {
+        let result = ratings::Entity::delete_many()
+            .filter(ratings::Column::UserId.eq(user_id))
+            .filter(ratings::Column::VideoId.eq(video_id))
+            .exec(&self.db)
+            .await?;
+
+        Ok(result.rows_affected > 0)
+    }
+}
@@ -0,0 +1,192 @@
+use sea_orm::{ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter, Set, TransactionTrait};
+use sea_orm::sea_query::Expr;
+use uuid::Uuid;
+use chrono::Utc;
+use crate::libs::shared::database::entities::{user_mfa, user_recovery_codes};
+
+pub type UserMfa = user_mfa::Model;
+
+// Segredo TOTP e códigos de recuperação de cada usuário
+pub struct UserMfaRepository {
+    db: DatabaseConnection,
+}
+
+impl UserMfaRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    pub async fn find(&self, user_id: &str) -> Result<Option<UserMfa>, sea_orm::DbErr> {
+        user_mfa::Entity::find_by_id(user_id.to_string()).one(&self.db).await
+    }
+
+    // Grava um segredo novo, ainda não habilitado. Um cadastro pendente
+    // anterior é substituído.
+    pub async fn save_pending(&self, user_id: &str, totp_secret: &str) -> Result<UserMfa, sea_orm::DbErr> {
+        let now = Utc::now();
+
+        match self.find(user_id).await? {
+            Some(existing) => {
+                let mut active: user_mfa::ActiveModel = existing.into();
+                active.totp_secret = Set(totp_secret.to_string());
+                active.enabled_at = Set(None);
+                active.last_used_step = Set(None);
+                active.failed_attempts = Set(0);
+                active.updated_at = Set(now);
+                active.update(&self.db).await
+            }
+            None => {
+                user_mfa::ActiveModel {
+                    user_id: Set(user_id.to_string()),
+                    totp_secret: Set(totp_secret.to_string()),
+                    enabled_at: Set(None),
+                    last_used_step: Set(None),
+                    failed_attempts: Set(0),
+                    created_at: Set(now),
+                    updated_at: Set(now),
+                }
+                .insert(&self.db)
+                .await
+            }
+        }
+    }
+
+    // Habilita o cadastro pendente e troca os códigos de recuperação numa
+    // mesma transação. Falso se o 2FA já estava habilitado.
+    pub async fn enable(&self, user_id: &str, recovery_code_hashes: &[String]) -> Result<bool, sea_orm::DbErr> {
+        let txn = self.db.begin().await?;
+        let now = Utc::now();
+
+        let result = user_mfa::Entity::update_many()
+            .col_expr(user_mfa::Column::EnabledAt, Expr::value(now))
+            .col_expr(user_mfa::Column::UpdatedAt, Expr::value(now))
+            .filter(user_mfa::Column::UserId.eq(user_id))
+            .filter(user_mfa::Column::EnabledAt.is_null())
+            .exec(&txn)
+            .await?;
+
+        if result.rows_affected == 0 {
+            txn.rollback().await?;
+            return Ok(false);
+        }
+
+        Self::insert_recovery_codes(&txn, user_id, recovery_code_hashes).await?;
+        txn.commit().await?;
+
+        Ok(true)
+    }
+
+    // Aceita o passo TOTP só se for posterior ao último usado: um código já
+    // aceito não entra de novo, nem em requisições concorrentes
+    pub async fn record_step(&self, user_id: &str, step: i64) -> Result<bool, sea_orm::DbErr> {
+        let result = user_mfa::Entity::update_many()
+            .col_expr(user_mfa::Column::LastUsedStep, Expr::value(step))
+            .col_expr(user_mfa::Column::FailedAttempts, Expr::value(0))
+            .col_expr(user_mfa::Column::UpdatedAt, Expr::value(Utc::now()))
+            .filter(user_mfa::Column::UserId.eq(user_id))
+            .filter(
+                Condition::any()
+                    .add(user_mfa::Column::LastUsedStep.is_null())
+                    .add(user_mfa::Column::LastUsedStep.lt(step)),
+            )
+            .exec(&self.db)
+            .await?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    // Soma uma falha e devolve o total desde o último acerto
+    pub async fn register_failure(&self, user_id: &str) -> Result<i32, sea_orm::DbErr> {
+        user_mfa::Entity::update_many()
+            .col_expr(
+                user_mfa::Column::FailedAttempts,
+                Expr::col(user_mfa::Column::FailedAttempts).add(1),
+            )
+            .filter(user_mfa::Column::UserId.eq(user_id))
+            .exec(&self.db)
+            .await?;
+
+        Ok(self.find(user_id).await?.map(|mfa| mfa.failed_attempts).unwrap_or(0))
+    }
+
+    pub async fn reset_failures(&self, user_id: &str) -> Result<(), sea_orm::DbErr> {
+        user_mfa::Entity::update_many()
+            .col_expr(user_mfa::Column::FailedAttempts, Expr::value(0))
+            .filter(user_mfa::Column::UserId.eq(user_id))
+            .exec(&self.db)
+            .await?;
+
+        Ok(())
+    }
+
+    // Remove o segredo e os códigos de recuperação
+    pub async fn delete(&self, user_id: &str) -> Result<bool, sea_orm::DbErr> {
+        let txn = self.db.begin().await?;
+
+        user_recovery_codes::Entity::delete_many()
+            .filter(user_recovery_codes::Column::UserId.eq(user_id))
+            .exec(&txn)
+            .await?;
+
+        let result = user_mfa::Entity::delete_by_id(user_id.to_string()).exec(&txn).await?;
+        txn.commit().await?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    // Os códigos anteriores deixam de valer, usados ou não
+    pub async fn replace_recovery_codes(&self, user_id: &str, code_hashes: &[String]) -> Result<(), sea_orm::DbErr> {
+        let txn = self.db.begin().await?;
+
+        user_recovery_codes::Entity::delete_many()
+            .filter(user_recovery_codes::Column::UserId.eq(user_id))
+            .exec(&txn)
+            .await?;
+
+        Self::insert_recovery_codes(&txn, user_id, code_hashes).await?;
+        txn.commit().await?;
+
+        Ok(())
+    }
+
+    // UPDATE condicional, como em user_tokens: só uma requisição consome
+    // cada código
+    pub async fn consume_recovery_code(&self, user_id: &str, code_hash: &str) -> Result<bool, sea_orm::DbErr> {
+        let result = user_recovery_codes::Entity::update_many()
+            .col_expr(user_recovery_codes::Column::UsedAt, Expr::value(Utc::now()))
+            .filter(user_recovery_codes::Column::UserId.eq(user_id))
+            .filter(user_recovery_codes::Column::CodeHash.eq(code_hash))
+            .filter(user_recovery_codes::Column::UsedAt.is_null())
+            .exec(&self.db)
+            .await?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    pub async fn count_unused_recovery_codes(&self, user_id: &str) -> Result<u64, sea_orm::DbErr> {
+        user_recovery_codes::Entity::find()
+            .filter(user_recovery_codes::Column::UserId.eq(user_id))
+            .filter(user_recovery_codes::Column::UsedAt.is_null())
+            .count(&self.db)
+            .await
+    }
+
+    async fn insert_recovery_codes<C: sea_orm::ConnectionTrait>(conn: &C, user_id: &str, code_hashes: &[String]) -> Result<(), sea_orm::DbErr> {
+        if code_hashes.is_empty() {
+            return Ok(());
+        }
+
+        let now = Utc::now();
+        let models = code_hashes.iter().map(|hash| user_recovery_codes::ActiveModel {
+            id: Set(Uuid::new_v4().to_string()),
+            user_id: Set(user_id.to_string()),
+            code_hash: Set(hash.clone()),
+            used_at: Set(None),
+            created_at: Set(now),
+        });
+
+        user_recovery_codes::Entity::insert_many(models).exec(conn).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set};
+use sea_orm::sea_query::Expr;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use crate::libs::shared::database::entities::user_tokens;
+
+pub type UserToken = user_tokens::Model;
+
+pub struct UserTokensRepository {
+    db: DatabaseConnection,
+}
+
+impl UserTokensRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    pub async fn create(&self, user_id: &str, purpose: &str, token_hash: &str, expires_at: DateTime<Utc>) -> Result<UserToken, sea_orm::DbErr> {
+        user_tokens::ActiveModel {
+            id: Set(Uuid::new_v4().to_string()),
+            user_id: Set(user_id.to_string()),
+            purpose: Set(purpose.to_string()),
+            token_hash: Set(token_hash.to_string()),
+            expires_at: Set(expires_at),
+            used_at: Set(None),
+            created_at: Set(Utc::now()),
+        }
+        .insert(&self.db)
+        .await
+    }
+
+    pub async fn find_by_hash(&self, token_hash: &str) -> Result<Option<UserToken>, sea_orm::DbErr> {
+        user_tokens::Entity::find()
+            .filter(user_tokens::Column::TokenHash.eq(token_hash))
+            .one(&self.db)
+            .await
+    }
+
+    // Tokens emitidos para o usuário desde `since`, do mais antigo ao mais
+    // recente; base do limite de reenvio
+    pub async fn find_created_since(&self, user_id: &str, purpose: &str, since: DateTime<Utc>) -> Result<Vec<UserToken>, sea_orm::DbErr> {
+        user_tokens::Entity::find()
+            .filter(user_tokens::Column::UserId.eq(user_id))
+            .filter(user_tokens::Column::Purpose.eq(purpose))
+            .filter(user_tokens::Column::CreatedAt.gte(since))
+            .order_by_asc(user_tokens::Column::CreatedAt)
+            .all(&self.db)
+            .await
+    }
+
+    // Marca o token como usado se ainda estiver valendo. É um único UPDATE
+    // condicional: entre requisições concorrentes com o mesmo token, só uma
+    // recebe o registro.
+    pub async fn consume(&self, purpose: &str, token_hash: &str) -> Result<Option<UserToken>, sea_orm::DbErr> {
+        let now = Utc::now();
+
+        let result = user_tokens::Entity::update_many()
+            .col_expr(user_tokens::Column::UsedAt, Expr::value(now))
+            .filter(user_tokens::Column::TokenHash.eq(token_hash))
+            .filter(user_tokens::Column::Purpose.eq(purpose))
+            .filter(user_tokens::Column::UsedAt.is_null())
+            .filter(user_tokens::Column::ExpiresAt.gt(now))
+            .exec(&self.db)
+            .await?;
+
+        if result.rows_affected == 0 {
+            return Ok(None);
+        }
+
+        self.find_by_hash(token_hash).await
+    }
+
+    // Invalida os tokens ainda não usados do usuário para a finalidade
+    pub async fn revoke_for_user(&self, user_id: &str, purpose: &str) -> Result<u64, sea_orm::DbErr> {
+        let result = user_tokens::Entity::update_many()
+            .col_expr(user_tokens::Column::UsedAt, Expr::value(Utc::now()))
+            .filter(user_tokens::Column::UserId.eq(user_id))
+            .filter(user_tokens::Column::Purpose.eq(purpose))
+            .filter(user_tokens::Column::UsedAt.is_null())
+            .exec(&self.db)
+            .await?;
+
+        Ok(result.rows_affected)
+    }
+}
@@ -1,20 +1,44 @@
-use sea_orm::{DatabaseConnection, Statement, FromQueryResult, EntityTrait, QueryFilter, ColumnTrait, Set, ActiveModelTrait};
+use sea_orm::{ColumnTrait, ConnectionTrait, DatabaseBackend, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, QueryTrait, Select, Set};
+use sea_orm::sea_query::Expr;
 use serde::{Deserialize, Serialize};
-use bcrypt::{hash, verify, DEFAULT_COST};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
+use crate::libs::shared::cache::invalidation_bus::{CacheAction, CacheEvent, InvalidationBus};
+use crate::libs::shared::database::entities::users;
+use crate::libs::shared::database::row_mapping::{query_all_as, query_one_as};
+use crate::libs::shared::modules::auth::passwords::PasswordHasher;
+use crate::impl_from_row;
 
-#[derive(Debug, Clone, Serialize, Deserialize, FromQueryResult)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct User {
     pub id: String,
     pub email: String,
     pub name: String,
     pub role: String,
     pub password_hash: String,
+    pub email_verified_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+impl User {
+    pub fn is_email_verified(&self) -> bool {
+        self.email_verified_at.is_some()
+    }
+}
+
+impl_from_row!(User {
+    id,
+    email,
+    name,
+    role,
+    password_hash,
+    email_verified_at,
+} or {
+    created_at = Utc::now(),
+    updated_at = Utc::now(),
+});
+
 #[derive(Debug, Deserialize)]
 pub struct LoginRequest {
     pub email: String,
@@ -27,6 +51,9 @@ pub struct CreateUserRequest {
     pub password: String,
     pub name: String,
     pub role: Option<String>,
+    // Contas criadas por um admin já nascem confirmadas; as do registro
+    // público dependem do link enviado por e-mail
+    pub email_verified: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -45,300 +72,259 @@ impl UsersRepository {
         Self { db }
     }
 
+    // Dialeto da conexão; o SQL é gerado pelo query builder para ele
+    fn backend(&self) -> DatabaseBackend {
+        self.db.get_database_backend()
+    }
+
+    // Apenas as colunas de `User`
+    fn select() -> Select<users::Entity> {
+        users::Entity::find()
+            .select_only()
+            .columns([
+                users::Column::Id,
+                users::Column::Email,
+                users::Column::Name,
+                users::Column::Role,
+                users::Column::PasswordHash,
+                users::Column::EmailVerifiedAt,
+                users::Column::CreatedAt,
+                users::Column::UpdatedAt,
+            ])
+    }
+
     pub async fn create(&self, request: CreateUserRequest) -> Result<User, sea_orm::DbErr> {
         let user_id = Uuid::new_v4().to_string();
         let now = Utc::now();
         let role = request.role.unwrap_or_else(|| "Viewer".to_string());
         
-        // Hash da senha
-        let password_hash = hash(request.password.as_bytes(), DEFAULT_COST)
-            .map_err(|e| sea_orm::DbErr::Custom(format!("Erro ao hash da senha: {}", e)))?;
-
-        let sql = r#"
-            INSERT INTO users (id, email, name, role, password_hash, created_at, updated_at)
-            VALUES (@P1, @P2, @P3, @P4, @P5, @P6, @P7)
-        "#;
-
-        let stmt = Statement::from_sql_and_values(
-            sea_orm::DatabaseBackend::SqlServer,
-            sql,
-            vec![
-                user_id.into(),
-                request.email.into(),
-                request.name.into(),
-                role.into(),
-                password_hash.into(),
-                now.into(),
-                now.into(),
-            ],
-        );
-
-        self.db.execute(stmt).await?;
+        // Hash da senha (Argon2id, fora do executor async)
+        let password_hash = Self::hash_password(&request.password).await?;
+
+        let user = users::ActiveModel {
+            id: Set(user_id.clone()),
+            email: Set(request.email),
+            name: Set(request.name),
+            role: Set(role),
+            password_hash: Set(password_hash),
+            email_verified_at: Set(request.email_verified.then_some(now)),
+            created_at: Set(now),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+
+        users::Entity::insert(user).exec_without_returning(&self.db).await?;
 
         // Buscar usuário criado
-        self.find_by_id(&user_id).await
-            .and_then(|user| user.ok_or(sea_orm::DbErr::Custom("Usuário não encontrado após criação".to_string())))
+        let user = self.find_by_id(&user_id).await
+            .and_then(|user| user.ok_or(sea_orm::DbErr::Custom("Usuário não encontrado após criação".to_string())))?;
+
+        InvalidationBus::shared()
+            .publish(CacheEvent::user(&user.id, CacheAction::Created))
+            .await;
+
+        Ok(user)
     }
 
     pub async fn find_by_id(&self, user_id: &str) -> Result<Option<User>, sea_orm::DbErr> {
-        let sql = r#"
-            SELECT id, email, name, role, password_hash, created_at, updated_at
-            FROM users
-            WHERE id = @P1
-        "#;
-
-        let stmt = Statement::from_sql_and_values(
-            sea_orm::DatabaseBackend::SqlServer,
-            sql,
-            vec![user_id.into()],
-        );
-
-        let result = self.db.query_one(stmt).await?;
-        
-        if let Some(row) = result {
-            let id: String = row.try_get("", "id").unwrap_or_default();
-            let email: String = row.try_get("", "email").unwrap_or_default();
-            let name: String = row.try_get("", "name").unwrap_or_default();
-            let role: String = row.try_get("", "role").unwrap_or_default();
-            let password_hash: String = row.try_get("", "password_hash").unwrap_or_default();
-            let created_at: DateTime<Utc> = row.try_get("", "created_at").unwrap_or_else(|_| Utc::now());
-            let updated_at: DateTime<Utc> = row.try_get("", "updated_at").unwrap_or_else(|_| Utc::now());
-
-            Ok(Some(User {
-                id,
-                email,
-                name,
-                role,
-                password_hash,
-                created_at,
-                updated_at,
-            }))
-        } else {
-            Ok(None)
-        }
+        let stmt = Self::select()
+            .filter(users::Column::Id.eq(user_id))
+            .build(self.backend());
+
+        query_one_as(&self.db, stmt).await
     }
 
     pub async fn find_by_email(&self, email: &str) -> Result<Option<User>, sea_orm::DbErr> {
-        let sql = r#"
-            SELECT id, email, name, role, password_hash, created_at, updated_at
-            FROM users
-            WHERE email = @P1
-        "#;
-
-        let stmt = Statement::from_sql_and_values(
-            sea_orm::DatabaseBackend::SqlServer,
-            sql,
-            vec![email.into()],
-        );
-
-        let result = self.db.query_one(stmt).await?;
-        
-        if let Some(row) = result {
-            let id: String = row.try_get("", "id").unwrap_or_default();
-            let email: String = row.try_get("", "email").unwrap_or_default();
-            let name: String = row.try_get("", "name").unwrap_or_default();
-            let role: String = row.try_get("", "role").unwrap_or_default();
-            let password_hash: String = row.try_get("", "password_hash").unwrap_or_default();
-            let created_at: DateTime<Utc> = row.try_get("", "created_at").unwrap_or_else(|_| Utc::now());
-            let updated_at: DateTime<Utc> = row.try_get("", "updated_at").unwrap_or_else(|_| Utc::now());
-
-            Ok(Some(User {
-                id,
-                email,
-                name,
-                role,
-                password_hash,
-                created_at,
-                updated_at,
-            }))
-        } else {
-            Ok(None)
-        }
+        let stmt = Self::select()
+            .filter(users::Column::Email.eq(email))
+            .build(self.backend());
+
+        query_one_as(&self.db, stmt).await
     }
 
     pub async fn find_all(&self, limit: Option<u64>, offset: Option<u64>) -> Result<Vec<User>, sea_orm::DbErr> {
-        let limit = limit.unwrap_or(100);
-        let offset = offset.unwrap_or(0);
-
-        let sql = r#"
-            SELECT id, email, name, role, password_hash, created_at, updated_at
-            FROM users
-            ORDER BY created_at DESC
-            OFFSET @P1 ROWS
-            FETCH NEXT @P2 ROWS ONLY
-        "#;
-
-        let stmt = Statement::from_sql_and_values(
-            sea_orm::DatabaseBackend::SqlServer,
-            sql,
-            vec![offset.into(), limit.into()],
-        );
-
-        let results = self.db.query_all(stmt).await?;
-        
-        let mut users = Vec::new();
-        for row in results {
-            let id: String = row.try_get("", "id").unwrap_or_default();
-            let email: String = row.try_get("", "email").unwrap_or_default();
-            let name: String = row.try_get("", "name").unwrap_or_default();
-            let role: String = row.try_get("", "role").unwrap_or_default();
-            let password_hash: String = row.try_get("", "password_hash").unwrap_or_default();
-            let created_at: DateTime<Utc> = row.try_get("", "created_at").unwrap_or_else(|_| Utc::now());
-            let updated_at: DateTime<Utc> = row.try_get("", "updated_at").unwrap_or_else(|_| Utc::now());
-
-            users.push(User {
-                id,
-                email,
-                name,
-                role,
-                password_hash,
-                created_at,
-                updated_at,
-            });
-        }
+        let stmt = Self::select()
+            .order_by_desc(users::Column::CreatedAt)
+            .limit(limit.unwrap_or(100))
+            .offset(offset.unwrap_or(0))
+            .build(self.backend());
 
-        Ok(users)
+        query_all_as(&self.db, stmt).await
     }
 
     pub async fn update(&self, user_id: &str, request: UpdateUserRequest) -> Result<Option<User>, sea_orm::DbErr> {
-        let now = Utc::now();
-        let mut updates = Vec::new();
-        let mut params = Vec::new();
-        let mut param_count = 1;
-
-        if let Some(name) = &request.name {
-            updates.push(format!("name = @P{}", param_count));
-            params.push(name.into());
-            param_count += 1;
+        let mut update = users::Entity::update_many();
+
+        if let Some(name) = request.name {
+            update = update.col_expr(users::Column::Name, Expr::value(name));
         }
 
-        if let Some(email) = &request.email {
-            updates.push(format!("email = @P{}", param_count));
-            params.push(email.into());
-            param_count += 1;
+        if let Some(email) = request.email {
+            update = update.col_expr(users::Column::Email, Expr::value(email));
         }
 
-        if let Some(role) = &request.role {
-            updates.push(format!("role = @P{}", param_count));
-            params.push(role.into());
-            param_count += 1;
+        if let Some(role) = request.role {
+            update = update.col_expr(users::Column::Role, Expr::value(role));
         }
 
-        updates.push(format!("updated_at = @P{}", param_count));
-        params.push(now.into());
-        param_count += 1;
+        let result = update
+            .col_expr(users::Column::UpdatedAt, Expr::value(Utc::now()))
+            .filter(users::Column::Id.eq(user_id))
+            .exec(&self.db)
+            .await?;
 
-        if updates.is_empty() {
-            return self.find_by_id(user_id).await;
+        if result.rows_affected > 0 {
+            InvalidationBus::shared()
+                .publish(CacheEvent::user(user_id, CacheAction::Updated))
+                .await;
         }
 
-        let sql = format!(
-            "UPDATE users SET {} WHERE id = @P{}",
-            updates.join(", "),
-            param_count
-        );
+        // Buscar usuário atualizado
+        self.find_by_id(user_id).await
+    }
 
-        params.push(user_id.into());
+    pub async fn delete(&self, user_id: &str) -> Result<bool, sea_orm::DbErr> {
+        let result = users::Entity::delete_by_id(user_id).exec(&self.db).await?;
+        let deleted = result.rows_affected > 0;
 
-        let stmt = Statement::from_sql_and_values(
-            sea_orm::DatabaseBackend::SqlServer,
-            sql,
-            params,
-        );
+        if deleted {
+            InvalidationBus::shared()
+                .publish(CacheEvent::user(user_id, CacheAction::Deleted))
+                .await;
+        }
 
-        self.db.execute(stmt).await?;
+        Ok(deleted)
+    }
 
-        // Buscar usuário atualizado
-        self.find_by_id(user_id).await
+    // Troca a senha; o hash é gerado aqui, como no `create`
+    pub async fn update_password(&self, user_id: &str, new_password: &str) -> Result<bool, sea_orm::DbErr> {
+        let password_hash = Self::hash_password(new_password).await?;
+
+        let result = users::Entity::update_many()
+            .col_expr(users::Column::PasswordHash, Expr::value(password_hash))
+            .col_expr(users::Column::UpdatedAt, Expr::value(Utc::now()))
+            .filter(users::Column::Id.eq(user_id))
+            .exec(&self.db)
+            .await?;
+
+        let updated = result.rows_affected > 0;
+
+        if updated {
+            InvalidationBus::shared()
+                .publish(CacheEvent::user(user_id, CacheAction::Updated))
+                .await;
+        }
+
+        Ok(updated)
     }
 
-    pub async fn delete(&self, user_id: &str) -> Result<bool, sea_orm::DbErr> {
-        let sql = "DELETE FROM users WHERE id = @P1";
+    // Só altera contas ainda não confirmadas; devolve se alterou
+    pub async fn mark_email_verified(&self, user_id: &str) -> Result<bool, sea_orm::DbErr> {
+        let now = Utc::now();
 
-        let stmt = Statement::from_sql_and_values(
-            sea_orm::DatabaseBackend::SqlServer,
-            sql,
-            vec![user_id.into()],
-        );
+        let result = users::Entity::update_many()
+            .col_expr(users::Column::EmailVerifiedAt, Expr::value(now))
+            .col_expr(users::Column::UpdatedAt, Expr::value(now))
+            .filter(users::Column::Id.eq(user_id))
+            .filter(users::Column::EmailVerifiedAt.is_null())
+            .exec(&self.db)
+            .await?;
 
-        let result = self.db.execute(stmt).await?;
-        Ok(result.rows_affected() > 0)
+        let updated = result.rows_affected > 0;
+
+        if updated {
+            InvalidationBus::shared()
+                .publish(CacheEvent::user(user_id, CacheAction::Updated))
+                .await;
+        }
+
+        Ok(updated)
+    }
+
+    async fn hash_password(password: &str) -> Result<String, sea_orm::DbErr> {
+        PasswordHasher::shared()
+            .hash(password)
+            .await
+            .map_err(|e| sea_orm::DbErr::Custom(e.to_string()))
+    }
+
+    pub async fn verify_password(user: &User, password: &str) -> Result<bool, sea_orm::DbErr> {
+        PasswordHasher::shared()
+            .verify(password, &user.password_hash)
+            .await
+            .map(|check| check.valid)
+            .map_err(|e| sea_orm::DbErr::Custom(e.to_string()))
     }
 
     pub async fn authenticate(&self, request: &LoginRequest) -> Result<Option<User>, sea_orm::DbErr> {
+        let hasher = PasswordHasher::shared();
         let user = self.find_by_email(&request.email).await?;
         
-        if let Some(user) = user {
+        if let Some(mut user) = user {
             // Verificar senha
-            let is_valid = verify(request.password.as_bytes(), &user.password_hash)
-                .map_err(|e| sea_orm::DbErr::Custom(format!("Erro ao verificar senha: {}", e)))?;
+            let check = hasher
+                .verify(&request.password, &user.password_hash)
+                .await
+                .map_err(|e| sea_orm::DbErr::Custom(e.to_string()))?;
             
-            if is_valid {
-                Ok(Some(user))
-            } else {
-                Ok(None)
+            if !check.valid {
+                return Ok(None);
             }
+
+            // Hash em bcrypt ou com parâmetros antigos: aproveita a senha em
+            // texto puro para gerar o hash atual. Falhar aqui não impede o login.
+            if check.needs_rehash {
+                match self.rehash_password(&user, &request.password).await {
+                    Ok(Some(password_hash)) => user.password_hash = password_hash,
+                    Ok(None) => {}
+                    Err(e) => tracing::warn!("Erro ao atualizar o hash da senha do usuário {}: {}", user.id, e),
+                }
+            }
+
+            Ok(Some(user))
         } else {
+            // Mesmo custo de uma senha errada: o tempo de resposta não revela
+            // se o e-mail está cadastrado
+            hasher.verify_dummy(&request.password).await;
+
             Ok(None)
         }
     }
 
-    pub async fn find_by_role(&self, role: &str) -> Result<Vec<User>, sea_orm::DbErr> {
-        let sql = r#"
-            SELECT id, email, name, role, password_hash, created_at, updated_at
-            FROM users
-            WHERE role = @P1
-            ORDER BY created_at DESC
-        "#;
-
-        let stmt = Statement::from_sql_and_values(
-            sea_orm::DatabaseBackend::SqlServer,
-            sql,
-            vec![role.into()],
-        );
-
-        let results = self.db.query_all(stmt).await?;
-        
-        let mut users = Vec::new();
-        for row in results {
-            let id: String = row.try_get("", "id").unwrap_or_default();
-            let email: String = row.try_get("", "email").unwrap_or_default();
-            let name: String = row.try_get("", "name").unwrap_or_default();
-            let role: String = row.try_get("", "role").unwrap_or_default();
-            let password_hash: String = row.try_get("", "password_hash").unwrap_or_default();
-            let created_at: DateTime<Utc> = row.try_get("", "created_at").unwrap_or_else(|_| Utc::now());
-            let updated_at: DateTime<Utc> = row.try_get("", "updated_at").unwrap_or_else(|_| Utc::now());
-
-            users.push(User {
-                id,
-                email,
-                name,
-                role,
-                password_hash,
-                created_at,
-                updated_at,
-            });
+    // Só troca se o hash ainda for o conferido no login; uma troca de senha
+    // feita no meio do caminho prevalece
+    async fn rehash_password(&self, user: &User, password: &str) -> Result<Option<String>, sea_orm::DbErr> {
+        let password_hash = Self::hash_password(password).await?;
+
+        let result = users::Entity::update_many()
+            .col_expr(users::Column::PasswordHash, Expr::value(password_hash.clone()))
+            .filter(users::Column::Id.eq(user.id.as_str()))
+            .filter(users::Column::PasswordHash.eq(user.password_hash.as_str()))
+            .exec(&self.db)
+            .await?;
+
+        if result.rows_affected == 0 {
+            return Ok(None);
         }
 
-        Ok(users)
+        tracing::info!("Hash da senha do usuário {} atualizado para Argon2id", user.id);
+        InvalidationBus::shared()
+            .publish(CacheEvent::user(&user.id, CacheAction::Updated))
+            .await;
+
+        Ok(Some(password_hash))
     }
 
-    pub async fn count(&self) -> Result<u64, sea_orm::DbErr> {
-        let sql = "SELECT COUNT(*) as count FROM users";
+    pub async fn find_by_role(&self, role: &str) -> Result<Vec<User>, sea_orm::DbErr> {
+        let stmt = Self::select()
+            .filter(users::Column::Role.eq(role))
+            .order_by_desc(users::Column::CreatedAt)
+            .build(self.backend());
 
-        let stmt = Statement::from_sql_and_values(
-            sea_orm::DatabaseBackend::SqlServer,
-            sql,
-            vec![],
-        );
+        query_all_as(&self.db, stmt).await
+    }
 
-        let result = self.db.query_one(stmt).await?;
-        
-        if let Some(row) = result {
-            let count: i64 = row.try_get("", "count").unwrap_or(0);
-            Ok(count as u64)
-        } else {
-            Ok(0)
-        }
+    pub async fn count(&self) -> Result<u64, sea_orm::DbErr> {
+        users::Entity::find().count(&self.db).await
     }
 }
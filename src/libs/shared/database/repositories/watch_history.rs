@@ -0,0 +1,99 @@
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set};
+use uuid::Uuid;
+use chrono::Utc;
+use crate::libs::shared::database::entities::{videos, watch_history};
+
+pub type WatchEntry = watch_history::Model;
+
+pub struct WatchHistoryRepository {
+    db: DatabaseConnection,
+}
+
+impl WatchHistoryRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    pub async fn find(&self, user_id: &str, video_id: &str) -> Result<Option<WatchEntry>, sea_orm::DbErr> {
+        watch_history::Entity::find()
+            .filter(watch_history::Column::UserId.eq(user_id))
+            .filter(watch_history::Column::VideoId.eq(video_id))
+            .one(&self.db)
+            .await
+    }
+
+    // Registra o progresso; existe no máximo uma entrada por usuário e vídeo
+    pub async fn record_progress(&self, user_id: &str, video_id: &str, watched_seconds: i32, is_completed: bool) -> Result<WatchEntry, sea_orm::DbErr> {
+        let now = Utc::now();
+
+        match self.find(user_id, video_id).await? {
+            Some(entry) => {
+                let mut active: watch_history::ActiveModel = entry.into();
+                active.watched_seconds = Set(watched_seconds);
+                active.is_completed = Set(is_completed);
+                active.last_watched_at = Set(now);
+                active.update(&self.db).await
+            }
+            None => {
+                watch_history::ActiveModel {
+                    id: Set(Uuid::new_v4().to_string()),
+                    user_id: Set(user_id.to_string()),
+                    video_id: Set(video_id.to_string()),
+                    watched_seconds: Set(watched_seconds),
+                    is_completed: Set(is_completed),
+                    last_watched_at: Set(now),
+                    created_at: Set(now),
+                }
+                .insert(&self.db)
+                .await
+            }
+        }
+    }
+
+    // Histórico do usuário com o vídeo de cada entrada, mais recentes primeiro
+    pub async fn find_by_user(&self, user_id: &str, limit: Option<u64>, offset: Option<u64>) -> Result<Vec<(WatchEntry, videos::Model)>, sea_orm::DbErr> {
+        let rows = watch_history::Entity::find()
+            .filter(watch_history::Column::UserId.eq(user_id))
+            .order_by_desc(watch_history::Column::LastWatchedAt)
+            .limit(limit.unwrap_or(50))
+            .offset(offset.unwrap_or(0))
+            .find_also_related(videos::Entity)
+            .all(&self.db)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(entry, video)| video.map(|video| (entry, video)))
+            .collect())
+    }
+
+    // "Continuar assistindo"
+    pub async fn find_in_progress(&self, user_id: &str, limit: Option<u64>) -> Result<Vec<WatchEntry>, sea_orm::DbErr> {
+        watch_history::Entity::find()
+            .filter(watch_history::Column::UserId.eq(user_id))
+            .filter(watch_history::Column::IsCompleted.eq(false))
+            .order_by_desc(watch_history::Column::LastWatchedAt)
+            .limit(limit.unwrap_or(20))
+            .all(&self.db)
+            .await
+    }
+
+    pub async fn delete(&self, user_id: &str, video_id: &str) -> Result<bool, sea_orm::DbErr> {
+        let result = watch_history::Entity::delete_many()
+            .filter(watch_history::Column::UserId.eq(user_id))
+            .filter(watch_history::Column::VideoId.eq(video_id))
+            .exec(&self.db)
+            .await?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    pub async fn clear(&self, user_id: &str) -> Result<u64, sea_orm::DbErr> {
+        let result = watch_history::Entity::delete_many()
+            .filter(watch_history::Column::UserId.eq(user_id))
+            .exec(&self.db)
+            .await?;
+
+        Ok(result.rows_affected)
+    }
+}
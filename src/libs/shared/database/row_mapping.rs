@@ -0,0 +1,125 @@
+use sea_orm::{ConnectionTrait, DbErr, QueryResult, Statement, TryGetError, TryGetable};
+
+// Mapeamento estrito de linhas para structs.
+//
+// Diferente de `try_get(...).unwrap_or_default()`, qualquer coluna ausente,
+// nula onde não deveria ou com tipo incompatível vira erro, e o erro sempre
+// diz qual coluna falhou. Colunas que podem ser nulas devem ser `Option<T>`;
+// as nuláveis com DEFAULT no esquema, que na prática vêm preenchidas, podem
+// ir para o bloco `or` do `impl_from_row!`, com o mesmo valor padrão.
+pub trait FromRow: Sized {
+    fn from_row(row: &QueryResult) -> Result<Self, DbErr>;
+}
+
+// Lê uma coluna da linha, anexando o nome da coluna ao erro
+pub fn get_column<T>(row: &QueryResult, column: &str) -> Result<T, DbErr>
+where
+    T: TryGetable,
+{
+    T::try_get(row, "", column).map_err(|e| match e {
+        TryGetError::Null(_) => DbErr::Type(format!("Coluna '{}': valor nulo em campo obrigatório", column)),
+        TryGetError::DbErr(e) => DbErr::Type(format!("Coluna '{}': {}", column, e)),
+    })
+}
+
+// Como `get_column`, mas NULL vira `default()`. Coluna ausente ou com tipo
+// incompatível continua sendo erro.
+pub fn get_column_or<T>(row: &QueryResult, column: &str, default: impl FnOnce() -> T) -> Result<T, DbErr>
+where
+    T: TryGetable,
+{
+    get_column::<Option<T>>(row, column).map(|value| value.unwrap_or_else(default))
+}
+
+pub async fn query_one_as<T, C>(db: &C, stmt: Statement) -> Result<Option<T>, DbErr>
+where
+    T: FromRow,
+    C: ConnectionTrait,
+{
+    match db.query_one(stmt).await? {
+        Some(row) => T::from_row(&row).map(Some),
+        None => Ok(None),
+    }
+}
+
+pub async fn query_all_as<T, C>(db: &C, stmt: Statement) -> Result<Vec<T>, DbErr>
+where
+    T: FromRow,
+    C: ConnectionTrait,
+{
+    db.query_all(stmt)
+        .await?
+        .iter()
+        .map(T::from_row)
+        .collect()
+}
+
+// Lê um único valor escalar (ex.: `SELECT COUNT(*) AS count`)
+pub async fn query_scalar<T, C>(db: &C, stmt: Statement, column: &str) -> Result<Option<T>, DbErr>
+where
+    T: TryGetable,
+    C: ConnectionTrait,
+{
+    match db.query_one(stmt).await? {
+        Some(row) => get_column(&row, column).map(Some),
+        None => Ok(None),
+    }
+}
+
+// Implementa `FromRow` lendo cada campo da coluna de mesmo nome. Os campos
+// do bloco `or` aceitam NULL e ficam com o valor indicado:
+//
+//   impl_from_row!(User { id, email, name } or { created_at = Utc::now() });
+#[macro_export]
+macro_rules! impl_from_row {
+    ($model:ident { $($field:ident),+ $(,)? }) => {
+        $crate::impl_from_row!($model { $($field),+ } or {});
+    };
+    ($model:ident { $($field:ident),+ $(,)? } or { $($default_field:ident = $default:expr),* $(,)? }) => {
+        impl $crate::libs::shared::database::row_mapping::FromRow for $model {
+            fn from_row(row: &sea_orm::QueryResult) -> Result<Self, sea_orm::DbErr> {
+                Ok(Self {
+                    $($field: $crate::libs::shared::database::row_mapping::get_column(row, stringify!($field))?,)+
+                    $($default_field: $crate::libs::shared::database::row_mapping::get_column_or(row, stringify!($default_field), || $default)?,)*
+                })
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use sea_orm::{ConnectionTrait, Database, DatabaseBackend, Statement};
+    use super::*;
+
+    struct Row {
+        id: String,
+        description: String,
+        is_available: bool,
+    }
+
+    crate::impl_from_row!(Row { id } or { description = String::new(), is_available = true });
+
+    async fn query(sql: &str) -> Result<Vec<Row>, DbErr> {
+        let db = Database::connect("sqlite::memory:").await?;
+        db.execute_unprepared("CREATE TABLE t (id TEXT, description TEXT, is_available BOOLEAN)").await?;
+        db.execute_unprepared(sql).await?;
+
+        query_all_as(&db, Statement::from_string(DatabaseBackend::Sqlite, "SELECT * FROM t ORDER BY id")).await
+    }
+
+    #[tokio::test]
+    async fn null_in_or_block_uses_default() {
+        let rows = query("INSERT INTO t VALUES ('a', NULL, NULL), ('b', 'texto', 0)").await.unwrap();
+
+        assert_eq!((rows[0].id.as_str(), rows[0].description.as_str(), rows[0].is_available), ("a", "", true));
+        assert_eq!((rows[1].id.as_str(), rows[1].description.as_str(), rows[1].is_available), ("b", "texto", false));
+    }
+
+    #[tokio::test]
+    async fn null_in_required_column_names_the_column() {
+        let err = query("INSERT INTO t VALUES (NULL, 'texto', 1)").await.err().unwrap();
+
+        assert!(err.to_string().contains("Coluna 'id'"));
+    }
+}
@@ -0,0 +1,42 @@
+use axum::{
+    extract::Request,
+    http::HeaderValue,
+    middleware::Next,
+    response::Response,
+};
+use uuid::Uuid;
+
+pub const CORRELATION_ID_HEADER: &str = "x-correlation-id";
+
+tokio::task_local! {
+    static CORRELATION_ID: String;
+}
+
+// Id de correlação da requisição em andamento. Fora de uma requisição
+// (jobs, testes) gera um novo, para que o erro ainda seja rastreável no log.
+pub fn current_correlation_id() -> String {
+    CORRELATION_ID
+        .try_with(|id| id.clone())
+        .unwrap_or_else(|_| Uuid::new_v4().to_string())
+}
+
+// Reaproveita o `x-correlation-id` enviado pelo cliente (ou gera um) e o
+// deixa disponível para o AppError durante o processamento da requisição.
+// O mesmo id volta no header da resposta.
+pub async fn correlation_id(request: Request, next: Next) -> Response {
+    let id = request
+        .headers()
+        .get(CORRELATION_ID_HEADER)
+        .and_then(|h| h.to_str().ok())
+        .filter(|id| !id.is_empty() && id.len() <= 128)
+        .map(|id| id.to_string())
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+    let mut response = CORRELATION_ID.scope(id.clone(), next.run(request)).await;
+
+    if let Ok(value) = HeaderValue::from_str(&id) {
+        response.headers_mut().insert(CORRELATION_ID_HEADER, value);
+    }
+
+    response
+}
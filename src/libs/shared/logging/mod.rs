@@ -1,3 +1,5 @@
+pub mod correlation;
+
 use axum::{
     extract::Request,
     http::{Method, StatusCode, Uri},
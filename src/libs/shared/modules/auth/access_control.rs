@@ -1,21 +1,25 @@
-use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::task::{Context, Poll};
 use axum::{
-    extract::{Request, State},
-    http::{header, StatusCode},
-    middleware::Next,
-    response::Response,
+    extract::{FromRef, FromRequestParts, Request},
+    http::{request::Parts, HeaderMap},
+    response::{IntoResponse, Response},
 };
-use axum_extra::extract::Bearer;
-use sea_orm::{DatabaseConnection, Statement, FromQueryResult};
+use futures::future::BoxFuture;
+use sea_orm::DatabaseConnection;
 use serde::{Deserialize, Serialize};
-use jsonwebtoken::{decode, DecodingKey, Validation};
-use crate::modules::jwt::Claims;
-
-// Chaves para metadados
-pub const ACCESS_GROUP_KEY: &str = "access_groups";
-pub const IS_PUBLIC_KEY: &str = "is_public";
+use tower::{Layer, Service};
+use crate::errors::AppError;
+use crate::libs::shared::database::repositories::access_groups::AccessGroupsRepository;
+use crate::libs::shared::database::repositories::users::UsersRepository;
+use crate::libs::shared::modules::token_service::Claims;
+use super::email_verification::UnverifiedLoginPolicy;
+use super::middleware::{authenticate, CurrentClaims};
+use super::permissions::{PermissionResolver, PermissionSet};
 
 // Enum para grupos de acesso
+#[allow(non_camel_case_types)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum AccessGroupEnum {
     VIEWER = 1,
@@ -42,174 +46,300 @@ impl From<AccessGroupEnum> for i32 {
     }
 }
 
+impl AccessGroupEnum {
+    // Nome do grupo na tabela access_groups; grupos sem equivalente
+    // (ex.: Moderator) não entram nas claims
+    pub fn from_group_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().replace([' ', '_', '-'], "").as_str() {
+            "standard" | "viewer" => Some(AccessGroupEnum::VIEWER),
+            "premium" => Some(AccessGroupEnum::PREMIUM),
+            "admin" => Some(AccessGroupEnum::ADMIN),
+            "superadmin" => Some(AccessGroupEnum::SUPER_ADMIN),
+            _ => None,
+        }
+    }
+}
+
 // Estrutura para usuário com grupos de acesso
-#[derive(Debug, Clone, Serialize, Deserialize, FromQueryResult)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserWithAccess {
     pub id: String,
     pub email: String,
     pub name: String,
     pub role: String,
     pub access_groups: Vec<AccessGroupEnum>,
+    pub email_verified: bool,
 }
 
-// Estrutura para metadados de rota
+impl UserWithAccess {
+    pub fn has_any_group(&self, groups: &[AccessGroupEnum]) -> bool {
+        groups_allow(&self.access_groups, groups)
+    }
+}
+
+// Requisito de acesso de uma rota
 #[derive(Debug, Clone)]
 pub struct RouteMetadata {
     pub is_public: bool,
     pub required_groups: Vec<AccessGroupEnum>,
+    // Todas são exigidas; resolvidas no banco a partir dos grupos do usuário
+    pub required_permissions: Vec<String>,
+    // Aceita contas sem e-mail confirmado mesmo com a política `limited`
+    pub allow_unverified: bool,
 }
 
-// Decorator para marcar rota como pública
-pub fn Public() -> RouteMetadata {
-    RouteMetadata {
+// Rota aberta. Se vier um token válido, as claims ficam disponíveis para o
+// handler; um token inválido é ignorado.
+pub fn public() -> AuthGuard {
+    AuthGuard::new(RouteMetadata {
         is_public: true,
         required_groups: vec![],
-    }
+        required_permissions: vec![],
+        allow_unverified: false,
+    })
+}
+
+// Qualquer usuário autenticado
+pub fn authenticated() -> AuthGuard {
+    AuthGuard::new(RouteMetadata {
+        is_public: false,
+        required_groups: vec![],
+        required_permissions: vec![],
+        allow_unverified: false,
+    })
+}
+
+// Qualquer usuário autenticado, inclusive sem e-mail confirmado: rotas da
+// própria conta (sessões, logout, senha)
+pub fn allow_unverified() -> AuthGuard {
+    AuthGuard::new(RouteMetadata {
+        is_public: false,
+        required_groups: vec![],
+        required_permissions: vec![],
+        allow_unverified: true,
+    })
 }
 
-// Decorator para requerer grupos de acesso
-pub fn RequireAccess(groups: &[AccessGroupEnum]) -> RouteMetadata {
-    RouteMetadata {
+// Usuário autenticado em pelo menos um dos grupos
+pub fn require_access(groups: &[AccessGroupEnum]) -> AuthGuard {
+    AuthGuard::new(RouteMetadata {
         is_public: false,
         required_groups: groups.to_vec(),
-    }
+        required_permissions: vec![],
+        allow_unverified: false,
+    })
+}
+
+// Usuário autenticado com a permissão (ver `permissions`)
+pub fn require_permission(permission: &str) -> AuthGuard {
+    require_permissions(&[permission])
+}
+
+// Usuário autenticado com todas as permissões
+pub fn require_permissions(permissions: &[&str]) -> AuthGuard {
+    AuthGuard::new(RouteMetadata {
+        is_public: false,
+        required_groups: vec![],
+        required_permissions: permissions.iter().map(|p| p.to_string()).collect(),
+        allow_unverified: false,
+    })
+}
+
+// SUPER_ADMIN passa em qualquer exigência de grupo
+fn groups_allow(user_groups: &[AccessGroupEnum], required: &[AccessGroupEnum]) -> bool {
+    required.is_empty()
+        || user_groups.contains(&AccessGroupEnum::SUPER_ADMIN)
+        || required.iter().any(|group| user_groups.contains(group))
 }
 
-// Middleware de autenticação e autorização
+// Guard por rota, aplicado como layer:
+//
+//     .route("/videos", post(create).route_layer(require_permission(VIDEOS_WRITE)))
+//
+// Grupos são checados pelas claims do access token (assinatura, denylist e
+// grupos), sem consultar o banco. Permissões são resolvidas pelo
+// `PermissionResolver` com a `DatabaseConnection` das extensions, então o
+// app precisa de `.layer(Extension(db))`. Claims e permissões ficam nas
+// extensions para `CurrentClaims`, `UserWithAccess` e `PermissionSet`.
+#[derive(Debug, Clone)]
 pub struct AuthGuard {
-    db: DatabaseConnection,
-    jwt_manager: crate::modules::jwt::JwtManager,
+    metadata: Arc<RouteMetadata>,
 }
 
 impl AuthGuard {
-    pub fn new(db: DatabaseConnection, jwt_manager: crate::modules::jwt::JwtManager) -> Self {
-        Self { db, jwt_manager }
+    pub fn new(metadata: RouteMetadata) -> Self {
+        Self {
+            metadata: Arc::new(metadata),
+        }
     }
 
-    pub async fn authenticate_and_authorize(
-        &self,
-        request: &Request,
-        metadata: &RouteMetadata,
-    ) -> Result<UserWithAccess, StatusCode> {
-        // Se a rota é pública, não precisa de autenticação
-        if metadata.is_public {
-            return Err(StatusCode::UNAUTHORIZED); // Mas ainda precisamos de um usuário válido
+    pub fn metadata(&self) -> &RouteMetadata {
+        &self.metadata
+    }
+
+    pub async fn authorize(&self, headers: &HeaderMap) -> Result<Option<Claims>, AppError> {
+        if self.metadata.is_public {
+            return Ok(authenticate(headers).await.ok());
         }
 
-        // Extrair token do header Authorization
-        let auth_header = request.headers()
-            .get("authorization")
-            .and_then(|h| h.to_str().ok())
-            .ok_or(StatusCode::UNAUTHORIZED)?;
+        let claims = authenticate(headers).await?;
+
+        if !claims.email_verified
+            && !self.metadata.allow_unverified
+            && !UnverifiedLoginPolicy::shared().allows_unverified_access()
+        {
+            return Err(AppError::Forbidden(
+                "Confirme o seu e-mail para acessar este recurso".to_string(),
+            ));
+        }
 
-        if !auth_header.starts_with("Bearer ") {
-            return Err(StatusCode::UNAUTHORIZED);
+        if !groups_allow(&claims.access_groups, &self.metadata.required_groups) {
+            return Err(AppError::Forbidden("Acesso negado para este recurso".to_string()));
         }
 
-        let token = &auth_header[7..]; // Remove "Bearer "
+        Ok(Some(claims))
+    }
 
-        // Validar JWT
-        let claims = self.jwt_manager
-            .validate_access_token(token)
-            .map_err(|_| StatusCode::UNAUTHORIZED)?;
+    // Só consulta o banco (ou o cache) se a rota exige permissões
+    pub async fn authorize_permissions(
+        &self,
+        claims: &Claims,
+        db: Option<&DatabaseConnection>,
+    ) -> Result<Option<PermissionSet>, AppError> {
+        if self.metadata.required_permissions.is_empty() {
+            return Ok(None);
+        }
 
-        // Buscar usuário com grupos de acesso
-        let user = self.get_user_with_access_groups(&claims.sub).await
-            .map_err(|_| StatusCode::UNAUTHORIZED)?;
+        let db = db.ok_or_else(|| {
+            AppError::Internal("DatabaseConnection ausente nas extensions da requisição".to_string())
+        })?;
 
-        let user = user.ok_or(StatusCode::UNAUTHORIZED)?;
+        let permissions = PermissionResolver::shared().for_user(db, &claims.sub).await?;
 
-        // Verificar se o usuário tem os grupos de acesso necessários
-        if !metadata.required_groups.is_empty() {
-            let has_access = metadata.required_groups.iter()
-                .any(|required_group| {
-                    user.access_groups.contains(required_group)
-                });
+        match self
+            .metadata
+            .required_permissions
+            .iter()
+            .find(|permission| !permissions.allows(permission))
+        {
+            Some(missing) => Err(AppError::Forbidden(format!("Permissão necessária: {}", missing))),
+            None => Ok(Some(permissions)),
+        }
+    }
+}
 
-            if !has_access {
-                return Err(StatusCode::FORBIDDEN);
-            }
+impl<S> Layer<S> for AuthGuard {
+    type Service = AuthGuardService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AuthGuardService {
+            inner,
+            guard: self.clone(),
         }
+    }
+}
 
-        Ok(user)
+#[derive(Debug, Clone)]
+pub struct AuthGuardService<S> {
+    inner: S,
+    guard: AuthGuard,
+}
+
+impl<S> Service<Request> for AuthGuardService<S>
+where
+    S: Service<Request, Response = Response, Error = Infallible> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Response, Infallible>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
     }
 
-    async fn get_user_with_access_groups(&self, user_id: &str) -> Result<Option<UserWithAccess>, sea_orm::DbErr> {
-        let sql = r#"
-            SELECT 
-                u.id, u.email, u.name, u.role,
-                STRING_AGG(CAST(uag.access_group_id AS VARCHAR), ',') as access_groups
-            FROM users u
-            LEFT JOIN users_access_groups uag ON u.id = uag.user_id
-            WHERE u.id = @P1
-            GROUP BY u.id, u.email, u.name, u.role
-        "#;
-
-        let stmt = Statement::from_sql_and_values(
-            sea_orm::DatabaseBackend::SqlServer,
-            sql,
-            vec![user_id.into()],
-        );
-
-        let result = self.db.query_one(stmt).await?;
-        
-        if let Some(row) = result {
-            let id: String = row.try_get("", "id").unwrap_or_default();
-            let email: String = row.try_get("", "email").unwrap_or_default();
-            let name: String = row.try_get("", "name").unwrap_or_default();
-            let role: String = row.try_get("", "role").unwrap_or_default();
-            let access_groups_str: Option<String> = row.try_get("", "access_groups").ok();
-
-            let access_groups = if let Some(groups_str) = access_groups_str {
-                groups_str.split(',')
-                    .filter_map(|s| s.parse::<i32>().ok().map(AccessGroupEnum::from))
-                    .collect()
-            } else {
-                vec![]
+    fn call(&mut self, mut request: Request) -> Self::Future {
+        // O clone pronto fica com a chamada; o original volta para o pool
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let guard = self.guard.clone();
+
+        Box::pin(async move {
+            let claims = match guard.authorize(request.headers()).await {
+                Ok(claims) => claims,
+                Err(e) => return Ok(e.into_response()),
             };
 
-            Ok(Some(UserWithAccess {
-                id,
-                email,
-                name,
-                role,
-                access_groups,
-            }))
-        } else {
-            Ok(None)
-        }
+            if let Some(claims) = claims {
+                let db = request.extensions().get::<DatabaseConnection>().cloned();
+
+                match guard.authorize_permissions(&claims, db.as_ref()).await {
+                    Ok(Some(permissions)) => {
+                        request.extensions_mut().insert(permissions);
+                    }
+                    Ok(None) => {}
+                    Err(e) => return Ok(e.into_response()),
+                }
+
+                request.extensions_mut().insert(claims);
+            }
+
+            inner.call(request).await
+        })
     }
 }
 
-// Middleware para aplicar autenticação
-pub async fn auth_middleware(
-    State(db): State<DatabaseConnection>,
-    State(jwt_manager): State<crate::modules::jwt::JwtManager>,
-    State(route_metadata): State<RouteMetadata>,
-    mut request: Request,
-    next: Next,
-) -> Result<Response, StatusCode> {
-    let auth_guard = AuthGuard::new(db, jwt_manager);
-    
-    match auth_guard.authenticate_and_authorize(&request, &route_metadata).await {
-        Ok(user) => {
-            // Adicionar usuário ao request extensions
-            request.extensions_mut().insert(user);
-            Ok(next.run(request).await)
+// Usuário autenticado com os grupos atuais, lidos do banco. Use em handlers
+// que precisam de dados além das claims.
+impl<S> FromRequestParts<S> for UserWithAccess
+where
+    DatabaseConnection: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        if let Some(user) = parts.extensions.get::<UserWithAccess>() {
+            return Ok(user.clone());
         }
-        Err(status) => {
-            let error_response = serde_json::json!({
-                "error": "Unauthorized",
-                "message": "Acesso negado"
-            });
-            
-            let response = Response::builder()
-                .status(status)
-                .header("content-type", "application/json")
-                .body(axum::body::Body::from(serde_json::to_string(&error_response).unwrap()))
-                .unwrap();
-            
-            Ok(response)
+
+        let CurrentClaims(claims) = CurrentClaims::from_request_parts(parts, state).await?;
+        let db = DatabaseConnection::from_ref(state);
+
+        let user = load_user_with_access(&db, &claims.sub)
+            .await?
+            .ok_or_else(|| AppError::Auth("Usuário não encontrado".to_string()))?;
+
+        parts.extensions.insert(user.clone());
+        Ok(user)
+    }
+}
+
+// Usuário com os grupos de acesso atuais, usado no guard e na emissão de
+// tokens. Todo usuário é VIEWER; os demais grupos vêm só dos vínculos.
+pub async fn load_user_with_access(db: &DatabaseConnection, user_id: &str) -> Result<Option<UserWithAccess>, sea_orm::DbErr> {
+    let user = match UsersRepository::new(db.clone()).find_by_id(user_id).await? {
+        Some(user) => user,
+        None => return Ok(None),
+    };
+
+    let mut access_groups = vec![AccessGroupEnum::VIEWER];
+
+    for group in AccessGroupsRepository::new(db.clone()).find_by_user(user_id).await? {
+        if let Some(group) = AccessGroupEnum::from_group_name(&group.name) {
+            access_groups.push(group);
         }
     }
+
+    access_groups.sort_by_key(|group| i32::from(*group));
+    access_groups.dedup();
+
+    Ok(Some(UserWithAccess {
+        email_verified: user.is_email_verified(),
+        id: user.id,
+        email: user.email,
+        name: user.name,
+        role: user.role,
+        access_groups,
+    }))
 }
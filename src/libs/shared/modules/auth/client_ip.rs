@@ -0,0 +1,219 @@
+use std::net::{IpAddr, SocketAddr};
+use std::sync::OnceLock;
+use axum::http::HeaderMap;
+use crate::config::Config;
+
+static SHARED_TRUSTED_PROXIES: OnceLock<TrustedProxies> = OnceLock::new();
+
+// Proxies cujos headers X-Forwarded-For / X-Real-IP são aceitos
+// (TRUSTED_PROXIES no .env: IPs ou faixas CIDR separados por vírgula).
+// Sem nenhum, vale só o endereço da conexão.
+#[derive(Debug, Clone, Default)]
+pub struct TrustedProxies {
+    networks: Vec<(IpAddr, u8)>,
+}
+
+impl TrustedProxies {
+    pub fn parse(value: &str) -> Self {
+        let networks = value
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .filter_map(|entry| {
+                let network = Self::parse_network(entry);
+                if network.is_none() {
+                    tracing::warn!("Entrada inválida em TRUSTED_PROXIES ignorada: {}", entry);
+                }
+                network
+            })
+            .collect();
+
+        Self { networks }
+    }
+
+    pub fn shared() -> &'static Self {
+        SHARED_TRUSTED_PROXIES.get_or_init(|| {
+            let config = Config::from_env().expect("Erro ao carregar configuração");
+            Self::parse(&config.trusted_proxies)
+        })
+    }
+
+    fn parse_network(entry: &str) -> Option<(IpAddr, u8)> {
+        let (address, prefix) = match entry.split_once('/') {
+            Some((address, prefix)) => (address, Some(prefix.parse::<u8>().ok()?)),
+            None => (entry, None),
+        };
+
+        let address = address.parse::<IpAddr>().ok()?.to_canonical();
+        let max_prefix = if address.is_ipv4() { 32 } else { 128 };
+        let prefix = prefix.unwrap_or(max_prefix);
+
+        (prefix <= max_prefix).then_some((address, prefix))
+    }
+
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        let ip = ip.to_canonical();
+
+        self.networks.iter().any(|(network, prefix)| match (network, ip) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => {
+                let mask = u32::MAX.checked_shl(32 - *prefix as u32).unwrap_or(0);
+                u32::from(*network) & mask == u32::from(ip) & mask
+            }
+            (IpAddr::V6(network), IpAddr::V6(ip)) => {
+                let mask = u128::MAX.checked_shl(128 - *prefix as u32).unwrap_or(0);
+                u128::from(*network) & mask == u128::from(ip) & mask
+            }
+            _ => false,
+        })
+    }
+
+    // Endereço usado em limites e bloqueios. Os headers de proxy só valem
+    // quando a conexão vem de um proxy confiável; aí o cliente é o primeiro
+    // endereço não confiável do X-Forwarded-For, lido da direita para a
+    // esquerda (o que está à esquerda dele o próprio cliente pode inventar).
+    pub fn client_ip(&self, headers: &HeaderMap, peer: Option<SocketAddr>) -> Option<IpAddr> {
+        let peer = peer?.ip().to_canonical();
+
+        if !self.contains(peer) {
+            return Some(peer);
+        }
+
+        let hops: Vec<&str> = headers
+            .get_all("x-forwarded-for")
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .map(str::trim)
+            .filter(|hop| !hop.is_empty())
+            .collect();
+
+        if hops.is_empty() {
+            return headers
+                .get("x-real-ip")
+                .and_then(|value| value.to_str().ok())
+                .and_then(Self::parse_hop)
+                .or(Some(peer));
+        }
+
+        let mut client = peer;
+        for hop in hops.iter().rev() {
+            // Endereço ilegível: fica o último que se pôde conferir
+            let Some(ip) = Self::parse_hop(hop) else {
+                break;
+            };
+
+            client = ip;
+            if !self.contains(ip) {
+                break;
+            }
+        }
+
+        Some(client)
+    }
+
+    // Aceita "1.2.3.4", "1.2.3.4:5678", "2001:db8::1" e "[2001:db8::1]:443"
+    fn parse_hop(hop: &str) -> Option<IpAddr> {
+        let hop = hop.trim();
+
+        hop.parse::<IpAddr>()
+            .ok()
+            .or_else(|| hop.parse::<SocketAddr>().ok().map(|addr| addr.ip()))
+            .map(|ip| ip.to_canonical())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn peer(ip: &str) -> Option<SocketAddr> {
+        Some(SocketAddr::new(ip.parse().unwrap(), 40000))
+    }
+
+    fn forwarded(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    fn ip(value: &str) -> Option<IpAddr> {
+        Some(value.parse().unwrap())
+    }
+
+    #[test]
+    fn ignores_forwarded_headers_without_trusted_proxies() {
+        let proxies = TrustedProxies::default();
+        let headers = forwarded("203.0.113.9");
+
+        assert_eq!(proxies.client_ip(&headers, peer("198.51.100.7")), ip("198.51.100.7"));
+    }
+
+    #[test]
+    fn ignores_forwarded_headers_from_untrusted_peer() {
+        let proxies = TrustedProxies::parse("10.0.0.0/8");
+        let headers = forwarded("203.0.113.9");
+
+        assert_eq!(proxies.client_ip(&headers, peer("198.51.100.7")), ip("198.51.100.7"));
+    }
+
+    #[test]
+    fn takes_rightmost_untrusted_hop_behind_trusted_proxy() {
+        let proxies = TrustedProxies::parse("10.0.0.0/8, 172.16.0.1");
+        // O primeiro valor foi inventado pelo cliente
+        let headers = forwarded("1.1.1.1, 203.0.113.9, 172.16.0.1");
+
+        assert_eq!(proxies.client_ip(&headers, peer("10.0.0.2")), ip("203.0.113.9"));
+    }
+
+    #[test]
+    fn joins_repeated_forwarded_headers() {
+        let proxies = TrustedProxies::parse("10.0.0.1");
+        let mut headers = forwarded("1.1.1.1");
+        headers.append("x-forwarded-for", HeaderValue::from_static("203.0.113.9"));
+
+        assert_eq!(proxies.client_ip(&headers, peer("10.0.0.1")), ip("203.0.113.9"));
+    }
+
+    #[test]
+    fn stops_at_unparseable_hop() {
+        let proxies = TrustedProxies::parse("10.0.0.1");
+        let headers = forwarded("203.0.113.9, unknown");
+
+        assert_eq!(proxies.client_ip(&headers, peer("10.0.0.1")), ip("10.0.0.1"));
+    }
+
+    #[test]
+    fn uses_real_ip_only_from_trusted_proxy() {
+        let proxies = TrustedProxies::parse("10.0.0.1");
+        let mut headers = HeaderMap::new();
+        headers.insert("x-real-ip", HeaderValue::from_static("203.0.113.9"));
+
+        assert_eq!(proxies.client_ip(&headers, peer("10.0.0.1")), ip("203.0.113.9"));
+        assert_eq!(proxies.client_ip(&headers, peer("198.51.100.7")), ip("198.51.100.7"));
+    }
+
+    #[test]
+    fn matches_ipv6_ranges_and_mapped_ipv4() {
+        let proxies = TrustedProxies::parse("fd00::/8, 192.168.0.0/16");
+
+        assert!(proxies.contains("fd12::1".parse().unwrap()));
+        assert!(proxies.contains("::ffff:192.168.1.1".parse().unwrap()));
+        assert!(!proxies.contains("2001:db8::1".parse().unwrap()));
+    }
+
+    #[test]
+    fn skips_invalid_entries() {
+        let proxies = TrustedProxies::parse("10.0.0.0/33, nada, 0.0.0.0/0");
+
+        assert!(proxies.contains("203.0.113.9".parse().unwrap()));
+        assert_eq!(proxies.networks.len(), 1);
+    }
+
+    #[test]
+    fn without_peer_there_is_no_address() {
+        let proxies = TrustedProxies::parse("10.0.0.1");
+
+        assert_eq!(proxies.client_ip(&forwarded("203.0.113.9"), None), None);
+    }
+}
@@ -0,0 +1,56 @@
+use std::sync::OnceLock;
+use crate::config::Config;
+use crate::errors::AppError;
+
+static SHARED_POLICY: OnceLock<UnverifiedLoginPolicy> = OnceLock::new();
+
+// O que uma conta sem e-mail confirmado pode fazer (UNVERIFIED_LOGIN_POLICY)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnverifiedLoginPolicy {
+    // Sem restrição
+    Allow,
+    // Recebe tokens, mas só acessa as rotas marcadas com `allow_unverified()`
+    // (sessões, logout, troca de senha...)
+    Limited,
+    // Não recebe tokens até confirmar o e-mail
+    Block,
+}
+
+impl UnverifiedLoginPolicy {
+    pub fn parse(value: &str) -> Result<Self, AppError> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "allow" => Ok(Self::Allow),
+            "limited" => Ok(Self::Limited),
+            "block" => Ok(Self::Block),
+            other => Err(AppError::Config(format!(
+                "UNVERIFIED_LOGIN_POLICY inválido: {} (use allow, limited ou block)",
+                other
+            ))),
+        }
+    }
+
+    // Valor inválido cai no `limited`, que não bloqueia ninguém de vez
+    pub fn from_config(config: &Config) -> Self {
+        Self::parse(&config.unverified_login_policy).unwrap_or_else(|e| {
+            tracing::warn!("{}; usando limited", e);
+            Self::Limited
+        })
+    }
+
+    pub fn shared() -> Self {
+        *SHARED_POLICY.get_or_init(|| {
+            Config::from_env()
+                .map(|config| Self::from_config(&config))
+                .unwrap_or(Self::Limited)
+        })
+    }
+
+    pub fn issues_tokens_to_unverified(&self) -> bool {
+        *self != Self::Block
+    }
+
+    // Se uma rota sem `allow_unverified()` aceita a conta
+    pub fn allows_unverified_access(&self) -> bool {
+        *self == Self::Allow
+    }
+}
@@ -0,0 +1,223 @@
+use std::sync::OnceLock;
+use sha2::{Digest, Sha256};
+use crate::config::Config;
+use crate::errors::AppError;
+use crate::libs::shared::cache::redis_cache::RedisCache;
+
+static SHARED_LOGIN_THROTTLE: OnceLock<LoginThrottle> = OnceLock::new();
+
+// Limites de tentativas de login (LOGIN_* no .env)
+#[derive(Debug, Clone)]
+pub struct LoginThrottleOptions {
+    pub max_failures_per_account: i64,
+    pub max_failures_per_ip: i64,
+    pub failure_window_seconds: u64,
+    pub lockout_seconds: u64,
+    pub backoff_base_seconds: u64,
+    pub backoff_max_seconds: u64,
+}
+
+impl LoginThrottleOptions {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            max_failures_per_account: config.login_max_failures_per_account.max(1),
+            max_failures_per_ip: config.login_max_failures_per_ip.max(1),
+            failure_window_seconds: config.login_failure_window_minutes.max(1) * 60,
+            lockout_seconds: config.login_lockout_minutes.max(1) * 60,
+            backoff_base_seconds: config.login_backoff_base_seconds,
+            backoff_max_seconds: config.login_backoff_max_seconds,
+        }
+    }
+}
+
+// Proteção contra força bruta no login, com contadores no Redis:
+//
+//   auth:login:fail:account:{hash}     -> falhas da conta na janela
+//   auth:login:fail:ip:{ip}            -> falhas do IP na janela
+//   auth:login:backoff:account:{hash}  -> espera entre tentativas (TTL)
+//   auth:login:lock:account:{hash}     -> conta bloqueada (TTL)
+//   auth:login:lock:ip:{ip}            -> IP bloqueado (TTL)
+//
+// A conta é identificada pelo SHA-256 do e-mail normalizado, exista ela ou
+// não: e-mails não cadastrados são contados e bloqueados do mesmo jeito, sem
+// revelar quais existem. Cada falha dobra a espera até a próxima tentativa;
+// ao atingir o limite, a conta (ou o IP) fica bloqueada. Sem Redis, o login
+// segue sem limite e o erro vai para o log.
+pub struct LoginThrottle {
+    options: LoginThrottleOptions,
+}
+
+impl LoginThrottle {
+    pub fn new(options: LoginThrottleOptions) -> Self {
+        Self { options }
+    }
+
+    pub fn shared() -> &'static Self {
+        SHARED_LOGIN_THROTTLE.get_or_init(|| {
+            let config = Config::from_env().expect("Erro ao carregar configuração");
+            Self::new(LoginThrottleOptions::from_config(&config))
+        })
+    }
+
+    fn account_id(email: &str) -> String {
+        hex::encode(Sha256::digest(email.trim().to_lowercase().as_bytes()))
+    }
+
+    fn account_failures_key(account: &str) -> String {
+        format!("auth:login:fail:account:{}", account)
+    }
+
+    fn ip_failures_key(ip: &str) -> String {
+        format!("auth:login:fail:ip:{}", ip)
+    }
+
+    fn backoff_key(account: &str) -> String {
+        format!("auth:login:backoff:account:{}", account)
+    }
+
+    fn account_lock_key(account: &str) -> String {
+        format!("auth:login:lock:account:{}", account)
+    }
+
+    fn ip_lock_key(ip: &str) -> String {
+        format!("auth:login:lock:ip:{}", ip)
+    }
+
+    // Espera depois da n-ésima falha: base, 2×base, 4×base... até o teto
+    fn backoff_seconds(&self, failures: i64) -> u64 {
+        let exponent = (failures - 1).clamp(0, 16) as u32;
+        self.options
+            .backoff_base_seconds
+            .saturating_mul(2u64.pow(exponent))
+            .min(self.options.backoff_max_seconds)
+    }
+
+    // Chamado antes de conferir a senha: bloqueio e espera valem mesmo
+    // para a senha certa
+    pub async fn check(&self, email: &str, ip: Option<&str>) -> Result<(), AppError> {
+        match self.blocked_for(email, ip).await {
+            Ok(None) => Ok(()),
+            Ok(Some((message, retry_after))) => Err(AppError::RateLimited(message, retry_after)),
+            Err(e) => {
+                tracing::warn!("Limite de tentativas de login indisponível: {}", e);
+                Ok(())
+            }
+        }
+    }
+
+    async fn blocked_for(&self, email: &str, ip: Option<&str>) -> Result<Option<(String, u64)>, AppError> {
+        let cache = RedisCache::shared().await?;
+        let account = Self::account_id(email);
+
+        if let Some(ip) = ip {
+            if let Some(ttl) = cache.get_ttl(Self::ip_lock_key(ip)).await?.filter(|ttl| *ttl > 0) {
+                return Ok(Some(("Muitas tentativas de login deste endereço; tente novamente mais tarde".to_string(), ttl)));
+            }
+        }
+
+        if let Some(ttl) = cache.get_ttl(Self::account_lock_key(&account)).await?.filter(|ttl| *ttl > 0) {
+            return Ok(Some(("Conta bloqueada temporariamente por excesso de tentativas".to_string(), ttl)));
+        }
+
+        if let Some(ttl) = cache.get_ttl(Self::backoff_key(&account)).await?.filter(|ttl| *ttl > 0) {
+            return Ok(Some(("Aguarde antes de tentar novamente".to_string(), ttl)));
+        }
+
+        Ok(None)
+    }
+
+    pub async fn record_failure(&self, email: &str, ip: Option<&str>) {
+        if let Err(e) = self.try_record_failure(email, ip).await {
+            tracing::warn!("Erro ao registrar falha de login: {}", e);
+        }
+    }
+
+    async fn try_record_failure(&self, email: &str, ip: Option<&str>) -> Result<(), AppError> {
+        let cache = RedisCache::shared().await?;
+        let account = Self::account_id(email);
+        let window = self.options.failure_window_seconds;
+
+        let failures = cache
+            .increment_with_ttl(Self::account_failures_key(&account), 1, window)
+            .await?;
+
+        if failures >= self.options.max_failures_per_account {
+            cache
+                .set_with_ttl(Self::account_lock_key(&account), &failures, self.options.lockout_seconds)
+                .await?;
+            // A contagem recomeça quando o bloqueio acabar
+            cache.delete(Self::account_failures_key(&account)).await?;
+            tracing::warn!("Conta bloqueada por {} falhas de login seguidas", failures);
+        } else {
+            let backoff = self.backoff_seconds(failures);
+            if backoff > 0 {
+                cache.set_with_ttl(Self::backoff_key(&account), &failures, backoff).await?;
+            }
+        }
+
+        if let Some(ip) = ip {
+            let ip_failures = cache.increment_with_ttl(Self::ip_failures_key(ip), 1, window).await?;
+
+            if ip_failures >= self.options.max_failures_per_ip {
+                cache
+                    .set_with_ttl(Self::ip_lock_key(ip), &ip_failures, self.options.lockout_seconds)
+                    .await?;
+                cache.delete(Self::ip_failures_key(ip)).await?;
+                tracing::warn!("IP {} bloqueado por {} falhas de login", ip, ip_failures);
+            }
+        }
+
+        Ok(())
+    }
+
+    // Bloqueio imediato da conta, por LOGIN_LOCKOUT_MINUTES (ex.: códigos de
+    // 2FA errados demais depois da senha certa)
+    pub async fn lock_account(&self, email: &str) {
+        if let Err(e) = self.try_lock_account(email).await {
+            tracing::warn!("Erro ao bloquear conta: {}", e);
+        }
+    }
+
+    async fn try_lock_account(&self, email: &str) -> Result<(), AppError> {
+        let cache = RedisCache::shared().await?;
+        let account = Self::account_id(email);
+
+        cache
+            .set_with_ttl(Self::account_lock_key(&account), &true, self.options.lockout_seconds)
+            .await?;
+
+        Ok(())
+    }
+
+    // Login certo zera a conta. O contador do IP continua: uma conta válida
+    // não pode servir para zerar as tentativas contra outras.
+    pub async fn record_success(&self, email: &str) {
+        if let Err(e) = self.clear_account(email).await {
+            tracing::warn!("Erro ao limpar falhas de login: {}", e);
+        }
+    }
+
+    // Desbloqueio manual (admin). Devolve se a conta estava bloqueada ou
+    // em espera.
+    pub async fn unlock(&self, email: &str) -> Result<bool, AppError> {
+        self.clear_account(email).await
+    }
+
+    async fn clear_account(&self, email: &str) -> Result<bool, AppError> {
+        let cache = RedisCache::shared().await?;
+        let account = Self::account_id(email);
+
+        let locked = cache.exists(Self::account_lock_key(&account)).await?
+            || cache.exists(Self::backoff_key(&account)).await?;
+
+        cache
+            .delete_many(&[
+                Self::account_failures_key(&account),
+                Self::backoff_key(&account),
+                Self::account_lock_key(&account),
+            ])
+            .await?;
+
+        Ok(locked)
+    }
+}
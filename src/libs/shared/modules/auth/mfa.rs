@@ -0,0 +1,58 @@
+use std::sync::OnceLock;
+use data_encoding::BASE32_NOPAD;
+use sha2::{Digest, Sha256};
+use crate::config::Config;
+use super::access_control::AccessGroupEnum;
+
+static SHARED_POLICY: OnceLock<MfaPolicy> = OnceLock::new();
+
+// Quando o segundo fator é obrigatório (MFA_REQUIRED_FOR_ADMINS)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MfaPolicy {
+    pub required_for_admins: bool,
+}
+
+impl MfaPolicy {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            required_for_admins: config.mfa_required_for_admins,
+        }
+    }
+
+    pub fn shared() -> Self {
+        *SHARED_POLICY.get_or_init(|| {
+            Config::from_env()
+                .map(|config| Self::from_config(&config))
+                .unwrap_or(Self { required_for_admins: true })
+        })
+    }
+
+    // Contas ADMIN e SUPER_ADMIN só entram com 2FA quando a política exige
+    pub fn is_required_for(&self, groups: &[AccessGroupEnum]) -> bool {
+        self.required_for_admins
+            && groups
+                .iter()
+                .any(|group| matches!(group, AccessGroupEnum::ADMIN | AccessGroupEnum::SUPER_ADMIN))
+    }
+}
+
+// Códigos no formato XXXXX-XXXXX (50 bits cada), fáceis de digitar
+pub fn generate_recovery_codes(count: usize) -> Vec<String> {
+    (0..count)
+        .map(|_| {
+            let encoded = BASE32_NOPAD.encode(&rand::random::<[u8; 8]>());
+            format!("{}-{}", &encoded[..5], &encoded[5..10])
+        })
+        .collect()
+}
+
+// Hash guardado no banco; ignora hífens, espaços e caixa
+pub fn recovery_code_hash(code: &str) -> String {
+    let normalized: String = code
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+
+    hex::encode(Sha256::digest(normalized.as_bytes()))
+}
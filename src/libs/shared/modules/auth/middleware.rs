@@ -0,0 +1,134 @@
+use axum::{
+    extract::{FromRequestParts, Request, State},
+    http::{header, request::Parts, HeaderMap},
+    middleware::Next,
+    response::Response,
+};
+use sea_orm::DatabaseConnection;
+use crate::errors::AppError;
+use crate::libs::shared::database::repositories::users::UsersRepository;
+use crate::libs::shared::modules::token_service::{Claims, TokenService};
+use super::permissions::{PermissionResolver, ALL_PERMISSIONS};
+use super::revocation::RevocationList;
+
+pub const ACCESS_TOKEN_COOKIE: &str = "access_token";
+
+// Token do header `Authorization: Bearer` ou, na falta dele, do cookie
+// `access_token`
+pub fn extract_token(headers: &HeaderMap) -> Option<String> {
+    let bearer = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|h| h.to_str().ok())
+        .and_then(|h| h.strip_prefix("Bearer "))
+        .map(|token| token.trim().to_string());
+
+    if bearer.is_some() {
+        return bearer;
+    }
+
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|h| h.to_str().ok())
+        .flat_map(|h| h.split(';'))
+        .filter_map(|cookie| cookie.trim().split_once('='))
+        .find(|(name, _)| *name == ACCESS_TOKEN_COOKIE)
+        .map(|(_, value)| value.to_string())
+}
+
+// Valida o access token da requisição e confere a denylist. Se o Redis
+// estiver fora do ar não há como saber se o token foi revogado, então ele é
+// recusado com 503; as rotas públicas seguem atendendo como anônimas.
+pub async fn authenticate(headers: &HeaderMap) -> Result<Claims, AppError> {
+    let token = extract_token(headers)
+        .ok_or_else(|| AppError::Auth("Token de acesso ausente".to_string()))?;
+
+    let claims = TokenService::shared().validate_access_token(&token)?;
+
+    match RevocationList::shared().is_revoked(&claims).await {
+        Ok(true) => Err(AppError::Auth("Token revogado".to_string())),
+        Ok(false) => Ok(claims),
+        Err(e) => {
+            tracing::error!("Denylist de tokens indisponível, token recusado: {}", e);
+            Err(AppError::Redis("Não foi possível verificar o token de acesso".to_string()))
+        }
+    }
+}
+
+// Claims do access token da requisição. Reaproveita as claims deixadas pelo
+// middleware quando ele já rodou.
+#[derive(Debug, Clone)]
+pub struct CurrentClaims(pub Claims);
+
+impl<S> FromRequestParts<S> for CurrentClaims
+where
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        if let Some(claims) = parts.extensions.get::<Claims>() {
+            return Ok(Self(claims.clone()));
+        }
+
+        authenticate(&parts.headers).await.map(Self)
+    }
+}
+
+pub struct AuthMiddleware;
+
+impl AuthMiddleware {
+    pub async fn auth(
+        State(db): State<DatabaseConnection>,
+        mut request: Request,
+        next: Next,
+    ) -> Result<Response, AppError> {
+        // Validar token
+        let claims = authenticate(request.headers()).await?;
+
+        // Buscar usuário no banco
+        let users_repo = UsersRepository::new(db);
+        let user = users_repo
+            .find_by_id(&claims.sub)
+            .await?
+            .ok_or_else(|| AppError::Auth("Usuário não encontrado".to_string()))?;
+
+        // Adicionar usuário ao request extensions
+        request.extensions_mut().insert(user);
+        request.extensions_mut().insert(claims);
+
+        Ok(next.run(request).await)
+    }
+}
+
+pub struct AdminMiddleware;
+
+impl AdminMiddleware {
+    pub async fn admin(
+        State(db): State<DatabaseConnection>,
+        mut request: Request,
+        next: Next,
+    ) -> Result<Response, AppError> {
+        // Validar token
+        let claims = authenticate(request.headers()).await?;
+
+        // Buscar usuário no banco
+        let users_repo = UsersRepository::new(db.clone());
+        let user = users_repo
+            .find_by_id(&claims.sub)
+            .await?
+            .ok_or_else(|| AppError::Auth("Usuário não encontrado".to_string()))?;
+
+        // Verificar se é admin: só grupos com `*` contam, a coluna role não
+        let permissions = PermissionResolver::shared().for_user(&db, &user.id).await?;
+        if !permissions.allows(ALL_PERMISSIONS) {
+            return Err(AppError::Forbidden("Acesso restrito a administradores".to_string()));
+        }
+
+        // Adicionar usuário ao request extensions
+        request.extensions_mut().insert(user);
+        request.extensions_mut().insert(claims);
+
+        Ok(next.run(request).await)
+    }
+}
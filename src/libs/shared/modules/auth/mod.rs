@@ -1,5 +1,27 @@
-pub mod jwt;
+pub mod middleware;
 pub mod access_control;
+pub mod client_ip;
+pub mod email_verification;
+pub mod login_throttle;
+pub mod mfa;
+pub mod one_time_tokens;
+pub mod password_policy;
+pub mod passwords;
+pub mod permissions;
+pub mod refresh_tokens;
+pub mod revocation;
+pub mod totp;
 
-pub use jwt::*;
+pub use middleware::*;
 pub use access_control::*;
+pub use client_ip::*;
+pub use email_verification::*;
+pub use login_throttle::*;
+pub use mfa::*;
+pub use one_time_tokens::*;
+pub use password_policy::*;
+pub use passwords::*;
+pub use permissions::*;
+pub use refresh_tokens::*;
+pub use revocation::*;
+pub use totp::*;
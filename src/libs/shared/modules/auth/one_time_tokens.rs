@@ -0,0 +1,73 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, Duration, Utc};
+use sea_orm::DatabaseConnection;
+use sha2::{Digest, Sha256};
+use crate::errors::AppError;
+use crate::libs::shared::database::repositories::user_tokens::UserTokensRepository;
+
+// Valores de user_tokens.purpose
+pub const PASSWORD_RESET: &str = "password_reset";
+pub const EMAIL_VERIFICATION: &str = "email_verification";
+pub const MFA_CHALLENGE: &str = "mfa_challenge";
+
+// Tokens de uso único enviados por e-mail. O token vai só na mensagem; o
+// banco guarda o SHA-256, então um dump da tabela não permite usá-los.
+// Emitir um token novo invalida os anteriores da mesma finalidade.
+pub struct OneTimeTokens {
+    repository: UserTokensRepository,
+}
+
+impl OneTimeTokens {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self {
+            repository: UserTokensRepository::new(db),
+        }
+    }
+
+    pub fn hash(token: &str) -> String {
+        hex::encode(Sha256::digest(token.as_bytes()))
+    }
+
+    // 256 bits aleatórios, seguros para ir numa URL
+    fn generate() -> String {
+        URL_SAFE_NO_PAD.encode(rand::random::<[u8; 32]>())
+    }
+
+    pub async fn issue(&self, user_id: &str, purpose: &str, ttl: Duration) -> Result<String, AppError> {
+        self.repository.revoke_for_user(user_id, purpose).await?;
+
+        let token = Self::generate();
+        self.repository
+            .create(user_id, purpose, &Self::hash(&token), Utc::now() + ttl)
+            .await?;
+
+        Ok(token)
+    }
+
+    // Id do usuário dono do token, se ele for válido; o token deixa de valer
+    pub async fn consume(&self, purpose: &str, token: &str) -> Result<Option<String>, AppError> {
+        let consumed = self.repository.consume(purpose, &Self::hash(token.trim())).await?;
+        Ok(consumed.map(|record| record.user_id))
+    }
+
+    // Id do dono de um token válido, sem consumi-lo (ex.: o desafio de 2FA
+    // continua valendo depois de um código digitado errado)
+    pub async fn peek(&self, purpose: &str, token: &str) -> Result<Option<String>, AppError> {
+        let record = self.repository.find_by_hash(&Self::hash(token.trim())).await?;
+
+        Ok(record
+            .filter(|record| record.purpose == purpose && record.used_at.is_none() && record.expires_at > Utc::now())
+            .map(|record| record.user_id))
+    }
+
+    // Quando foram emitidos os tokens da finalidade dentro da janela
+    pub async fn issued_since(&self, user_id: &str, purpose: &str, since: DateTime<Utc>) -> Result<Vec<DateTime<Utc>>, AppError> {
+        let tokens = self.repository.find_created_since(user_id, purpose, since).await?;
+        Ok(tokens.into_iter().map(|token| token.created_at).collect())
+    }
+
+    pub async fn revoke_all(&self, user_id: &str, purpose: &str) -> Result<u64, AppError> {
+        Ok(self.repository.revoke_for_user(user_id, purpose).await?)
+    }
+}
@@ -0,0 +1,270 @@
+use std::collections::HashSet;
+use std::sync::OnceLock;
+use serde::Deserialize;
+use crate::config::ProjectConfig;
+use crate::errors::{AppError, FieldError};
+
+static SHARED_PASSWORD_POLICY: OnceLock<PasswordPolicy> = OnceLock::new();
+
+// Lista embutida no binário; ver o cabeçalho do arquivo
+const COMMON_PASSWORDS: &str = include_str!("common_passwords.txt");
+
+// Partes do e-mail ou do nome menores que isso não contam como dado pessoal
+const MIN_PERSONAL_TOKEN_LENGTH: usize = 3;
+
+// Chaves password_* da seção [security] do config/project.toml. Sem a seção,
+// vale só o tamanho mínimo e as listas de bloqueio.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct PasswordPolicySettings {
+    #[serde(rename = "password_min_length")]
+    pub min_length: usize,
+    #[serde(rename = "password_max_length")]
+    pub max_length: usize,
+    #[serde(rename = "password_require_lowercase")]
+    pub require_lowercase: bool,
+    #[serde(rename = "password_require_uppercase")]
+    pub require_uppercase: bool,
+    #[serde(rename = "password_require_digit")]
+    pub require_digit: bool,
+    #[serde(rename = "password_require_symbol")]
+    pub require_symbol: bool,
+    // Recusa senhas que contenham o e-mail (ou a parte antes do @) ou o nome
+    #[serde(rename = "password_reject_personal_info")]
+    pub reject_personal_info: bool,
+    #[serde(rename = "password_reject_common")]
+    pub reject_common: bool,
+}
+
+impl Default for PasswordPolicySettings {
+    fn default() -> Self {
+        Self {
+            min_length: 8,
+            max_length: 128,
+            require_lowercase: false,
+            require_uppercase: false,
+            require_digit: false,
+            require_symbol: false,
+            reject_personal_info: true,
+            reject_common: true,
+        }
+    }
+}
+
+// Regras de senha do cadastro, da criação de usuário pelo admin e da troca
+// ou redefinição. Todas as regras violadas são devolvidas de uma vez, como
+// erros do campo informado.
+pub struct PasswordPolicy {
+    settings: PasswordPolicySettings,
+    common_passwords: HashSet<&'static str>,
+}
+
+impl PasswordPolicy {
+    pub fn new(settings: PasswordPolicySettings) -> Self {
+        let common_passwords = COMMON_PASSWORDS
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .collect();
+
+        Self {
+            settings,
+            common_passwords,
+        }
+    }
+
+    pub fn shared() -> &'static Self {
+        SHARED_PASSWORD_POLICY.get_or_init(|| Self::new(ProjectConfig::shared().security.password_policy.clone()))
+    }
+
+    pub fn settings(&self) -> &PasswordPolicySettings {
+        &self.settings
+    }
+
+    // `email` e `name` são os da conta dona da senha
+    pub fn check(&self, field: &str, password: &str, email: &str, name: &str) -> Vec<FieldError> {
+        let settings = &self.settings;
+        let mut errors = Vec::new();
+        let length = password.chars().count();
+
+        if length < settings.min_length {
+            errors.push(FieldError::new(
+                field,
+                "password_too_short",
+                format!("A senha deve ter pelo menos {} caracteres", settings.min_length),
+            ));
+        }
+
+        if length > settings.max_length {
+            errors.push(FieldError::new(
+                field,
+                "password_too_long",
+                format!("A senha deve ter no máximo {} caracteres", settings.max_length),
+            ));
+        }
+
+        let classes = [
+            (settings.require_lowercase, password.chars().any(char::is_lowercase), "password_missing_lowercase", "uma letra minúscula"),
+            (settings.require_uppercase, password.chars().any(char::is_uppercase), "password_missing_uppercase", "uma letra maiúscula"),
+            (settings.require_digit, password.chars().any(|c| c.is_ascii_digit()), "password_missing_digit", "um número"),
+            (settings.require_symbol, password.chars().any(|c| !c.is_alphanumeric() && !c.is_whitespace()), "password_missing_symbol", "um símbolo"),
+        ];
+
+        for (required, present, code, description) in classes {
+            if required && !present {
+                errors.push(FieldError::new(field, code, format!("A senha deve ter pelo menos {}", description)));
+            }
+        }
+
+        let normalized = password.to_lowercase();
+
+        if settings.reject_personal_info && contains_personal_info(&normalized, email, name) {
+            errors.push(FieldError::new(
+                field,
+                "password_contains_personal_info",
+                "A senha não pode conter o seu e-mail ou o seu nome",
+            ));
+        }
+
+        if settings.reject_common && self.common_passwords.contains(normalized.trim()) {
+            errors.push(FieldError::new(
+                field,
+                "password_too_common",
+                "Esta senha é muito comum; escolha outra",
+            ));
+        }
+
+        errors
+    }
+
+    pub fn validate(&self, field: &str, password: &str, email: &str, name: &str) -> Result<(), AppError> {
+        let errors = self.check(field, password, email, name);
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(AppError::InvalidFields(errors))
+        }
+    }
+}
+
+// O e-mail inteiro, a parte antes do @ e cada palavra do nome
+fn contains_personal_info(password: &str, email: &str, name: &str) -> bool {
+    let email = email.trim().to_lowercase();
+    let local_part = email.split('@').next().unwrap_or_default().to_string();
+
+    let name_parts = name
+        .split(|c: char| !c.is_alphanumeric())
+        .map(str::to_lowercase);
+
+    [email, local_part]
+        .into_iter()
+        .chain(name_parts)
+        .filter(|token| token.chars().count() >= MIN_PERSONAL_TOKEN_LENGTH)
+        .any(|token| password.contains(&token))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strict() -> PasswordPolicy {
+        PasswordPolicy::new(PasswordPolicySettings {
+            require_lowercase: true,
+            require_uppercase: true,
+            require_digit: true,
+            require_symbol: true,
+            ..Default::default()
+        })
+    }
+
+    fn codes(errors: &[FieldError]) -> Vec<&str> {
+        errors.iter().map(|error| error.code.as_ref()).collect()
+    }
+
+    #[test]
+    fn accepts_password_meeting_every_rule() {
+        let errors = strict().check("password", "Vento-Norte42", "ana@example.com", "Ana Souza");
+
+        assert!(errors.is_empty(), "{:?}", codes(&errors));
+    }
+
+    #[test]
+    fn reports_every_violated_rule_on_the_field() {
+        let errors = strict().check("new_password", "abc", "ana@example.com", "Ana Souza");
+
+        assert_eq!(
+            codes(&errors),
+            ["password_too_short", "password_missing_uppercase", "password_missing_digit", "password_missing_symbol"]
+        );
+        assert!(errors.iter().all(|error| error.field == "new_password"));
+    }
+
+    #[test]
+    fn counts_characters_not_bytes() {
+        let policy = PasswordPolicy::new(PasswordPolicySettings {
+            min_length: 4,
+            max_length: 4,
+            ..Default::default()
+        });
+
+        assert!(policy.check("password", "çãõé", "ana@example.com", "Ana").is_empty());
+        assert_eq!(codes(&policy.check("password", "çãõéü", "ana@example.com", "Ana")), ["password_too_long"]);
+    }
+
+    #[test]
+    fn whitespace_is_not_a_symbol() {
+        let errors = strict().check("password", "Vento Norte42", "ana@example.com", "Ana Souza");
+
+        assert_eq!(codes(&errors), ["password_missing_symbol"]);
+    }
+
+    #[test]
+    fn rejects_common_passwords_ignoring_case() {
+        let policy = PasswordPolicy::new(PasswordPolicySettings::default());
+
+        assert_eq!(codes(&policy.check("password", "PassWord1", "ana@example.com", "Ana")), ["password_too_common"]);
+    }
+
+    #[test]
+    fn common_list_skips_comments_and_blank_lines() {
+        let policy = PasswordPolicy::new(PasswordPolicySettings::default());
+
+        assert!(!policy.common_passwords.contains(""));
+        assert!(policy.common_passwords.iter().all(|password| !password.starts_with('#')));
+    }
+
+    #[test]
+    fn rejects_email_local_part_and_name_words() {
+        assert!(contains_personal_info("minhaanasouza!", "ana.souza@example.com", "Ana"));
+        assert!(contains_personal_info("xx-ana.souza-xx", "ana.souza@example.com", "Ana"));
+        assert!(contains_personal_info("souza2024!", "a@example.com", "Ana Souza"));
+        assert!(contains_personal_info("ana.souza@example.com1", "Ana.Souza@Example.com", ""));
+    }
+
+    #[test]
+    fn ignores_personal_tokens_shorter_than_the_minimum() {
+        // "Li" e "Bo" têm menos de 3 caracteres
+        assert!(!contains_personal_info("libo-senha-forte", "li@example.com", "Bo Li"));
+    }
+
+    #[test]
+    fn personal_info_check_can_be_disabled() {
+        let policy = PasswordPolicy::new(PasswordPolicySettings {
+            reject_personal_info: false,
+            ..Default::default()
+        });
+
+        assert!(policy.check("password", "souza-2024-x", "ana@example.com", "Ana Souza").is_empty());
+    }
+
+    #[test]
+    fn validate_wraps_errors_as_invalid_fields() {
+        let policy = PasswordPolicy::new(PasswordPolicySettings::default());
+
+        match policy.validate("password", "curta", "ana@example.com", "Ana") {
+            Err(AppError::InvalidFields(errors)) => assert_eq!(codes(&errors), ["password_too_short"]),
+            other => panic!("esperava InvalidFields, veio {:?}", other.err()),
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use std::sync::OnceLock;
+use argon2::password_hash::{PasswordHash, PasswordHasher as _, PasswordVerifier as _, SaltString};
+use argon2::{Algorithm, Argon2, Params, Version};
+use rand::rngs::OsRng;
+use crate::config::Config;
+use crate::errors::AppError;
+
+static SHARED_PASSWORD_HASHER: OnceLock<PasswordHasher> = OnceLock::new();
+
+// Custo do Argon2id (PASSWORD_ARGON2_* no .env)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PasswordHasherOptions {
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+}
+
+impl PasswordHasherOptions {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            memory_kib: config.password_argon2_memory_kib,
+            iterations: config.password_argon2_iterations,
+            parallelism: config.password_argon2_parallelism,
+        }
+    }
+}
+
+impl Default for PasswordHasherOptions {
+    // Mínimo recomendado pela OWASP para Argon2id
+    fn default() -> Self {
+        Self {
+            memory_kib: 19 * 1024,
+            iterations: 2,
+            parallelism: 1,
+        }
+    }
+}
+
+// Resultado da conferência de uma senha
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PasswordCheck {
+    pub valid: bool,
+    // Hash em bcrypt ou com parâmetros antigos: gerar de novo no próximo login
+    pub needs_rehash: bool,
+}
+
+// Hash de senhas em Argon2id, no formato PHC ($argon2id$v=19$m=...). Hashes
+// bcrypt ($2a$, $2b$, $2y$) de contas antigas continuam sendo conferidos.
+// O custo é de CPU e memória, então os métodos async rodam no pool de
+// threads bloqueantes do Tokio.
+#[derive(Debug, Clone)]
+pub struct PasswordHasher {
+    params: Params,
+}
+
+impl PasswordHasher {
+    pub fn new(options: PasswordHasherOptions) -> Result<Self, AppError> {
+        let params = Params::new(options.memory_kib, options.iterations, options.parallelism, None)
+            .map_err(|e| AppError::Config(format!("Parâmetros do Argon2 inválidos: {}", e)))?;
+
+        Ok(Self { params })
+    }
+
+    pub fn shared() -> &'static Self {
+        SHARED_PASSWORD_HASHER.get_or_init(|| {
+            let options = Config::from_env()
+                .map(|config| PasswordHasherOptions::from_config(&config))
+                .unwrap_or_default();
+
+            Self::new(options).unwrap_or_else(|e| {
+                tracing::warn!("{}; usando os parâmetros padrão", e);
+                Self::new(PasswordHasherOptions::default()).expect("Parâmetros padrão do Argon2")
+            })
+        })
+    }
+
+    fn argon2(&self) -> Argon2<'static> {
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, self.params.clone())
+    }
+
+    pub async fn hash(&self, password: &str) -> Result<String, AppError> {
+        let hasher = self.clone();
+        let password = password.to_string();
+
+        tokio::task::spawn_blocking(move || hasher.hash_blocking(&password))
+            .await
+            .map_err(|e| AppError::Internal(format!("Erro ao gerar hash da senha: {}", e)))?
+    }
+
+    pub async fn verify(&self, password: &str, password_hash: &str) -> Result<PasswordCheck, AppError> {
+        let hasher = self.clone();
+        let password = password.to_string();
+        let password_hash = password_hash.to_string();
+
+        tokio::task::spawn_blocking(move || hasher.verify_blocking(&password, &password_hash))
+            .await
+            .map_err(|e| AppError::Internal(format!("Erro ao verificar senha: {}", e)))?
+    }
+
+    // Confere a senha contra um hash fixo e descarta o resultado: o login de
+    // e-mail não cadastrado leva o mesmo tempo de uma senha errada
+    pub async fn verify_dummy(&self, password: &str) {
+        static DUMMY_PASSWORD_HASH: OnceLock<String> = OnceLock::new();
+
+        let dummy_hash = DUMMY_PASSWORD_HASH
+            .get_or_init(|| self.hash_blocking("dummy-password").unwrap_or_default())
+            .clone();
+
+        let _ = self.verify(password, &dummy_hash).await;
+    }
+
+    pub fn hash_blocking(&self, password: &str) -> Result<String, AppError> {
+        let salt = SaltString::generate(&mut OsRng);
+
+        self.argon2()
+            .hash_password(password.as_bytes(), &salt)
+            .map(|hash| hash.to_string())
+            .map_err(|e| AppError::Internal(format!("Erro ao gerar hash da senha: {}", e)))
+    }
+
+    pub fn verify_blocking(&self, password: &str, password_hash: &str) -> Result<PasswordCheck, AppError> {
+        if is_bcrypt(password_hash) {
+            let valid = bcrypt::verify(password.as_bytes(), password_hash)?;
+            return Ok(PasswordCheck { valid, needs_rehash: valid });
+        }
+
+        let parsed = PasswordHash::new(password_hash)
+            .map_err(|e| AppError::Internal(format!("Hash de senha inválido: {}", e)))?;
+
+        // Os parâmetros vêm do próprio hash; os atuais só decidem o rehash
+        let valid = Argon2::default()
+            .verify_password(password.as_bytes(), &parsed)
+            .is_ok();
+
+        Ok(PasswordCheck {
+            valid,
+            needs_rehash: valid && !self.is_current(&parsed),
+        })
+    }
+
+    fn is_current(&self, parsed: &PasswordHash) -> bool {
+        parsed.algorithm == Algorithm::Argon2id.ident()
+            && parsed.version == Some(Version::V0x13.into())
+            && Params::try_from(parsed).is_ok_and(|params| {
+                params.m_cost() == self.params.m_cost()
+                    && params.t_cost() == self.params.t_cost()
+                    && params.p_cost() == self.params.p_cost()
+            })
+    }
+}
+
+fn is_bcrypt(password_hash: &str) -> bool {
+    ["$2a$", "$2b$", "$2x$", "$2y$"]
+        .iter()
+        .any(|prefix| password_hash.starts_with(prefix))
+}
@@ -0,0 +1,318 @@
+use std::collections::BTreeSet;
+use std::sync::OnceLock;
+use std::time::Duration;
+use axum::{
+    extract::{FromRef, FromRequestParts},
+    http::request::Parts,
+};
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use crate::errors::AppError;
+use crate::libs::shared::cache::invalidation_bus::{access_group_tag, user_tag};
+use crate::libs::shared::cache::tiered_cache::TieredCache;
+use crate::libs::shared::database::repositories::access_groups::AccessGroupsRepository;
+use crate::libs::shared::database::repositories::users::UsersRepository;
+use super::middleware::CurrentClaims;
+
+pub const VIDEOS_READ: &str = "videos:read";
+pub const VIDEOS_WRITE: &str = "videos:write";
+pub const VIDEOS_PUBLISH: &str = "videos:publish";
+pub const USERS_READ: &str = "users:read";
+pub const USERS_MANAGE: &str = "users:manage";
+pub const GROUPS_MANAGE: &str = "groups:manage";
+
+// Libera qualquer permissão; `recurso:*` libera as ações do recurso
+pub const ALL_PERMISSIONS: &str = "*";
+
+pub const KNOWN_PERMISSIONS: &[&str] = &[
+    VIDEOS_READ,
+    VIDEOS_WRITE,
+    VIDEOS_PUBLISH,
+    USERS_READ,
+    USERS_MANAGE,
+    GROUPS_MANAGE,
+];
+
+// Todo usuário autenticado pode ver o catálogo
+const BASELINE_PERMISSIONS: &[&str] = &[VIDEOS_READ];
+
+const PERMISSIONS_CACHE_TTL: Duration = Duration::from_secs(300);
+
+static SHARED_PERMISSION_RESOLVER: OnceLock<PermissionResolver> = OnceLock::new();
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PermissionSet(BTreeSet<String>);
+
+impl PermissionSet {
+    pub fn new<I, P>(permissions: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<String>,
+    {
+        Self(permissions.into_iter().map(Into::into).collect())
+    }
+
+    pub fn insert(&mut self, permission: impl Into<String>) {
+        self.0.insert(permission.into());
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(String::as_str)
+    }
+
+    pub fn allows(&self, permission: &str) -> bool {
+        if self.0.contains(ALL_PERMISSIONS) || self.0.contains(permission) {
+            return true;
+        }
+
+        match permission.split_once(':') {
+            Some((resource, _)) => self.0.contains(&format!("{}:*", resource)),
+            None => false,
+        }
+    }
+
+    pub fn allows_all(&self, permissions: &[&str]) -> bool {
+        permissions.iter().all(|permission| self.allows(permission))
+    }
+
+    // Checagem dentro do handler, para regras que dependem do corpo
+    pub fn require(&self, permission: &str) -> Result<(), AppError> {
+        if self.allows(permission) {
+            Ok(())
+        } else {
+            Err(AppError::Forbidden(format!("Permissão necessária: {}", permission)))
+        }
+    }
+
+    // Verdadeiro quando este conjunto libera tudo o que `other` libera
+    pub fn covers(&self, other: &PermissionSet) -> bool {
+        other.iter().all(|permission| self.allows(permission))
+    }
+
+    // Quem altera ou remove uma conta precisa ter ao menos as permissões
+    // dela; sem isso users:manage bastaria para tomar a conta de um admin
+    pub fn require_covers(&self, other: &PermissionSet) -> Result<(), AppError> {
+        if self.covers(other) {
+            Ok(())
+        } else {
+            Err(AppError::Forbidden("A conta tem permissões que você não possui".to_string()))
+        }
+    }
+}
+
+impl<P: Into<String>> Extend<P> for PermissionSet {
+    fn extend<I: IntoIterator<Item = P>>(&mut self, permissions: I) {
+        self.0.extend(permissions.into_iter().map(Into::into));
+    }
+}
+
+// `*`, uma permissão conhecida ou `recurso:*` de um recurso conhecido
+pub fn is_valid_permission(permission: &str) -> bool {
+    if permission == ALL_PERMISSIONS || KNOWN_PERMISSIONS.contains(&permission) {
+        return true;
+    }
+
+    match permission.split_once(':') {
+        Some((resource, "*")) => KNOWN_PERMISSIONS
+            .iter()
+            .any(|known| known.split_once(':').is_some_and(|(known_resource, _)| known_resource == resource)),
+        _ => false,
+    }
+}
+
+// Coluna access_groups.permissions: lista (`["videos:read"]`) ou objeto
+// (`{"videos:read": true}`). Vazio ou `null` equivale a nenhuma permissão.
+pub fn parse_permissions(raw: &str) -> Result<Vec<String>, String> {
+    if raw.trim().is_empty() {
+        return Ok(vec![]);
+    }
+
+    let value: serde_json::Value = serde_json::from_str(raw).map_err(|e| e.to_string())?;
+
+    let permissions = match value {
+        serde_json::Value::Null => vec![],
+        serde_json::Value::Array(items) => items
+            .into_iter()
+            .map(|item| match item {
+                serde_json::Value::String(permission) => Ok(permission),
+                other => Err(format!("permissão deve ser texto, recebido {}", other)),
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+        serde_json::Value::Object(entries) => {
+            let mut permissions = Vec::new();
+            for (permission, enabled) in entries {
+                match enabled {
+                    serde_json::Value::Bool(true) => permissions.push(permission),
+                    serde_json::Value::Bool(false) => {}
+                    other => return Err(format!("valor de {} deve ser booleano, recebido {}", permission, other)),
+                }
+            }
+            permissions
+        }
+        other => return Err(format!("esperado lista ou objeto, recebido {}", other)),
+    };
+
+    match permissions.iter().find(|permission| !is_valid_permission(permission)) {
+        Some(unknown) => Err(format!("permissão desconhecida: {}", unknown)),
+        None => Ok(permissions),
+    }
+}
+
+// Valor guardado no cache: os grupos entram nas tags para que a edição de um
+// grupo invalide as permissões de todos os membros
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ResolvedPermissions {
+    group_ids: Vec<String>,
+    permissions: PermissionSet,
+}
+
+// Permissões efetivas do usuário: base + união dos grupos. A coluna `role`
+// não concede nada; administrador é quem está num grupo com `*`.
+//
+// O resultado fica no TieredCache por alguns minutos, com as tags
+// `user:{id}` e `access_group:{id}`; mudanças de vínculo ou de grupo feitas
+// pelos repositórios limpam a memória das instâncias pelo barramento.
+pub struct PermissionResolver {
+    cache: TieredCache,
+    ttl: Duration,
+}
+
+impl PermissionResolver {
+    pub fn new(cache: TieredCache, ttl: Duration) -> Self {
+        Self { cache, ttl }
+    }
+
+    pub fn shared() -> &'static Self {
+        SHARED_PERMISSION_RESOLVER.get_or_init(|| Self::new(TieredCache::shared(), PERMISSIONS_CACHE_TTL))
+    }
+
+    fn cache_key(user_id: &str) -> String {
+        format!("auth:permissions:v1:{}", user_id)
+    }
+
+    pub async fn for_user(&self, db: &DatabaseConnection, user_id: &str) -> Result<PermissionSet, AppError> {
+        let tag_user_id = user_id.to_string();
+        let load_user_id = user_id.to_string();
+        let db = db.clone();
+
+        let resolved = self
+            .cache
+            .get_or_load(
+                &Self::cache_key(user_id),
+                self.ttl,
+                move |resolved: &ResolvedPermissions| {
+                    let mut tags = vec![user_tag(&tag_user_id)];
+                    tags.extend(resolved.group_ids.iter().map(|group_id| access_group_tag(group_id)));
+                    tags
+                },
+                move || {
+                    let db = db.clone();
+                    let user_id = load_user_id.clone();
+                    async move { resolve_permissions(&db, &user_id).await }
+                },
+            )
+            .await?;
+
+        Ok(resolved.permissions)
+    }
+
+    // Remove das duas camadas; a memória das outras instâncias é limpa pelo
+    // evento que o repositório publica
+    pub async fn invalidate_user(&self, user_id: &str) {
+        if let Err(e) = self.cache.invalidate(&[user_tag(user_id)]).await {
+            tracing::warn!("Erro ao invalidar permissões do usuário {}: {}", user_id, e);
+        }
+    }
+
+    pub async fn invalidate_group(&self, group_id: &str) {
+        if let Err(e) = self.cache.invalidate(&[access_group_tag(group_id)]).await {
+            tracing::warn!("Erro ao invalidar permissões do grupo {}: {}", group_id, e);
+        }
+    }
+}
+
+async fn resolve_permissions(db: &DatabaseConnection, user_id: &str) -> Result<ResolvedPermissions, AppError> {
+    UsersRepository::new(db.clone())
+        .find_by_id(user_id)
+        .await?
+        .ok_or_else(|| AppError::Auth("Usuário não encontrado".to_string()))?;
+
+    let groups = AccessGroupsRepository::new(db.clone()).find_by_user(user_id).await?;
+
+    let mut permissions = PermissionSet::new(BASELINE_PERMISSIONS.iter().copied());
+
+    for group in &groups {
+        let raw = match group.permissions.as_deref() {
+            Some(raw) => raw,
+            None => continue,
+        };
+
+        // Um grupo mal configurado não concede nada, mas não bloqueia os demais
+        match parse_permissions(raw) {
+            Ok(group_permissions) => permissions.extend(group_permissions),
+            Err(e) => tracing::warn!("Permissões inválidas no grupo {} ({}): {}", group.name, group.id, e),
+        }
+    }
+
+    Ok(ResolvedPermissions {
+        group_ids: groups.into_iter().map(|group| group.id).collect(),
+        permissions,
+    })
+}
+
+// Permissões efetivas do usuário autenticado. Em rotas com
+// `require_permission` o guard já deixa o conjunto nas extensions.
+impl<S> FromRequestParts<S> for PermissionSet
+where
+    DatabaseConnection: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        if let Some(permissions) = parts.extensions.get::<PermissionSet>() {
+            return Ok(permissions.clone());
+        }
+
+        let CurrentClaims(claims) = CurrentClaims::from_request_parts(parts, state).await?;
+        let db = DatabaseConnection::from_ref(state);
+
+        let permissions = PermissionResolver::shared().for_user(&db, &claims.sub).await?;
+
+        parts.extensions.insert(permissions.clone());
+        Ok(permissions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wildcard_covers_everything() {
+        let admin = PermissionSet::new([ALL_PERMISSIONS]);
+        let manager = PermissionSet::new([VIDEOS_READ, USERS_MANAGE]);
+
+        assert!(admin.covers(&manager));
+        assert!(admin.covers(&admin));
+        assert!(!manager.covers(&admin));
+    }
+
+    #[test]
+    fn resource_wildcard_covers_its_actions_only() {
+        let videos = PermissionSet::new(["videos:*"]);
+
+        assert!(videos.covers(&PermissionSet::new([VIDEOS_WRITE, VIDEOS_PUBLISH])));
+        assert!(!videos.covers(&PermissionSet::new([USERS_MANAGE])));
+        assert!(!PermissionSet::new([VIDEOS_WRITE]).covers(&videos));
+    }
+
+    #[test]
+    fn manager_cannot_act_on_wider_account() {
+        let manager = PermissionSet::new([VIDEOS_READ, USERS_MANAGE]);
+        let moderator = PermissionSet::new([VIDEOS_READ, VIDEOS_WRITE]);
+
+        assert!(manager.require_covers(&PermissionSet::new([VIDEOS_READ])).is_ok());
+        assert!(matches!(manager.require_covers(&moderator), Err(AppError::Forbidden(_))));
+    }
+}
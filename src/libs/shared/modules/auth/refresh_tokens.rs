@@ -0,0 +1,396 @@
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::OnceLock;
+use axum::extract::{ConnectInfo, FromRequestParts};
+use axum::http::{header, request::Parts, HeaderMap};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::errors::AppError;
+use crate::libs::shared::cache::redis_cache::RedisCache;
+use crate::libs::shared::modules::token_service::{Claims, IssuedTokens, TokenPair, TokenService, TokenSubject};
+use super::client_ip::TrustedProxies;
+use super::revocation::RevocationList;
+
+static SHARED_REFRESH_STORE: OnceLock<RefreshTokenStore> = OnceLock::new();
+
+// Troca o refresh token usado pelo novo numa operação só: se a gravação do
+// novo par falhar, o token antigo continua valendo.
+//
+//   KEYS = refresh usado, refresh novo, sessão, sessões do usuário
+//   ARGV = registro novo, sessão (JSON), sid, TTL em segundos
+//
+// Retorna 1 na troca, 0 se o token usado já foi consumido e -1 se a sessão
+// foi encerrada.
+const ROTATE_SCRIPT: &str = r#"
+if redis.call('EXISTS', KEYS[3]) == 0 then
+    return -1
+end
+
+if redis.call('DEL', KEYS[1]) == 0 then
+    return 0
+end
+
+redis.call('SET', KEYS[2], ARGV[1], 'EX', ARGV[4])
+redis.call('SET', KEYS[3], ARGV[2], 'EX', ARGV[4])
+redis.call('SADD', KEYS[4], ARGV[3])
+redis.call('EXPIRE', KEYS[4], ARGV[4], 'NX')
+redis.call('EXPIRE', KEYS[4], ARGV[4], 'GT')
+
+return 1
+"#;
+
+// Refresh token ainda não usado: aponta para a sessão que o emitiu
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RefreshRecord {
+    session_id: String,
+    user_id: String,
+}
+
+// Uma sessão é a família de refresh tokens gerada a partir de um login.
+// Só o último token emitido (`current_jti`) é válido.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Session {
+    pub id: String,
+    pub user_id: String,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    #[serde(default)]
+    pub current_jti: String,
+    #[serde(default)]
+    pub device: Option<String>,
+    #[serde(default)]
+    pub ip: Option<String>,
+    #[serde(default)]
+    pub user_agent: Option<String>,
+}
+
+// Origem da requisição que abriu ou renovou a sessão. `ip` é só para
+// exibição e vem dos headers do jeito que chegaram; limites e bloqueios usam
+// `remote_ip`, que só aceita esses headers de um proxy confiável.
+#[derive(Debug, Clone, Default)]
+pub struct ClientInfo {
+    pub device: Option<String>,
+    pub ip: Option<String>,
+    pub remote_ip: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+pub const DEVICE_NAME_HEADER: &str = "x-device-name";
+
+impl ClientInfo {
+    pub fn from_headers(headers: &HeaderMap, peer: Option<SocketAddr>) -> Self {
+        let header_value = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|h| h.to_str().ok())
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+
+        // Atrás do proxy, o primeiro endereço do X-Forwarded-For é o cliente
+        let ip = header_value("x-forwarded-for")
+            .and_then(|value| value.split(',').next().map(|ip| ip.trim().to_string()))
+            .or_else(|| header_value("x-real-ip"))
+            .or_else(|| peer.map(|addr| addr.ip().to_string()));
+
+        let remote_ip = TrustedProxies::shared()
+            .client_ip(headers, peer)
+            .map(|ip| ip.to_string());
+
+        let user_agent = header_value(header::USER_AGENT.as_str());
+        let device = header_value(DEVICE_NAME_HEADER)
+            .or_else(|| user_agent.as_deref().and_then(Self::device_from_user_agent));
+
+        Self { device, ip, remote_ip, user_agent }
+    }
+
+    fn device_from_user_agent(user_agent: &str) -> Option<String> {
+        let known = [
+            ("iPhone", "iPhone"),
+            ("iPad", "iPad"),
+            ("Android", "Android"),
+            ("Windows", "Windows"),
+            ("Mac OS", "macOS"),
+            ("CrOS", "ChromeOS"),
+            ("Linux", "Linux"),
+        ];
+
+        known
+            .iter()
+            .find(|(pattern, _)| user_agent.contains(pattern))
+            .map(|(_, device)| device.to_string())
+    }
+}
+
+impl<S> FromRequestParts<S> for ClientInfo
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let peer = parts
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| *addr);
+
+        Ok(Self::from_headers(&parts.headers, peer))
+    }
+}
+
+// Rotação de refresh tokens com detecção de reuso, guardada no Redis:
+//
+//   auth:refresh:{jti}            -> RefreshRecord (removido ao ser usado)
+//   auth:session:{sid}            -> Session
+//   auth:user_sessions:{user_id}  -> SET com os sids do usuário
+//
+// Cada refresh troca o token por um par novo na mesma sessão, num script Lua
+// que remove o antigo e grava o novo juntos. Um token que já foi consumido
+// mas pertence a uma sessão ainda viva indica roubo: a sessão inteira é
+// revogada. Sem Redis, o refresh falha (503).
+pub struct RefreshTokenStore {
+    tokens: &'static TokenService,
+    revocations: &'static RevocationList,
+    rotate_script: redis::Script,
+}
+
+impl RefreshTokenStore {
+    pub fn new(tokens: &'static TokenService, revocations: &'static RevocationList) -> Self {
+        Self {
+            tokens,
+            revocations,
+            rotate_script: redis::Script::new(ROTATE_SCRIPT),
+        }
+    }
+
+    pub fn shared() -> &'static Self {
+        SHARED_REFRESH_STORE.get_or_init(|| Self::new(TokenService::shared(), RevocationList::shared()))
+    }
+
+    fn refresh_key(jti: &str) -> String {
+        format!("auth:refresh:{}", jti)
+    }
+
+    fn session_key(session_id: &str) -> String {
+        format!("auth:session:{}", session_id)
+    }
+
+    fn user_sessions_key(user_id: &str) -> String {
+        format!("auth:user_sessions:{}", user_id)
+    }
+
+    // Segundos até `exp`, com no mínimo 1 (SETEX não aceita zero)
+    fn ttl_until(exp: i64) -> u64 {
+        (exp - Utc::now().timestamp()).max(1) as u64
+    }
+
+    // Abre uma sessão nova (login, registro) e emite o primeiro par
+    pub async fn start_session(&self, subject: &TokenSubject, client: &ClientInfo) -> Result<TokenPair, AppError> {
+        let cache = RedisCache::shared().await?;
+        let now = Utc::now();
+
+        let mut subject = subject.clone();
+        let session_id = Uuid::new_v4().to_string();
+        subject.session_id = Some(session_id.clone());
+
+        let issued = self.tokens.issue_pair_with_claims(&subject)?;
+
+        let mut session = Session {
+            id: session_id,
+            user_id: subject.user_id.clone(),
+            created_at: now,
+            last_used_at: now,
+            expires_at: now,
+            current_jti: String::new(),
+            device: client.device.clone(),
+            ip: client.ip.clone(),
+            user_agent: client.user_agent.clone(),
+        };
+        session.record(&issued);
+
+        self.store(&cache, &session, &issued.refresh_claims).await?;
+        Ok(issued.pair)
+    }
+
+    // Valida o refresh token sem consumi-lo. Retorna as claims para que o
+    // chamador recarregue o usuário e chame `rotate`, que é quem consome.
+    pub async fn validate(&self, refresh_token: &str) -> Result<Claims, AppError> {
+        let claims = self.tokens.validate_refresh_token(refresh_token)?;
+
+        // Tokens emitidos antes da rotação não têm sessão
+        let session_id = claims
+            .sid
+            .clone()
+            .ok_or_else(|| AppError::Auth("Refresh token sem sessão; faça login novamente".to_string()))?;
+
+        let cache = RedisCache::shared().await?;
+        let record: Option<RefreshRecord> = cache.get(Self::refresh_key(&claims.jti)).await?;
+
+        match record {
+            Some(record) if record.session_id == session_id && record.user_id == claims.sub => Ok(claims),
+            Some(_) => Err(AppError::Auth("Refresh token inválido".to_string())),
+            None => Err(self.reject_reuse(&cache, &claims.sub, &session_id).await),
+        }
+    }
+
+    // Consome o refresh token de `refresh_claims` e emite um novo par na
+    // sessão de `subject.session_id`
+    pub async fn rotate(&self, refresh_claims: &Claims, subject: &TokenSubject, client: &ClientInfo) -> Result<TokenPair, AppError> {
+        let session_id = subject
+            .session_id
+            .as_deref()
+            .ok_or_else(|| AppError::Internal("Rotação de refresh token sem sessão".to_string()))?;
+
+        let cache = RedisCache::shared().await?;
+
+        // A sessão pode ter sido revogada depois da validação; o script
+        // confere de novo
+        let mut session: Session = cache
+            .get(Self::session_key(session_id))
+            .await?
+            .ok_or_else(|| AppError::Auth("Sessão encerrada".to_string()))?;
+
+        let issued = self.tokens.issue_pair_with_claims(subject)?;
+
+        session.last_used_at = Utc::now();
+        session.record(&issued);
+        if client.ip.is_some() {
+            session.ip = client.ip.clone();
+        }
+        if client.user_agent.is_some() {
+            session.user_agent = client.user_agent.clone();
+        }
+
+        let record = RefreshRecord {
+            session_id: session.id.clone(),
+            user_id: session.user_id.clone(),
+        };
+        let to_json = |e: serde_json::Error| AppError::Internal(format!("Erro ao serializar sessão: {}", e));
+        let keys = [
+            Self::refresh_key(&refresh_claims.jti),
+            Self::refresh_key(&issued.refresh_claims.jti),
+            Self::session_key(&session.id),
+            Self::user_sessions_key(&session.user_id),
+        ];
+        let args = [
+            serde_json::to_string(&record).map_err(to_json)?,
+            serde_json::to_string(&session).map_err(to_json)?,
+            session.id.clone(),
+            Self::ttl_until(issued.refresh_claims.exp).to_string(),
+        ];
+
+        match cache.run_script::<i64, _>(&self.rotate_script, &keys, &args).await? {
+            1 => Ok(issued.pair),
+            // Outra requisição usou o mesmo token entre a validação e a troca
+            0 => Err(self.reject_reuse(&cache, &refresh_claims.sub, session_id).await),
+            _ => Err(AppError::Auth("Sessão encerrada".to_string())),
+        }
+    }
+
+    // Token já consumido de uma sessão viva: revoga a sessão
+    async fn reject_reuse(&self, cache: &RedisCache, user_id: &str, session_id: &str) -> AppError {
+        match cache.exists(Self::session_key(session_id)).await {
+            Ok(true) => {
+                tracing::warn!(
+                    "Reuso de refresh token detectado (usuário {}, sessão {}); sessão revogada",
+                    user_id,
+                    session_id
+                );
+                if let Err(e) = self.revoke_session(user_id, session_id).await {
+                    return e;
+                }
+            }
+            Ok(false) => {}
+            Err(e) => return e.into(),
+        }
+
+        AppError::Auth("Refresh token inválido ou já utilizado".to_string())
+    }
+
+    async fn store(&self, cache: &RedisCache, session: &Session, refresh_claims: &Claims) -> Result<(), AppError> {
+        let ttl = Self::ttl_until(refresh_claims.exp);
+        let record = RefreshRecord {
+            session_id: session.id.clone(),
+            user_id: session.user_id.clone(),
+        };
+
+        cache.set_with_ttl(Self::session_key(&session.id), session, ttl).await?;
+        cache.set_with_ttl(Self::refresh_key(&refresh_claims.jti), &record, ttl).await?;
+        cache.add_to_set(Self::user_sessions_key(&session.user_id), &session.id, ttl).await?;
+
+        Ok(())
+    }
+
+    pub async fn find_session(&self, session_id: &str) -> Result<Option<Session>, AppError> {
+        let cache = RedisCache::shared().await?;
+        Ok(cache.get(Self::session_key(session_id)).await?)
+    }
+
+    // Sessões ativas do usuário; remove do SET as que já expiraram
+    pub async fn list_sessions(&self, user_id: &str) -> Result<Vec<Session>, AppError> {
+        let cache = RedisCache::shared().await?;
+        let user_sessions_key = Self::user_sessions_key(user_id);
+        let mut sessions = Vec::new();
+
+        for session_id in cache.set_members(&user_sessions_key).await? {
+            match cache.get::<_, Session>(Self::session_key(&session_id)).await? {
+                Some(session) => sessions.push(session),
+                None => {
+                    cache.remove_from_set(&user_sessions_key, &session_id).await?;
+                }
+            }
+        }
+
+        sessions.sort_by_key(|session| std::cmp::Reverse(session.last_used_at));
+        Ok(sessions)
+    }
+
+    // Revoga a sessão, o refresh token atual e os access tokens emitidos
+    // nela. Retorna false se a sessão não existe ou não pertence ao usuário.
+    pub async fn revoke_session(&self, user_id: &str, session_id: &str) -> Result<bool, AppError> {
+        let cache = RedisCache::shared().await?;
+
+        let session: Option<Session> = cache.get(Self::session_key(session_id)).await?;
+        let session = match session {
+            Some(session) if session.user_id == user_id => session,
+            _ => return Ok(false),
+        };
+
+        if !session.current_jti.is_empty() {
+            cache.delete(Self::refresh_key(&session.current_jti)).await?;
+        }
+        cache.delete(Self::session_key(session_id)).await?;
+        cache.remove_from_set(Self::user_sessions_key(user_id), session_id).await?;
+        self.revocations.revoke_session(session_id).await?;
+
+        Ok(true)
+    }
+
+    // Revoga todas as sessões do usuário e todos os access tokens emitidos
+    // até agora; retorna quantas sessões foram encerradas
+    pub async fn revoke_all(&self, user_id: &str) -> Result<usize, AppError> {
+        let cache = RedisCache::shared().await?;
+        let mut revoked = 0;
+
+        for session_id in cache.set_members(Self::user_sessions_key(user_id)).await? {
+            if self.revoke_session(user_id, &session_id).await? {
+                revoked += 1;
+            }
+        }
+
+        cache.delete(Self::user_sessions_key(user_id)).await?;
+        self.revocations.revoke_user(user_id).await?;
+
+        Ok(revoked)
+    }
+}
+
+impl Session {
+    // Registra o par recém-emitido como o atual da sessão
+    fn record(&mut self, issued: &IssuedTokens) {
+        self.expires_at = DateTime::from_timestamp(issued.refresh_claims.exp, 0).unwrap_or(self.expires_at);
+        self.current_jti = issued.refresh_claims.jti.clone();
+    }
+}
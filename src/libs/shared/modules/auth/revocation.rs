@@ -0,0 +1,84 @@
+use std::sync::OnceLock;
+use chrono::Utc;
+use crate::errors::AppError;
+use crate::libs::shared::cache::redis_cache::RedisCache;
+use crate::libs::shared::modules::token_service::{Claims, TokenService};
+
+static SHARED_REVOCATION_LIST: OnceLock<RevocationList> = OnceLock::new();
+
+// Denylist de access tokens no Redis. Cada chave guarda o timestamp da
+// revogação e expira junto com o último token que ela pode afetar:
+//
+//   auth:revoked:jti:{jti}        -> token revogado (logout)
+//   auth:revoked:sid:{sid}        -> todos os tokens da sessão
+//   auth:revoked:user:{user_id}   -> tokens do usuário emitidos antes do timestamp
+pub struct RevocationList {
+    tokens: &'static TokenService,
+}
+
+impl RevocationList {
+    pub fn new(tokens: &'static TokenService) -> Self {
+        Self { tokens }
+    }
+
+    pub fn shared() -> &'static Self {
+        SHARED_REVOCATION_LIST.get_or_init(|| Self::new(TokenService::shared()))
+    }
+
+    fn jti_key(jti: &str) -> String {
+        format!("auth:revoked:jti:{}", jti)
+    }
+
+    fn session_key(session_id: &str) -> String {
+        format!("auth:revoked:sid:{}", session_id)
+    }
+
+    fn user_key(user_id: &str) -> String {
+        format!("auth:revoked:user:{}", user_id)
+    }
+
+    pub async fn revoke_token(&self, jti: &str, exp: i64) -> Result<(), AppError> {
+        let ttl = (exp - Utc::now().timestamp()).max(1) as u64;
+        let cache = RedisCache::shared().await?;
+        cache.set_with_ttl(Self::jti_key(jti), &Utc::now().timestamp(), ttl).await?;
+        Ok(())
+    }
+
+    // Invalida todos os access tokens já emitidos na sessão
+    pub async fn revoke_session(&self, session_id: &str) -> Result<(), AppError> {
+        let cache = RedisCache::shared().await?;
+        cache
+            .set_with_ttl(Self::session_key(session_id), &Utc::now().timestamp(), self.tokens.access_ttl_seconds().max(1))
+            .await?;
+        Ok(())
+    }
+
+    // Invalida todos os access tokens do usuário emitidos até agora,
+    // inclusive os que não pertencem a nenhuma sessão
+    pub async fn revoke_user(&self, user_id: &str) -> Result<(), AppError> {
+        let cache = RedisCache::shared().await?;
+        cache
+            .set_with_ttl(Self::user_key(user_id), &Utc::now().timestamp(), self.tokens.access_ttl_seconds().max(1))
+            .await?;
+        Ok(())
+    }
+
+    // Consulta as três chaves num único MGET
+    pub async fn is_revoked(&self, claims: &Claims) -> Result<bool, AppError> {
+        let mut keys = vec![Self::jti_key(&claims.jti), Self::user_key(&claims.sub)];
+        if let Some(session_id) = &claims.sid {
+            keys.push(Self::session_key(session_id));
+        }
+
+        let cache = RedisCache::shared().await?;
+        let revoked_at: Vec<Option<i64>> = cache.get_many(&keys).await?;
+
+        let token_revoked = revoked_at[0].is_some();
+        // O iat só tem segundos: um token do mesmo segundo do corte pode ter
+        // sido emitido antes dele, então também cai
+        let user_revoked = revoked_at[1].is_some_and(|cutoff| claims.iat <= cutoff);
+        let session_revoked = revoked_at.get(2).is_some_and(|revoked| revoked.is_some());
+
+        Ok(token_revoked || user_revoked || session_revoked)
+    }
+}
@@ -0,0 +1,108 @@
+use chrono::{DateTime, Utc};
+use data_encoding::BASE32_NOPAD;
+use hmac::{Hmac, Mac};
+use qrcode::render::svg;
+use qrcode::QrCode;
+use sha1::Sha1;
+use crate::errors::AppError;
+
+// Parâmetros padrão dos aplicativos autenticadores (RFC 6238)
+pub const DIGITS: usize = 6;
+pub const STEP_SECONDS: i64 = 30;
+// Passos aceitos antes e depois do atual, para relógios fora de sincronia
+const ALLOWED_SKEW: i64 = 1;
+
+// Códigos TOTP (HMAC-SHA1, 6 dígitos, 30 segundos)
+pub struct Totp {
+    secret: Vec<u8>,
+}
+
+impl Totp {
+    // 160 bits aleatórios em base32, o formato que os aplicativos esperam
+    pub fn generate_secret() -> String {
+        BASE32_NOPAD.encode(&rand::random::<[u8; 20]>())
+    }
+
+    pub fn from_base32(secret: &str) -> Result<Self, AppError> {
+        let normalized = secret.trim().trim_end_matches('=').to_ascii_uppercase();
+        let secret = BASE32_NOPAD
+            .decode(normalized.as_bytes())
+            .map_err(|e| AppError::Internal(format!("Segredo TOTP inválido: {}", e)))?;
+
+        Ok(Self { secret })
+    }
+
+    pub fn step_at(time: DateTime<Utc>) -> i64 {
+        time.timestamp().div_euclid(STEP_SECONDS)
+    }
+
+    pub fn code_at_step(&self, step: i64) -> String {
+        let mut mac = Hmac::<Sha1>::new_from_slice(&self.secret).expect("HMAC aceita chave de qualquer tamanho");
+        mac.update(&step.to_be_bytes());
+        let digest = mac.finalize().into_bytes();
+
+        // Truncamento dinâmico da RFC 4226
+        let offset = (digest[digest.len() - 1] & 0x0f) as usize;
+        let binary = u32::from_be_bytes([
+            digest[offset] & 0x7f,
+            digest[offset + 1],
+            digest[offset + 2],
+            digest[offset + 3],
+        ]);
+
+        format!("{:0width$}", binary % 10u32.pow(DIGITS as u32), width = DIGITS)
+    }
+
+    // Passo em que o código bate, se bater. Quem chama deve recusar passos
+    // já usados (ver `UserMfaRepository::record_step`).
+    pub fn verify(&self, code: &str, now: DateTime<Utc>) -> Option<i64> {
+        let code: String = code.chars().filter(|c| !c.is_whitespace()).collect();
+        if code.len() != DIGITS || !code.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+
+        let current = Self::step_at(now);
+        (current - ALLOWED_SKEW..=current + ALLOWED_SKEW)
+            .find(|step| constant_time_eq(self.code_at_step(*step).as_bytes(), code.as_bytes()))
+    }
+}
+
+// URI otpauth:// lida pelos aplicativos autenticadores (Google Authenticator,
+// 1Password, Authy...)
+pub fn provisioning_uri(secret: &str, issuer: &str, account: &str) -> String {
+    format!(
+        "otpauth://totp/{}:{}?secret={}&issuer={}&algorithm=SHA1&digits={}&period={}",
+        percent_encode(issuer),
+        percent_encode(account),
+        secret,
+        percent_encode(issuer),
+        DIGITS,
+        STEP_SECONDS
+    )
+}
+
+// QR code da URI em SVG, para o front exibir direto
+pub fn qr_code_svg(data: &str) -> Result<String, AppError> {
+    let code = QrCode::new(data.as_bytes())
+        .map_err(|e| AppError::Internal(format!("Erro ao gerar QR code: {}", e)))?;
+
+    Ok(code
+        .render::<svg::Color>()
+        .min_dimensions(200, 200)
+        .quiet_zone(true)
+        .build())
+}
+
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (byte as char).to_string(),
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
@@ -0,0 +1,95 @@
+use std::path::PathBuf;
+use std::sync::{Arc, OnceLock};
+use chrono::Utc;
+use futures::future::BoxFuture;
+use uuid::Uuid;
+use crate::config::Config;
+use crate::errors::AppError;
+
+static SHARED_MAILER: OnceLock<Arc<dyn Mailer>> = OnceLock::new();
+
+#[derive(Debug, Clone)]
+pub struct EmailMessage {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+// Envio de e-mails transacionais. Para trocar o transporte (SMTP, API de um
+// provedor...), implemente o trait e chame `install` antes de subir o servidor.
+pub trait Mailer: Send + Sync {
+    fn send<'a>(&'a self, message: &'a EmailMessage) -> BoxFuture<'a, Result<(), AppError>>;
+}
+
+// Mailer do processo: o instalado com `install` ou, se nenhum foi, o
+// `OutboxMailer` configurado pelo .env
+pub fn shared() -> Arc<dyn Mailer> {
+    SHARED_MAILER
+        .get_or_init(|| {
+            let config = Config::from_env().expect("Erro ao carregar configuração");
+            Arc::new(OutboxMailer::from_config(&config))
+        })
+        .clone()
+}
+
+// Falha se o mailer do processo já foi definido
+pub fn install(mailer: Arc<dyn Mailer>) -> Result<(), AppError> {
+    SHARED_MAILER
+        .set(mailer)
+        .map_err(|_| AppError::Config("Mailer já inicializado".to_string()))
+}
+
+// Não envia nada: grava cada mensagem como um .eml no diretório de saída,
+// para testar os fluxos por e-mail offline
+pub struct OutboxMailer {
+    dir: PathBuf,
+    from: String,
+}
+
+impl OutboxMailer {
+    pub fn new(dir: impl Into<PathBuf>, from: impl Into<String>) -> Self {
+        Self {
+            dir: dir.into(),
+            from: from.into(),
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(&config.mail_outbox_dir, &config.mail_from)
+    }
+
+    fn render(&self, message: &EmailMessage) -> String {
+        format!(
+            "From: {}\r\nTo: {}\r\nSubject: {}\r\nDate: {}\r\nContent-Type: text/plain; charset=utf-8\r\n\r\n{}\r\n",
+            self.from,
+            message.to,
+            message.subject,
+            Utc::now().to_rfc2822(),
+            message.body.replace('\n', "\r\n"),
+        )
+    }
+}
+
+impl Mailer for OutboxMailer {
+    fn send<'a>(&'a self, message: &'a EmailMessage) -> BoxFuture<'a, Result<(), AppError>> {
+        Box::pin(async move {
+            let write_error = |e: std::io::Error| {
+                AppError::Internal(format!("Erro ao gravar e-mail em {}: {}", self.dir.display(), e))
+            };
+
+            tokio::fs::create_dir_all(&self.dir).await.map_err(write_error)?;
+
+            // Nome ordenável pela data de envio
+            let path = self.dir.join(format!(
+                "{}-{}.eml",
+                Utc::now().format("%Y%m%dT%H%M%S%.3fZ"),
+                Uuid::new_v4()
+            ));
+
+            tokio::fs::write(&path, self.render(message)).await.map_err(write_error)?;
+            tracing::info!("E-mail para {} gravado em {}", message.to, path.display());
+
+            Ok(())
+        })
+    }
+}
@@ -1,2 +1,7 @@
-pub mod jwt;
+pub mod token_service;
+pub mod signing_keys;
 pub mod auth;
+pub mod mailer;
+pub mod rate_limit;
+pub mod validation;
+pub mod openapi;
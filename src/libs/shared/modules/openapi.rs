@@ -0,0 +1,200 @@
+use utoipa::openapi::path::{Operation, PathItem};
+use utoipa::openapi::security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityRequirement, SecurityScheme};
+use utoipa::openapi::{ContentBuilder, Ref, RefOr, Response, ResponseBuilder};
+use utoipa::{Modify, OpenApi, ToResponse};
+use utoipa_axum::router::UtoipaMethodRouter;
+use crate::errors::{FieldError, ProblemDetails};
+use super::auth::access_control::{AuthGuard, RouteMetadata};
+use super::auth::middleware::ACCESS_TOKEN_COOKIE;
+
+// Nomes dos esquemas de segurança em components.securitySchemes
+pub const BEARER_AUTH: &str = "bearer_auth";
+pub const COOKIE_AUTH: &str = "cookie_auth";
+
+const PROBLEM_CONTENT_TYPE: &str = "application/problem+json";
+
+// Base do documento servido em /openapi.json. As rotas entram pelos
+// `OpenApiRouter` de cada API, então o documento acompanha o que está de
+// fato montado no servidor.
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "API Rust Monorepo",
+        description = "Auth, Admin e Viewer API. Erros seguem o formato `application/problem+json` (RFC 7807).",
+        license(name = "MIT", identifier = "MIT"),
+    ),
+    components(
+        schemas(ProblemDetails, FieldError),
+        responses(InvalidRequest, Unauthorized, Forbidden, NotFound, Conflict, TooManyRequests),
+    ),
+    tags(
+        (name = "Autenticação", description = "Login, cadastro, refresh e chaves públicas"),
+        (name = "2FA", description = "Autenticação em dois fatores (TOTP)"),
+        (name = "E-mail", description = "Confirmação de e-mail"),
+        (name = "Senha", description = "Troca e redefinição de senha"),
+        (name = "Sessões", description = "Sessões (refresh tokens) do próprio usuário"),
+        (name = "Usuários", description = "Gestão de usuários"),
+        (name = "Grupos de acesso", description = "Grupos de acesso e permissões"),
+        (name = "Admin - Vídeos", description = "Cadastro e publicação de vídeos"),
+        (name = "Catálogo", description = "Catálogo para os espectadores"),
+        (name = "Health", description = "Verificação de saúde de cada API"),
+    ),
+    modifiers(&SecuritySchemes),
+)]
+pub struct ApiDoc;
+
+// Access token no header `Authorization: Bearer` ou no cookie `access_token`
+// (o mesmo que `authenticate` aceita)
+pub struct SecuritySchemes;
+
+impl Modify for SecuritySchemes {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+
+        components.add_security_scheme(
+            BEARER_AUTH,
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .bearer_format("JWT")
+                    .description(Some("Access token emitido por POST /auth/login"))
+                    .build(),
+            ),
+        );
+        components.add_security_scheme(
+            COOKIE_AUTH,
+            SecurityScheme::ApiKey(ApiKey::Cookie(ApiKeyValue::with_description(
+                ACCESS_TOKEN_COOKIE,
+                "Mesmo access token, enviado em cookie",
+            ))),
+        );
+    }
+}
+
+// Respostas de erro reaproveitadas nos `#[utoipa::path]`:
+//
+//   responses((status = 404, response = NotFound))
+macro_rules! problem_response {
+    ($name:ident, $description:literal) => {
+        pub struct $name;
+
+        impl<'r> ToResponse<'r> for $name {
+            fn response() -> (&'r str, RefOr<Response>) {
+                (stringify!($name), problem_response($description).into())
+            }
+        }
+    };
+}
+
+problem_response!(InvalidRequest, "Corpo ou query inválidos; as regras violadas vêm em `errors`");
+problem_response!(Unauthorized, "Access token ausente, inválido ou expirado");
+problem_response!(Forbidden, "Sem permissão para este recurso");
+problem_response!(NotFound, "Recurso não encontrado");
+problem_response!(Conflict, "Conflito com o estado atual do recurso");
+problem_response!(TooManyRequests, "Muitas tentativas; aguarde o tempo informado em Retry-After");
+
+fn problem_response(description: &str) -> Response {
+    ResponseBuilder::new()
+        .description(description)
+        .content(
+            PROBLEM_CONTENT_TYPE,
+            ContentBuilder::new()
+                .schema(Some(Ref::from_schema_name("ProblemDetails")))
+                .build(),
+        )
+        .build()
+}
+
+// Aplica o AuthGuard à rota e registra no documento o que ele exige, para
+// que a segurança descrita seja sempre a aplicada:
+//
+//   .routes(routes!(user_controller::list_users).with_guard(require_permission(USERS_READ)))
+pub trait GuardedRoutes {
+    fn with_guard(self, guard: AuthGuard) -> Self;
+}
+
+impl<S> GuardedRoutes for UtoipaMethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    fn with_guard(self, guard: AuthGuard) -> Self {
+        let (schemas, mut paths, method_router) = self;
+
+        for item in paths.paths.values_mut() {
+            for operation in operations_mut(item) {
+                document_guard(operation, guard.metadata());
+            }
+        }
+
+        (schemas, paths, method_router.route_layer(guard))
+    }
+}
+
+fn operations_mut(item: &mut PathItem) -> impl Iterator<Item = &mut Operation> {
+    [
+        &mut item.get,
+        &mut item.put,
+        &mut item.post,
+        &mut item.delete,
+        &mut item.options,
+        &mut item.head,
+        &mut item.patch,
+        &mut item.trace,
+    ]
+    .into_iter()
+    .filter_map(Option::as_mut)
+}
+
+// Rota pública fica sem `security`: um token válido é usado se vier, mas não
+// é exigido
+fn document_guard(operation: &mut Operation, metadata: &RouteMetadata) {
+    if metadata.is_public {
+        return;
+    }
+
+    // Qualquer um dos dois basta
+    operation.security = Some(vec![
+        SecurityRequirement::new(BEARER_AUTH, Vec::<String>::new()),
+        SecurityRequirement::new(COOKIE_AUTH, Vec::<String>::new()),
+    ]);
+
+    let mut requirements = Vec::new();
+
+    if !metadata.required_permissions.is_empty() {
+        let permissions: Vec<_> = metadata
+            .required_permissions
+            .iter()
+            .map(|permission| format!("`{}`", permission))
+            .collect();
+        requirements.push(format!("Permissões necessárias: {}.", permissions.join(", ")));
+    }
+
+    if !metadata.required_groups.is_empty() {
+        let groups: Vec<_> = metadata
+            .required_groups
+            .iter()
+            .map(|group| format!("`{:?}`", group))
+            .collect();
+        requirements.push(format!("Grupos aceitos: {}.", groups.join(", ")));
+    }
+
+    if metadata.allow_unverified {
+        requirements.push("Aceita contas com e-mail ainda não confirmado.".to_string());
+    }
+
+    if !requirements.is_empty() {
+        let requirements = requirements.join(" ");
+        operation.description = Some(match operation.description.take() {
+            Some(description) => format!("{}\n\n{}", description, requirements),
+            None => requirements,
+        });
+    }
+
+    let responses = &mut operation.responses.responses;
+    responses
+        .entry("401".to_string())
+        .or_insert_with(|| Ref::from_response_name("Unauthorized").into());
+    responses
+        .entry("403".to_string())
+        .or_insert_with(|| Ref::from_response_name("Forbidden").into());
+}
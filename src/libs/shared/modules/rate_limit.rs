@@ -0,0 +1,560 @@
+use std::collections::HashSet;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::{Arc, OnceLock};
+use std::task::{Context, Poll};
+use axum::{
+    extract::{ConnectInfo, MatchedPath, Request},
+    http::{HeaderMap, HeaderName, HeaderValue},
+    response::{IntoResponse, Response},
+};
+use chrono::Utc;
+use futures::future::BoxFuture;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use tower::{Layer, Service};
+use crate::config::{Config, ProjectConfig};
+use crate::errors::AppError;
+use crate::libs::shared::cache::redis_cache::RedisCache;
+use crate::libs::shared::modules::auth::middleware::extract_token;
+use crate::libs::shared::modules::auth::client_ip::TrustedProxies;
+use crate::libs::shared::modules::token_service::TokenService;
+
+static SHARED_RATE_LIMIT: OnceLock<RateLimitLayer> = OnceLock::new();
+
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+// Janela deslizante aproximada por dois contadores de janela fixa: a janela
+// anterior entra com peso proporcional ao quanto dela ainda cabe na janela
+// deslizante. Só incrementa se a requisição for aceita.
+//
+//   KEYS[1] = contador da janela atual, KEYS[2] = da anterior
+//   ARGV    = limite, janela em ms, ms decorridos na janela atual
+const SLIDING_WINDOW_SCRIPT: &str = r#"
+local current = tonumber(redis.call('GET', KEYS[1]) or '0')
+local previous = tonumber(redis.call('GET', KEYS[2]) or '0')
+local limit = tonumber(ARGV[1])
+local window = tonumber(ARGV[2])
+local elapsed = tonumber(ARGV[3])
+
+if previous * (window - elapsed) / window + current + 1 > limit then
+    return {0, current, previous}
+end
+
+current = redis.call('INCR', KEYS[1])
+if current == 1 then
+    redis.call('PEXPIRE', KEYS[1], window * 2)
+end
+
+return {1, current, previous}
+"#;
+
+// Quem é contado: o IP, o usuário das claims ou a chave de API do header
+// X-API-Key. Sem token ou sem chave conhecida, cai no IP.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLimitKey {
+    #[default]
+    Ip,
+    User,
+    ApiKey,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RateLimitPolicy {
+    #[serde(default)]
+    pub key: RateLimitKey,
+    pub limit: u64,
+    pub window_seconds: u64,
+}
+
+// Política de uma rota. `path` é o padrão da rota com o prefixo do app, como
+// declarado no router (ex.: "/viewer/videos/{id}"); sem `method`, vale para
+// todos.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RouteRateLimit {
+    pub method: Option<String>,
+    pub path: String,
+    #[serde(flatten)]
+    pub policy: RateLimitPolicy,
+}
+
+// Seção [rate_limit] do config/project.toml; sem a seção, nada é limitado
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RateLimitSettings {
+    #[serde(default = "enabled_default")]
+    pub enabled: bool,
+    // Rotas sem política própria; sem `default`, elas ficam sem limite
+    pub default: Option<RateLimitPolicy>,
+    #[serde(default)]
+    pub routes: Vec<RouteRateLimit>,
+}
+
+fn enabled_default() -> bool {
+    true
+}
+
+impl RateLimitSettings {
+    // Política da rota e o identificador do contador. As rotas sem regra
+    // própria dividem o contador do `default`.
+    fn policy_for(&self, method: &str, path: &str) -> Option<(String, &RateLimitPolicy)> {
+        self.routes
+            .iter()
+            .find(|route| {
+                route.path == path
+                    && match route.method.as_deref() {
+                        Some(route_method) => route_method.eq_ignore_ascii_case(method),
+                        None => true,
+                    }
+            })
+            .map(|route| {
+                let method = route.method.as_deref().unwrap_or("*").to_ascii_uppercase();
+                (format!("{}:{}", method, route.path), &route.policy)
+            })
+            .or_else(|| self.default.as_ref().map(|policy| ("default".to_string(), policy)))
+    }
+}
+
+// Chaves aceitas no header X-API-Key (RATE_LIMIT_API_KEYS no .env, separadas
+// por vírgula), guardadas pelo SHA-256. Uma chave fora da lista não ganha
+// contador próprio: se ganhasse, bastaria inventar chaves para escapar do
+// limite.
+#[derive(Debug, Clone, Default)]
+pub struct ApiKeys(HashSet<String>);
+
+impl ApiKeys {
+    pub fn parse(value: &str) -> Self {
+        Self(
+            value
+                .split(',')
+                .map(str::trim)
+                .filter(|key| !key.is_empty())
+                .map(Self::digest)
+                .collect(),
+        )
+    }
+
+    fn digest(key: &str) -> String {
+        hex::encode(Sha256::digest(key.as_bytes()))
+    }
+
+    // Identificador do contador da chave, se ela for conhecida
+    fn identify(&self, key: &str) -> Option<String> {
+        let digest = Self::digest(key.trim());
+        self.0.contains(&digest).then(|| format!("key:{}", digest))
+    }
+}
+
+// Resultado de uma checagem, para os headers RateLimit-*
+#[derive(Debug, Clone)]
+pub struct RateLimitDecision {
+    pub allowed: bool,
+    pub limit: u64,
+    pub remaining: u64,
+    pub reset_seconds: u64,
+    pub retry_after_seconds: u64,
+    pub window_seconds: u64,
+}
+
+impl RateLimitDecision {
+    fn evaluate(policy: &RateLimitPolicy, allowed: bool, current: u64, previous: u64, elapsed_ms: u64) -> Self {
+        let window_ms = policy.window_seconds.max(1) * 1000;
+        let reset_ms = window_ms - elapsed_ms;
+        let estimated = current + Self::weighted(previous, reset_ms, window_ms);
+        let remaining = policy.limit.saturating_sub(estimated);
+
+        let retry_after_ms = if allowed {
+            0
+        } else {
+            Self::retry_after_ms(policy.limit, current, previous, window_ms, elapsed_ms)
+        };
+
+        Self {
+            allowed,
+            limit: policy.limit,
+            remaining,
+            reset_seconds: reset_ms.div_ceil(1000),
+            retry_after_seconds: retry_after_ms.div_ceil(1000).max(1),
+            window_seconds: policy.window_seconds,
+        }
+    }
+
+    // ⌈count × part / whole⌉ em inteiros: em ponto flutuante, 60000 × 0,01
+    // dá 600,0000000000006 e o arredondamento para cima erra por 1
+    fn weighted(count: u64, part: u64, whole: u64) -> u64 {
+        (count as u128 * part as u128).div_ceil(whole as u128) as u64
+    }
+
+    // Quando a estimativa volta a caber mais uma requisição: ainda nesta
+    // janela, se o peso da anterior cair o bastante, ou na próxima
+    fn retry_after_ms(limit: u64, current: u64, previous: u64, window_ms: u64, elapsed_ms: u64) -> u64 {
+        let room = limit.saturating_sub(1);
+
+        // previous × (janela − t) / janela + current ≤ room
+        if current <= room && previous > 0 {
+            let at = Self::weighted(window_ms, previous.saturating_sub(room - current), previous);
+            if at < window_ms {
+                return at.saturating_sub(elapsed_ms);
+            }
+        }
+
+        // Na próxima janela, a atual vira a anterior
+        let at_next = Self::weighted(window_ms, current.saturating_sub(room), current.max(1));
+
+        window_ms - elapsed_ms + at_next
+    }
+
+    fn apply_headers(&self, headers: &mut HeaderMap) {
+        let values = [
+            ("ratelimit-limit", self.limit.to_string()),
+            ("ratelimit-remaining", self.remaining.to_string()),
+            ("ratelimit-reset", self.reset_seconds.to_string()),
+            ("ratelimit-policy", format!("{};w={}", self.limit, self.window_seconds)),
+        ];
+
+        for (name, value) in values {
+            if let Ok(value) = HeaderValue::from_str(&value) {
+                headers.insert(HeaderName::from_static(name), value);
+            }
+        }
+    }
+}
+
+// Limite de requisições por rota, com contadores no Redis. Aplicado no
+// router inteiro, depois dos `nest`:
+//
+//     Router::new().nest("/auth", routes).layer(RateLimitLayer::shared())
+//
+// Cada rota usa a política da seção [rate_limit] do config/project.toml.
+// Se o Redis falhar, a requisição passa sem limite.
+#[derive(Debug, Clone)]
+pub struct RateLimitLayer {
+    settings: Arc<RateLimitSettings>,
+    api_keys: Arc<ApiKeys>,
+    script: Arc<redis::Script>,
+}
+
+impl RateLimitLayer {
+    pub fn new(settings: RateLimitSettings, api_keys: ApiKeys) -> Self {
+        Self {
+            settings: Arc::new(settings),
+            api_keys: Arc::new(api_keys),
+            script: Arc::new(redis::Script::new(SLIDING_WINDOW_SCRIPT)),
+        }
+    }
+
+    pub fn shared() -> Self {
+        SHARED_RATE_LIMIT
+            .get_or_init(|| {
+                let config = Config::from_env().expect("Erro ao carregar configuração");
+                Self::new(ProjectConfig::shared().rate_limit.clone(), ApiKeys::parse(&config.rate_limit_api_keys))
+            })
+            .clone()
+    }
+
+    fn subject(&self, key: RateLimitKey, request: &Request) -> String {
+        let headers = request.headers();
+
+        let by_key = match key {
+            RateLimitKey::Ip => None,
+            // Só a assinatura é conferida: a denylist fica com o AuthGuard
+            RateLimitKey::User => extract_token(headers)
+                .and_then(|token| TokenService::shared().validate_access_token(&token).ok())
+                .map(|claims| format!("user:{}", claims.sub)),
+            RateLimitKey::ApiKey => headers
+                .get(API_KEY_HEADER)
+                .and_then(|h| h.to_str().ok())
+                .and_then(|value| self.api_keys.identify(value)),
+        };
+
+        by_key.unwrap_or_else(|| {
+            let peer = request
+                .extensions()
+                .get::<ConnectInfo<SocketAddr>>()
+                .map(|ConnectInfo(addr)| *addr);
+
+            match TrustedProxies::shared().client_ip(headers, peer) {
+                Some(ip) => format!("ip:{}", ip),
+                None => "ip:unknown".to_string(),
+            }
+        })
+    }
+
+    pub async fn check(&self, route_id: &str, policy: &RateLimitPolicy, subject: &str) -> Result<RateLimitDecision, AppError> {
+        let window_ms = policy.window_seconds.max(1) * 1000;
+        let now_ms = Utc::now().timestamp_millis().max(0) as u64;
+        let window_index = now_ms / window_ms;
+        let elapsed_ms = now_ms % window_ms;
+
+        let keys = [
+            format!("ratelimit:{}:{}:{}", route_id, subject, window_index),
+            format!("ratelimit:{}:{}:{}", route_id, subject, window_index.saturating_sub(1)),
+        ];
+
+        let cache = RedisCache::shared().await?;
+        let (allowed, current, previous): (i64, u64, u64) = cache
+            .run_script(&self.script, &keys, &[policy.limit as i64, window_ms as i64, elapsed_ms as i64])
+            .await?;
+
+        Ok(RateLimitDecision::evaluate(policy, allowed == 1, current, previous, elapsed_ms))
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimitService {
+            inner,
+            limiter: self.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RateLimitService<S> {
+    inner: S,
+    limiter: RateLimitLayer,
+}
+
+impl<S> Service<Request> for RateLimitService<S>
+where
+    S: Service<Request, Response = Response, Error = Infallible> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Response, Infallible>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        // O clone pronto fica com a chamada; o original volta para o pool
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let limiter = self.limiter.clone();
+
+        Box::pin(async move {
+            if !limiter.settings.enabled {
+                return inner.call(request).await;
+            }
+
+            let path = request
+                .extensions()
+                .get::<MatchedPath>()
+                .map(|matched| matched.as_str().to_string())
+                .unwrap_or_else(|| request.uri().path().to_string());
+
+            let Some((route_id, policy)) = limiter.settings.policy_for(request.method().as_str(), &path) else {
+                return inner.call(request).await;
+            };
+            let policy = policy.clone();
+
+            let subject = limiter.subject(policy.key, &request);
+
+            let decision = match limiter.check(&route_id, &policy, &subject).await {
+                Ok(decision) => decision,
+                Err(e) => {
+                    tracing::warn!("Rate limit indisponível, requisição liberada: {}", e);
+                    return inner.call(request).await;
+                }
+            };
+
+            if !decision.allowed {
+                tracing::warn!("Rate limit atingido em {} por {}", route_id, subject);
+
+                let mut response = AppError::RateLimited(
+                    "Muitas requisições; tente novamente mais tarde".to_string(),
+                    decision.retry_after_seconds,
+                )
+                .into_response();
+                decision.apply_headers(response.headers_mut());
+
+                return Ok(response);
+            }
+
+            let mut response = inner.call(request).await?;
+            decision.apply_headers(response.headers_mut());
+
+            Ok(response)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WINDOW_MS: u64 = 60_000;
+
+    fn policy(limit: u64) -> RateLimitPolicy {
+        RateLimitPolicy {
+            key: RateLimitKey::Ip,
+            limit,
+            window_seconds: WINDOW_MS / 1000,
+        }
+    }
+
+    // Mesma conta do script: cabe mais uma requisição `after_ms` depois do
+    // ponto `elapsed_ms` da janela atual? Passada a virada, a janela atual
+    // vira a anterior e a nova começa vazia.
+    fn fits_after(limit: u64, current: u64, previous: u64, elapsed_ms: u64, after_ms: u64) -> bool {
+        let at = elapsed_ms + after_ms;
+        let (previous, current, elapsed) = if at < WINDOW_MS {
+            (previous, current, at)
+        } else if at < 2 * WINDOW_MS {
+            (current, 0, at - WINDOW_MS)
+        } else {
+            (0, 0, 0)
+        };
+
+        previous as f64 * (WINDOW_MS - elapsed) as f64 / WINDOW_MS as f64 + current as f64 + 1.0 <= limit as f64
+    }
+
+    fn assert_earliest_retry(limit: u64, current: u64, previous: u64, elapsed_ms: u64) {
+        let retry = RateLimitDecision::retry_after_ms(limit, current, previous, WINDOW_MS, elapsed_ms);
+
+        assert!(
+            fits_after(limit, current, previous, elapsed_ms, retry),
+            "limit={} current={} previous={} elapsed={}: ainda bloqueado depois de {} ms",
+            limit, current, previous, elapsed_ms, retry
+        );
+        assert!(
+            retry == 0 || !fits_after(limit, current, previous, elapsed_ms, retry - 1),
+            "limit={} current={} previous={} elapsed={}: já cabia antes de {} ms",
+            limit, current, previous, elapsed_ms, retry
+        );
+    }
+
+    #[test]
+    fn retry_after_is_the_earliest_moment_that_fits() {
+        for limit in [1, 2, 5, 10, 100] {
+            for current in [0, 1, limit - 1, limit] {
+                for previous in [0, 1, limit / 2, limit] {
+                    for elapsed_ms in [0, 1, 15_000, 30_000, 59_999] {
+                        if !fits_after(limit, current, previous, elapsed_ms, 0) {
+                            assert_earliest_retry(limit, current, previous, elapsed_ms);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn retry_after_without_previous_waits_for_next_window() {
+        // Sem janela anterior, só a virada libera; com limite 10 e 10 na
+        // atual, na próxima janela o peso delas precisa cair para 9
+        let retry = RateLimitDecision::retry_after_ms(10, 10, 0, WINDOW_MS, 20_000);
+
+        assert_eq!(retry, 40_000 + 6_000);
+    }
+
+    #[test]
+    fn retry_after_with_empty_current_window_waits_for_previous_to_decay() {
+        // 10 na anterior e nenhuma na atual: cabe quando o peso da anterior
+        // chegar a 9, a 10% da janela
+        let retry = RateLimitDecision::retry_after_ms(10, 0, 10, WINDOW_MS, 1_000);
+
+        assert_eq!(retry, 5_000);
+    }
+
+    #[test]
+    fn retry_after_with_limit_one_skips_a_whole_window() {
+        // Com limite 1, a requisição da janela atual pesa até o fim da
+        // seguinte
+        assert_eq!(RateLimitDecision::retry_after_ms(1, 1, 0, WINDOW_MS, 10_000), 50_000 + WINDOW_MS);
+        // Só a anterior cheia: libera na virada
+        assert_eq!(RateLimitDecision::retry_after_ms(1, 0, 1, WINDOW_MS, 10_000), 50_000);
+    }
+
+    #[test]
+    fn evaluate_allowed_request_reports_remaining_and_reset() {
+        // 10 na anterior com metade da janela: estimativa 5 + 3 = 8
+        let decision = RateLimitDecision::evaluate(&policy(10), true, 3, 10, 30_000);
+
+        assert!(decision.allowed);
+        assert_eq!(decision.remaining, 2);
+        assert_eq!(decision.reset_seconds, 30);
+        assert_eq!(decision.window_seconds, 60);
+    }
+
+    #[test]
+    fn evaluate_rounds_partial_estimate_up() {
+        // 1 × 0,5 + 0 = 0,5 conta como 1
+        let decision = RateLimitDecision::evaluate(&policy(10), true, 0, 1, 30_000);
+        assert_eq!(decision.remaining, 9);
+
+        // 100 × 0,99 é exatamente 99, sem sobra de ponto flutuante
+        let decision = RateLimitDecision::evaluate(&policy(100), true, 0, 100, 600);
+        assert_eq!(decision.remaining, 1);
+    }
+
+    #[test]
+    fn evaluate_denied_request_has_no_remaining_and_retry_at_least_one_second() {
+        let decision = RateLimitDecision::evaluate(&policy(10), false, 10, 0, 59_999);
+
+        assert!(!decision.allowed);
+        assert_eq!(decision.remaining, 0);
+        assert_eq!(decision.reset_seconds, 1);
+        assert_eq!(decision.retry_after_seconds, 7);
+    }
+
+    #[test]
+    fn evaluate_rounds_sub_second_retry_up_to_one() {
+        // Cabe 1 ms depois: Retry-After nunca é 0
+        let decision = RateLimitDecision::evaluate(&policy(10), false, 0, 10, 5_999);
+
+        assert_eq!(decision.retry_after_seconds, 1);
+    }
+
+    #[test]
+    fn policy_for_matches_method_and_falls_back_to_default() {
+        let settings: RateLimitSettings = toml::from_str(
+            r#"
+            default = { key = "ip", limit = 300, window_seconds = 60 }
+
+            [[routes]]
+            method = "post"
+            path = "/auth/login"
+            limit = 10
+            window_seconds = 60
+
+            [[routes]]
+            path = "/admin/videos"
+            key = "user"
+            limit = 60
+            window_seconds = 60
+            "#,
+        )
+        .unwrap();
+
+        let (id, policy) = settings.policy_for("POST", "/auth/login").unwrap();
+        assert_eq!((id.as_str(), policy.limit, policy.key), ("POST:/auth/login", 10, RateLimitKey::Ip));
+
+        let (id, policy) = settings.policy_for("DELETE", "/admin/videos").unwrap();
+        assert_eq!((id.as_str(), policy.key), ("*:/admin/videos", RateLimitKey::User));
+
+        let (id, policy) = settings.policy_for("GET", "/auth/login").unwrap();
+        assert_eq!((id.as_str(), policy.limit), ("default", 300));
+    }
+
+    #[test]
+    fn without_default_unmatched_routes_are_unlimited() {
+        let settings = RateLimitSettings::default();
+
+        assert!(settings.policy_for("GET", "/viewer/videos").is_none());
+    }
+
+    #[test]
+    fn only_configured_api_keys_get_their_own_counter() {
+        let keys = ApiKeys::parse(" chave-a , ,chave-b");
+
+        assert_eq!(keys.identify("chave-a"), Some(format!("key:{}", ApiKeys::digest("chave-a"))));
+        assert_eq!(keys.identify(" chave-b "), keys.identify("chave-b"));
+        assert_eq!(keys.identify("inventada"), None);
+        assert_eq!(ApiKeys::default().identify("chave-a"), None);
+    }
+}
@@ -0,0 +1,207 @@
+use std::fs;
+use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+use base64::Engine;
+use jsonwebtoken::jwk::{
+    AlgorithmParameters, CommonParameters, Jwk, JwkSet, KeyAlgorithm, OctetKeyPairParameters,
+    OctetKeyPairType, PublicKeyUse, RSAKeyParameters, RSAKeyType, EllipticCurve,
+};
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey};
+use rsa::pkcs1::DecodeRsaPublicKey;
+use rsa::pkcs8::DecodePublicKey;
+use rsa::traits::PublicKeyParts;
+use rsa::RsaPublicKey;
+use crate::config::Config;
+use crate::errors::AppError;
+
+// Tamanho da chave pública Ed25519 no fim do SubjectPublicKeyInfo
+const ED25519_PUBLIC_KEY_LEN: usize = 32;
+
+pub struct SigningKey {
+    pub kid: Option<String>,
+    pub algorithm: Algorithm,
+    pub encoding: EncodingKey,
+}
+
+pub struct VerificationKey {
+    pub kid: Option<String>,
+    pub algorithm: Algorithm,
+    pub decoding: DecodingKey,
+    // Só chaves públicas aparecem no JWKS
+    pub jwk: Option<Jwk>,
+}
+
+// Chave de assinatura atual mais as chaves aceitas na verificação. Durante a
+// rotação, a chave anterior continua na lista (pelo `kid`) até os tokens
+// assinados com ela expirarem.
+pub struct KeyRing {
+    signing: Option<SigningKey>,
+    verification: Vec<VerificationKey>,
+}
+
+impl KeyRing {
+    // HS256 com segredo compartilhado; tokens sem `kid`
+    pub fn from_secret(secret: &str) -> Self {
+        Self {
+            signing: Some(SigningKey {
+                kid: None,
+                algorithm: Algorithm::HS256,
+                encoding: EncodingKey::from_secret(secret.as_bytes()),
+            }),
+            verification: vec![VerificationKey {
+                kid: None,
+                algorithm: Algorithm::HS256,
+                decoding: DecodingKey::from_secret(secret.as_bytes()),
+                jwk: None,
+            }],
+        }
+    }
+
+    // Chaves de access token definidas no Config:
+    //
+    //   JWT_ALGORITHM             EdDSA ou RS256
+    //   JWT_KEY_ID                kid da chave atual
+    //   JWT_PUBLIC_KEY_PATH       PEM público da chave atual
+    //   JWT_PRIVATE_KEY_PATH      PEM privado (só no auth_api)
+    //   JWT_PREVIOUS_PUBLIC_KEYS  kid=caminho,... ainda aceitas na verificação
+    //
+    // Sem JWT_PUBLIC_KEY_PATH, cai no HS256 com JWT_ACCESS_SECRET.
+    pub fn from_config(config: &Config) -> Result<Self, AppError> {
+        if config.jwt_public_key_path.is_empty() {
+            tracing::warn!("JWT_PUBLIC_KEY_PATH não definido: access tokens assinados com HS256 (segredo compartilhado)");
+            return Ok(Self::from_secret(&config.jwt_access_secret));
+        }
+
+        let algorithm = match config.jwt_algorithm.as_str() {
+            "EdDSA" => Algorithm::EdDSA,
+            "RS256" => Algorithm::RS256,
+            other => {
+                return Err(AppError::Config(format!(
+                    "JWT_ALGORITHM inválido: {} (use EdDSA ou RS256)",
+                    other
+                )))
+            }
+        };
+
+        if config.jwt_key_id.is_empty() {
+            return Err(AppError::Config("JWT_KEY_ID é obrigatório com JWT_PUBLIC_KEY_PATH".to_string()));
+        }
+
+        let signing = if config.jwt_private_key_path.is_empty() {
+            None
+        } else {
+            let pem = read_pem(&config.jwt_private_key_path)?;
+            let encoding = match algorithm {
+                Algorithm::EdDSA => EncodingKey::from_ed_pem(&pem),
+                _ => EncodingKey::from_rsa_pem(&pem),
+            }
+            .map_err(|e| AppError::Config(format!("Chave privada inválida em {}: {}", config.jwt_private_key_path, e)))?;
+
+            Some(SigningKey {
+                kid: Some(config.jwt_key_id.clone()),
+                algorithm,
+                encoding,
+            })
+        };
+
+        let mut verification = vec![VerificationKey::from_public_pem(
+            &config.jwt_key_id,
+            algorithm,
+            &config.jwt_public_key_path,
+        )?];
+
+        for entry in config.jwt_previous_public_keys.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (kid, path) = entry.split_once('=').ok_or_else(|| {
+                AppError::Config(format!("JWT_PREVIOUS_PUBLIC_KEYS: esperado kid=caminho, recebido {}", entry))
+            })?;
+            verification.push(VerificationKey::from_public_pem(kid.trim(), algorithm, path.trim())?);
+        }
+
+        Ok(Self { signing, verification })
+    }
+
+    // Serviços que só validam tokens não têm chave de assinatura
+    pub fn signing_key(&self) -> Result<&SigningKey, AppError> {
+        self.signing
+            .as_ref()
+            .ok_or_else(|| AppError::Config("Este serviço não tem chave para assinar tokens".to_string()))
+    }
+
+    pub fn find(&self, kid: Option<&str>) -> Option<&VerificationKey> {
+        self.verification.iter().find(|key| key.kid.as_deref() == kid)
+    }
+
+    pub fn jwks(&self) -> JwkSet {
+        JwkSet {
+            keys: self.verification.iter().filter_map(|key| key.jwk.clone()).collect(),
+        }
+    }
+}
+
+impl VerificationKey {
+    fn from_public_pem(kid: &str, algorithm: Algorithm, path: &str) -> Result<Self, AppError> {
+        let pem = read_pem(path)?;
+        let invalid = |e: String| AppError::Config(format!("Chave pública inválida em {}: {}", path, e));
+
+        let (decoding, parameters, key_algorithm) = match algorithm {
+            Algorithm::EdDSA => {
+                let der = pem_to_der(&pem).map_err(invalid)?;
+                if der.len() < ED25519_PUBLIC_KEY_LEN {
+                    return Err(invalid("chave Ed25519 curta demais".to_string()));
+                }
+                let x = URL_SAFE_NO_PAD.encode(&der[der.len() - ED25519_PUBLIC_KEY_LEN..]);
+                let decoding = DecodingKey::from_ed_pem(&pem).map_err(|e| invalid(e.to_string()))?;
+                let parameters = AlgorithmParameters::OctetKeyPair(OctetKeyPairParameters {
+                    key_type: OctetKeyPairType::OctetKeyPair,
+                    curve: EllipticCurve::Ed25519,
+                    x,
+                });
+                (decoding, parameters, KeyAlgorithm::EdDSA)
+            }
+            _ => {
+                let text = String::from_utf8_lossy(&pem);
+                let public_key = RsaPublicKey::from_public_key_pem(&text)
+                    .or_else(|_| RsaPublicKey::from_pkcs1_pem(&text))
+                    .map_err(|e| invalid(e.to_string()))?;
+                let decoding = DecodingKey::from_rsa_pem(&pem).map_err(|e| invalid(e.to_string()))?;
+                let parameters = AlgorithmParameters::RSA(RSAKeyParameters {
+                    key_type: RSAKeyType::RSA,
+                    n: URL_SAFE_NO_PAD.encode(public_key.n().to_bytes_be()),
+                    e: URL_SAFE_NO_PAD.encode(public_key.e().to_bytes_be()),
+                });
+                (decoding, parameters, KeyAlgorithm::RS256)
+            }
+        };
+
+        let jwk = Jwk {
+            common: CommonParameters {
+                public_key_use: Some(PublicKeyUse::Signature),
+                key_algorithm: Some(key_algorithm),
+                key_id: Some(kid.to_string()),
+                ..Default::default()
+            },
+            algorithm: parameters,
+        };
+
+        Ok(Self {
+            kid: Some(kid.to_string()),
+            algorithm,
+            decoding,
+            jwk: Some(jwk),
+        })
+    }
+}
+
+fn read_pem(path: &str) -> Result<Vec<u8>, AppError> {
+    fs::read(path).map_err(|e| AppError::Config(format!("Erro ao ler chave {}: {}", path, e)))
+}
+
+// Corpo base64 entre as linhas BEGIN/END
+fn pem_to_der(pem: &[u8]) -> Result<Vec<u8>, String> {
+    let body: String = String::from_utf8_lossy(pem)
+        .lines()
+        .filter(|line| !line.starts_with("-----"))
+        .map(str::trim)
+        .collect();
+
+    STANDARD.decode(body).map_err(|e| e.to_string())
+}
@@ -0,0 +1,258 @@
+use std::sync::OnceLock;
+use chrono::{Duration, Utc};
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{decode, decode_header, encode, Header, Validation};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use crate::config::Config;
+use crate::errors::AppError;
+use super::auth::access_control::{AccessGroupEnum, UserWithAccess};
+use super::signing_keys::KeyRing;
+
+static SHARED_TOKEN_SERVICE: OnceLock<TokenService> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenType {
+    Access,
+    Refresh,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Claims {
+    pub sub: String,                          // User ID
+    pub email: String,
+    pub role: String,
+    pub access_groups: Vec<AccessGroupEnum>,
+    pub exp: i64,
+    pub iat: i64,
+    pub jti: String,                          // Identificador único do token
+    pub token_type: TokenType,
+    // Sessão (família de refresh tokens) que originou o token
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sid: Option<String>,
+    // Tokens emitidos antes do campo existir são de contas confirmadas
+    #[serde(default = "email_verified_default")]
+    pub email_verified: bool,
+}
+
+fn email_verified_default() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TokenPair {
+    pub access_token: String,
+    pub refresh_token: String,
+    pub expires_in: i64,
+    pub refresh_expires_in: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct IssuedTokens {
+    pub pair: TokenPair,
+    pub access_claims: Claims,
+    pub refresh_claims: Claims,
+}
+
+// Dados do usuário que vão para as claims
+#[derive(Debug, Clone)]
+pub struct TokenSubject {
+    pub user_id: String,
+    pub email: String,
+    pub role: String,
+    pub access_groups: Vec<AccessGroupEnum>,
+    pub email_verified: bool,
+    pub session_id: Option<String>,
+}
+
+impl From<&UserWithAccess> for TokenSubject {
+    fn from(user: &UserWithAccess) -> Self {
+        Self {
+            user_id: user.id.clone(),
+            email: user.email.clone(),
+            role: user.role.clone(),
+            access_groups: user.access_groups.clone(),
+            email_verified: user.email_verified,
+            session_id: None,
+        }
+    }
+}
+
+impl From<&Claims> for TokenSubject {
+    fn from(claims: &Claims) -> Self {
+        Self {
+            user_id: claims.sub.clone(),
+            email: claims.email.clone(),
+            role: claims.role.clone(),
+            access_groups: claims.access_groups.clone(),
+            email_verified: claims.email_verified,
+            session_id: claims.sid.clone(),
+        }
+    }
+}
+
+// Emissão e validação de tokens para as três APIs. Access e refresh usam
+// chaves diferentes: um refresh token nunca é aceito como access token.
+//
+// Access tokens podem ser assinados com chave assimétrica (ver `KeyRing`):
+// admin_api e viewer_api validam só com a chave pública. Refresh tokens
+// ficam sempre em HS256, porque só o auth_api os valida.
+pub struct TokenService {
+    access_keys: KeyRing,
+    refresh_keys: KeyRing,
+    access_ttl: Duration,
+    refresh_ttl: Duration,
+}
+
+impl TokenService {
+    pub fn new(
+        access_secret: &str,
+        refresh_secret: &str,
+        access_expiry_hours: u64,
+        refresh_expiry_days: u64,
+    ) -> Self {
+        Self::with_access_keys(
+            KeyRing::from_secret(access_secret),
+            refresh_secret,
+            access_expiry_hours,
+            refresh_expiry_days,
+        )
+    }
+
+    pub fn with_access_keys(
+        access_keys: KeyRing,
+        refresh_secret: &str,
+        access_expiry_hours: u64,
+        refresh_expiry_days: u64,
+    ) -> Self {
+        Self {
+            access_keys,
+            refresh_keys: KeyRing::from_secret(refresh_secret),
+            access_ttl: Duration::hours(access_expiry_hours as i64),
+            refresh_ttl: Duration::days(refresh_expiry_days as i64),
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Result<Self, AppError> {
+        Ok(Self::with_access_keys(
+            KeyRing::from_config(config)?,
+            &config.jwt_refresh_secret,
+            config.jwt_access_expiry_hours,
+            config.jwt_refresh_expiry_days,
+        ))
+    }
+
+    // Instância do processo, criada a partir do Config na primeira chamada
+    pub fn shared() -> &'static Self {
+        SHARED_TOKEN_SERVICE.get_or_init(|| {
+            let config = Config::from_env().expect("Erro ao carregar configuração");
+            Self::from_config(&config).expect("Erro ao carregar chaves JWT")
+        })
+    }
+
+    // Chaves públicas de verificação dos access tokens
+    pub fn jwks(&self) -> JwkSet {
+        self.access_keys.jwks()
+    }
+
+    // Tempo de vida do access token: depois disso uma revogação não precisa
+    // mais ser lembrada
+    pub fn access_ttl_seconds(&self) -> u64 {
+        self.access_ttl.num_seconds().max(0) as u64
+    }
+
+    pub fn issue_pair(&self, subject: &TokenSubject) -> Result<TokenPair, AppError> {
+        self.issue_pair_with_claims(subject).map(|issued| issued.pair)
+    }
+
+    // Emite o par e devolve também as claims dos dois tokens, que o
+    // RefreshTokenStore registra na sessão
+    pub fn issue_pair_with_claims(&self, subject: &TokenSubject) -> Result<IssuedTokens, AppError> {
+        let (access_token, access_claims) = self.issue(subject, TokenType::Access)?;
+        let (refresh_token, refresh_claims) = self.issue(subject, TokenType::Refresh)?;
+
+        let pair = TokenPair {
+            access_token,
+            refresh_token,
+            expires_in: access_claims.exp,
+            refresh_expires_in: refresh_claims.exp,
+        };
+
+        Ok(IssuedTokens {
+            pair,
+            access_claims,
+            refresh_claims,
+        })
+    }
+
+    pub fn issue_access_token(&self, subject: &TokenSubject) -> Result<String, AppError> {
+        self.issue(subject, TokenType::Access).map(|(token, _)| token)
+    }
+
+    fn issue(&self, subject: &TokenSubject, token_type: TokenType) -> Result<(String, Claims), AppError> {
+        let now = Utc::now();
+        let (keys, ttl) = match token_type {
+            TokenType::Access => (&self.access_keys, self.access_ttl),
+            TokenType::Refresh => (&self.refresh_keys, self.refresh_ttl),
+        };
+        let exp = (now + ttl).timestamp();
+
+        let claims = Claims {
+            sub: subject.user_id.clone(),
+            email: subject.email.clone(),
+            role: subject.role.clone(),
+            access_groups: subject.access_groups.clone(),
+            exp,
+            iat: now.timestamp(),
+            jti: Uuid::new_v4().to_string(),
+            token_type,
+            sid: subject.session_id.clone(),
+            email_verified: subject.email_verified,
+        };
+
+        let signing = keys.signing_key()?;
+        let mut header = Header::new(signing.algorithm);
+        header.kid = signing.kid.clone();
+
+        let token = encode(&header, &claims, &signing.encoding)
+            .map_err(|e| AppError::Internal(format!("Erro ao gerar token: {}", e)))?;
+
+        Ok((token, claims))
+    }
+
+    pub fn validate_access_token(&self, token: &str) -> Result<Claims, AppError> {
+        self.validate(token, TokenType::Access)
+    }
+
+    pub fn validate_refresh_token(&self, token: &str) -> Result<Claims, AppError> {
+        self.validate(token, TokenType::Refresh)
+    }
+
+    fn validate(&self, token: &str, expected: TokenType) -> Result<Claims, AppError> {
+        let keys = match expected {
+            TokenType::Access => &self.access_keys,
+            TokenType::Refresh => &self.refresh_keys,
+        };
+
+        let invalid = || AppError::Auth("Token inválido ou expirado".to_string());
+
+        // A chave é escolhida pelo `kid` e o algoritmo vem dela, nunca do header
+        let header = decode_header(token).map_err(|_| invalid())?;
+        let key = keys.find(header.kid.as_deref()).ok_or_else(invalid)?;
+        if header.alg != key.algorithm {
+            return Err(invalid());
+        }
+
+        let claims = decode::<Claims>(token, &key.decoding, &Validation::new(key.algorithm))
+            .map_err(|_| invalid())?
+            .claims;
+
+        if claims.token_type != expected {
+            return Err(AppError::Auth("Tipo de token inválido".to_string()));
+        }
+
+        Ok(claims)
+    }
+}
@@ -0,0 +1,152 @@
+use axum::{
+    extract::{FromRequest, FromRequestParts, Query, Request},
+    http::request::Parts,
+    Json,
+};
+use chrono::{Datelike, Utc};
+use serde::de::DeserializeOwned;
+use validator::{Validate, ValidationError, ValidationErrors, ValidationErrorsKind};
+use crate::errors::{AppError, FieldError};
+
+// Primeiro ano com filme registrado; `release_year` antes disso é erro de digitação
+pub const FIRST_RELEASE_YEAR: u32 = 1888;
+
+// Lançamentos já anunciados entram no catálogo antes da estreia
+pub const MAX_RELEASE_YEARS_AHEAD: u32 = 5;
+
+// Corpo JSON desserializado e validado pelas regras `#[validate(...)]` do
+// DTO. Falhas voltam como 422 com um item em `errors` por regra violada:
+//
+//     Json(payload): Json<RegisterRequestDto>            // antes
+//     ValidatedJson(payload): ValidatedJson<RegisterRequestDto>
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidatedJson<T>(pub T);
+
+impl<T, S> FromRequest<S> for ValidatedJson<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(value) = Json::<T>::from_request(request, state)
+            .await
+            .map_err(|rejection| AppError::Validation(rejection.body_text()))?;
+
+        value.validate()?;
+
+        Ok(Self(value))
+    }
+}
+
+// O mesmo para a query string
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidatedQuery<T>(pub T);
+
+impl<T, S> FromRequestParts<S> for ValidatedQuery<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Query(value) = Query::<T>::from_request_parts(parts, state)
+            .await
+            .map_err(|rejection| AppError::Validation(rejection.body_text()))?;
+
+        value.validate()?;
+
+        Ok(Self(value))
+    }
+}
+
+impl From<ValidationErrors> for AppError {
+    fn from(errors: ValidationErrors) -> Self {
+        let mut fields = Vec::new();
+        collect_field_errors(None, &errors, &mut fields);
+
+        AppError::InvalidFields(fields)
+    }
+}
+
+// Campos aninhados viram "endereco.cep" e itens de lista "itens[0].nome"
+fn collect_field_errors(prefix: Option<&str>, errors: &ValidationErrors, fields: &mut Vec<FieldError>) {
+    let mut entries: Vec<_> = errors.errors().iter().collect();
+    entries.sort_by_key(|(field, _)| *field);
+
+    for (field, kind) in entries {
+        let path = match prefix {
+            Some(prefix) => format!("{}.{}", prefix, field),
+            None => field.to_string(),
+        };
+
+        match kind {
+            ValidationErrorsKind::Field(field_errors) => {
+                fields.extend(field_errors.iter().map(|error| {
+                    FieldError::new(path.clone(), error.code.clone(), describe(error))
+                }));
+            }
+            ValidationErrorsKind::Struct(nested) => collect_field_errors(Some(&path), nested, fields),
+            ValidationErrorsKind::List(items) => {
+                for (index, nested) in items {
+                    collect_field_errors(Some(&format!("{}[{}]", path, index)), nested, fields);
+                }
+            }
+        }
+    }
+}
+
+// Mensagem da regra ou, sem ela, uma padrão montada com os parâmetros
+fn describe(error: &ValidationError) -> String {
+    if let Some(message) = &error.message {
+        return message.to_string();
+    }
+
+    let param = |name: &str| error.params.get(name).map(|value| value.to_string());
+
+    match error.code.as_ref() {
+        "email" => "E-mail inválido".to_string(),
+        "url" => "URL inválida".to_string(),
+        "required" => "Campo obrigatório".to_string(),
+        "length" => match (param("min"), param("max"), param("equal")) {
+            (_, _, Some(equal)) => format!("Deve ter exatamente {} caracteres", equal),
+            (Some(min), Some(max), _) => format!("Deve ter entre {} e {} caracteres", min, max),
+            (Some(min), None, _) => format!("Deve ter pelo menos {} caracteres", min),
+            (None, Some(max), _) => format!("Deve ter no máximo {} caracteres", max),
+            _ => "Tamanho inválido".to_string(),
+        },
+        "range" => match (param("min"), param("max")) {
+            (Some(min), Some(max)) => format!("Deve estar entre {} e {}", min, max),
+            (Some(min), None) => format!("Deve ser no mínimo {}", min),
+            (None, Some(max)) => format!("Deve ser no máximo {}", max),
+            _ => "Valor fora do intervalo".to_string(),
+        },
+        _ => "Valor inválido".to_string(),
+    }
+}
+
+// Regras usadas com `#[validate(custom(function = ...))]` nos DTOs
+
+pub fn not_blank(value: &str) -> Result<(), ValidationError> {
+    if value.trim().is_empty() {
+        return Err(ValidationError::new("blank").with_message("Não pode ficar em branco".into()));
+    }
+
+    Ok(())
+}
+
+pub fn release_year_in_range(year: u32) -> Result<(), ValidationError> {
+    let max = Utc::now().year() as u32 + MAX_RELEASE_YEARS_AHEAD;
+
+    if !(FIRST_RELEASE_YEAR..=max).contains(&year) {
+        let mut error = ValidationError::new("range")
+            .with_message(format!("Deve estar entre {} e {}", FIRST_RELEASE_YEAR, max).into());
+        error.add_param("min".into(), &FIRST_RELEASE_YEAR);
+        error.add_param("max".into(), &max);
+        return Err(error);
+    }
+
+    Ok(())
+}
@@ -1,16 +1,22 @@
 //https://www.tabnews.com.br/ddanielsantos/criando-uma-api-rest-com-rust
 
-use std::net::ToSocketAddrs;
-use axum::{Router, routing::get};
+use std::net::{SocketAddr, ToSocketAddrs};
+use axum::{Extension, Router, routing::get, Json};
 use tokio::net::TcpListener;
 use tracing_subscriber;
-use std::sync::Arc;
+use utoipa::OpenApi;
+use utoipa_axum::router::OpenApiRouter;
+use utoipa_swagger_ui::SwaggerUi;
 
 use api_rust::{
     config::Config,
     apps::{create_auth_router, create_admin_router, create_viewer_router},
-    libs::modules::AuthService,
-    infrastructure::db::DatabaseConnection,
+    libs::shared::database::connection::create_connection,
+    libs::shared::database::migrations::Migrator,
+    libs::shared::modules::token_service::TokenService,
+    libs::shared::cache::redis_cache::RedisCache,
+    libs::shared::modules::rate_limit::RateLimitLayer,
+    libs::shared::modules::openapi::ApiDoc,
 };
 
 #[tokio::main]
@@ -26,32 +32,41 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .next()
         .expect("Erro ao resolver localhost");
 
-    // Inicializar serviços
-    let auth_service = Arc::new(AuthService::new(
-        config.jwt_access_secret.clone(),
-        config.jwt_refresh_secret.clone(),
-        config.jwt_access_expiry_hours,
-        config.jwt_refresh_expiry_days,
-    ));
+    // Validar as chaves JWT na subida, e não na primeira requisição
+    TokenService::from_config(&config)?;
 
-    // Inicializar usuários de teste
-    auth_service.initialize_test_users().await;
+    // Conectar ao banco de dados; o backend vem do esquema do DATABASE_URL
+    let db = create_connection(&config).await?;
 
-    // Conectar ao banco de dados (em produção, usar conexão real)
-    let db = DatabaseConnection::new(&config.database_url).await?;
+    // Aplicar migrações pendentes (RUN_MIGRATIONS=true)
+    if config.run_migrations {
+        Migrator::new(db.clone(), &config.migrations_dir).up().await?;
+    }
 
-    // Criar router principal que integra automaticamente todas as APIs
+    // Integrar automaticamente as APIs da pasta apps; o documento OpenAPI é
+    // montado junto, a partir das mesmas rotas
+    let (apis, openapi) = OpenApiRouter::with_openapi(ApiDoc::openapi())
+        .nest("/auth", create_auth_router())
+        .nest("/admin", create_admin_router())
+        .nest("/viewer", create_viewer_router())
+        .split_for_parts();
+
+    // Criar router principal
     let app = Router::new()
         // Rotas públicas
         .route("/", get(|| async { "API Rust Monorepo - Status: OK" }))
         .route("/health", get(|| async { "Healthy" }))
-        
-        // Integrar automaticamente as APIs da pasta apps
-        .nest("/auth", create_auth_router())
-        .nest("/admin", create_admin_router())
-        .nest("/viewer", create_viewer_router())
-        
-        .with_state(db);
+        .route("/health/redis", get(|| async { Json(RedisCache::shared_health().await) }))
+
+        // Documento em /openapi.json e Swagger UI em /docs
+        .merge(SwaggerUi::new("/docs").url("/openapi.json", openapi))
+
+        .merge(apis)
+        .with_state(db.clone())
+        // O AuthGuard resolve permissões com a conexão das extensions
+        .layer(Extension(db))
+        // Políticas por rota do [rate_limit] do config/project.toml
+        .layer(RateLimitLayer::shared());
 
     let listener = TcpListener::bind(addr).await.unwrap();
 
@@ -66,15 +81,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("   - Admin API: http://localhost:{}/admin", config.api_port);
     println!("   - Viewer API: http://localhost:{}/viewer", config.api_port);
     println!("");
-    println!("🔐 Usuários de teste:");
-    println!("   - Admin: admin@example.com / admin123");
-    println!("   - User: user@example.com / user123");
+    println!("📚 Documentação:");
+    println!("   - Swagger UI: http://localhost:{}/docs", config.api_port);
+    println!("   - OpenAPI: http://localhost:{}/openapi.json", config.api_port);
     println!("");
     println!("✅ API Rust Monorepo iniciada com sucesso!");
     println!("🎯 Aguardando conexões em http://{}", addr);
 
     // Servir a aplicação
-    axum::serve(listener, app)
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
         .await
         .unwrap();
 